@@ -0,0 +1,324 @@
+//! `--analysis-cache`: reuse the parsed dependencies of a project whose manifests
+//! have not changed since the previous scan.
+//!
+//! Entries live in `~/.cache/license-checkr/analysis/{project-hash}.json` and are
+//! keyed by the size and mtime of every file directly in the project directory
+//! (manifests and lockfiles); any change there re-parses the whole project.
+//! Each ecosystem's entry also records every other path its analyzer read,
+//! checked for or listed: included build scripts
+//! and requirement files, installed packages under `node_modules` or a
+//! virtualenv, POMs in `~/.m2`, the Cargo registry, … A change to any of them
+//! re-parses that ecosystem. The entries also record the environment variables
+//! that move those locations (`$VIRTUAL_ENV`, `$CARGO_HOME`, …): when one
+//! differs, the whole cache is dropped. Only analyzer output is cached —
+//! classification and policy always run fresh.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::history::project_hash;
+use crate::models::{Dependency, Ecosystem};
+
+/// Size and modification time of one top-level project file.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct FileStamp {
+    name: String,
+    size: u64,
+    mtime_ns: u64,
+}
+
+/// Size and modification time of a file or directory an analyzer used; both
+/// `None` when it did not exist.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct InputStamp {
+    path: PathBuf,
+    size: Option<u64>,
+    mtime_ns: Option<u64>,
+}
+
+impl InputStamp {
+    fn of(path: &Path) -> Self {
+        let meta = std::fs::metadata(path).ok();
+        InputStamp {
+            path: path.to_path_buf(),
+            size: meta.as_ref().map(|m| m.len()),
+            mtime_ns: meta.as_ref().and_then(mtime_ns),
+        }
+    }
+
+    fn is_current(&self) -> bool {
+        *self == InputStamp::of(&self.path)
+    }
+}
+
+/// The cached output of one ecosystem's analyzer, with the inputs it depends on.
+#[derive(Debug, Serialize, Deserialize)]
+struct EcosystemEntry {
+    ecosystem: Ecosystem,
+    inputs: Vec<InputStamp>,
+    deps: Vec<Dependency>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    /// Version of license-checkr that wrote the cache; analyzer changes invalidate it.
+    version: String,
+    /// [`ENV_ROOTS`] that were set, with their values.
+    environment: BTreeMap<String, String>,
+    files: Vec<FileStamp>,
+    ecosystems: Vec<EcosystemEntry>,
+}
+
+/// Cached analyzer results for one project, loaded by [`AnalysisCache::open`].
+#[derive(Debug)]
+pub struct AnalysisCache {
+    file: PathBuf,
+    data: CacheFile,
+    /// Ecosystems served from the cache in this run.
+    pub hits: usize,
+}
+
+/// Environment variables the analyzers read to locate installed packages:
+/// the active virtualenv, the Cargo home, Maven installations and the home
+/// directory (`~/.m2`, `~/.cargo`).
+const ENV_ROOTS: &[&str] = &[
+    "VIRTUAL_ENV",
+    "CARGO_HOME",
+    "M2_HOME",
+    "MAVEN_HOME",
+    "HOME",
+    "USERPROFILE",
+];
+
+/// Location of the analysis cache for `project`:
+/// `~/.cache/license-checkr/analysis/{project-hash}.json`.
+pub fn cache_path(project: &Path) -> Result<PathBuf> {
+    let cache = dirs::cache_dir().context("Could not determine the user cache directory")?;
+    Ok(cache
+        .join("license-checkr")
+        .join("analysis")
+        .join(format!("{:016x}.json", project_hash(project))))
+}
+
+impl AnalysisCache {
+    /// Load the cache stored at `file` for `project`, dropping its entries when
+    /// the project's files or the [`ENV_ROOTS`] changed, and each ecosystem's
+    /// entry when one of its inputs did. A missing or unreadable cache starts empty.
+    pub fn open(file: &Path, project: &Path) -> Self {
+        let environment = environment();
+        let files = fingerprint(project);
+        let cached = std::fs::read_to_string(file)
+            .ok()
+            .and_then(|s| serde_json::from_str::<CacheFile>(&s).ok())
+            .filter(|c| {
+                c.version == env!("CARGO_PKG_VERSION")
+                    && c.environment == environment
+                    && c.files == files
+            });
+        AnalysisCache {
+            file: file.to_path_buf(),
+            data: CacheFile {
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                environment,
+                files,
+                ecosystems: cached
+                    .map(|c| c.ecosystems)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|entry| entry.inputs.iter().all(InputStamp::is_current))
+                    .collect(),
+            },
+            hits: 0,
+        }
+    }
+
+    /// The cached dependencies for `ecosystem`, or the result of `analyze`
+    /// (which is then cached with the inputs it noted).
+    pub fn analyze(
+        &mut self,
+        ecosystem: &Ecosystem,
+        analyze: impl FnOnce() -> Result<Vec<Dependency>>,
+    ) -> Result<Vec<Dependency>> {
+        if let Some(entry) = self
+            .data
+            .ecosystems
+            .iter()
+            .find(|e| e.ecosystem == *ecosystem)
+        {
+            self.hits += 1;
+            return Ok(entry.deps.clone());
+        }
+        let (deps, inputs) = super::recording_inputs(analyze);
+        let deps = deps?;
+        self.data.ecosystems.push(EcosystemEntry {
+            ecosystem: ecosystem.clone(),
+            inputs: inputs.iter().map(|path| InputStamp::of(path)).collect(),
+            deps: deps.clone(),
+        });
+        Ok(deps)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(dir) = self.file.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        std::fs::write(&self.file, serde_json::to_string(&self.data)?)
+            .with_context(|| format!("Failed to write analysis cache {}", self.file.display()))
+    }
+}
+
+/// The [`ENV_ROOTS`] set in this process.
+fn environment() -> BTreeMap<String, String> {
+    ENV_ROOTS
+        .iter()
+        .filter_map(|var| {
+            let value = std::env::var_os(var)?;
+            Some((var.to_string(), value.to_string_lossy().into_owned()))
+        })
+        .collect()
+}
+
+/// Stamps of the regular files directly in `project`, sorted by name.
+fn fingerprint(project: &Path) -> Vec<FileStamp> {
+    let Ok(entries) = std::fs::read_dir(project) else {
+        return Vec::new();
+    };
+    let mut files: Vec<FileStamp> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok().filter(|m| m.is_file())?;
+            Some(FileStamp {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                size: meta.len(),
+                mtime_ns: mtime_ns(&meta)?,
+            })
+        })
+        .collect();
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+    files
+}
+
+fn mtime_ns(meta: &std::fs::Metadata) -> Option<u64> {
+    let mtime = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some(u64::try_from(mtime.as_nanos()).unwrap_or(u64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::node::NodeAnalyzer;
+    use crate::analyzer::Analyzer;
+
+    #[test]
+    fn test_unchanged_project_hits_cache() {
+        let project = tempfile::TempDir::new().unwrap();
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        let file = cache_dir.path().join("analysis.json");
+        let manifest = project.path().join("package.json");
+        std::fs::write(&manifest, r#"{"dependencies": {"express": "^4.18.2"}}"#).unwrap();
+
+        let mut cache = AnalysisCache::open(&file, project.path());
+        let deps = cache
+            .analyze(&Ecosystem::Node, || {
+                NodeAnalyzer::new().analyze(project.path())
+            })
+            .unwrap();
+        assert_eq!(deps.len(), 1);
+        assert_eq!(cache.hits, 0);
+        cache.save().unwrap();
+
+        // Unchanged: served from the cache without running the analyzer
+        let mut cache = AnalysisCache::open(&file, project.path());
+        let deps = cache
+            .analyze(&Ecosystem::Node, || panic!("analyzer ran on a cache hit"))
+            .unwrap();
+        assert_eq!(deps[0].name, "express");
+        assert_eq!(cache.hits, 1);
+
+        // A changed manifest is parsed again
+        std::fs::write(
+            &manifest,
+            r#"{"dependencies": {"express": "^4.18.2", "lodash": "^4.17.21"}}"#,
+        )
+        .unwrap();
+        let mut cache = AnalysisCache::open(&file, project.path());
+        let deps = cache
+            .analyze(&Ecosystem::Node, || {
+                NodeAnalyzer::new().analyze(project.path())
+            })
+            .unwrap();
+        assert_eq!(deps.len(), 2);
+        assert_eq!(cache.hits, 0);
+    }
+
+    #[test]
+    fn test_changed_installed_package_invalidates_ecosystem() {
+        let project = tempfile::TempDir::new().unwrap();
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        let file = cache_dir.path().join("analysis.json");
+        std::fs::write(
+            project.path().join("package-lock.json"),
+            r#"{"lockfileVersion": 3, "packages": {"node_modules/express": {"version": "4.18.2"}}}"#,
+        )
+        .unwrap();
+        let analyze = |cache: &mut AnalysisCache| {
+            cache
+                .analyze(&Ecosystem::Node, || {
+                    NodeAnalyzer::new().analyze(project.path())
+                })
+                .unwrap()
+        };
+
+        let mut cache = AnalysisCache::open(&file, project.path());
+        assert_eq!(analyze(&mut cache)[0].license_raw, None);
+        cache.save().unwrap();
+
+        // Installing the package below the project directory is noticed
+        let installed = project.path().join("node_modules").join("express");
+        std::fs::create_dir_all(&installed).unwrap();
+        std::fs::write(installed.join("package.json"), r#"{"license": "MIT"}"#).unwrap();
+        let mut cache = AnalysisCache::open(&file, project.path());
+        assert_eq!(analyze(&mut cache)[0].license_raw.as_deref(), Some("MIT"));
+        assert_eq!(cache.hits, 0);
+    }
+
+    #[test]
+    fn test_changed_environment_root_invalidates_cache() {
+        let project = tempfile::TempDir::new().unwrap();
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        let file = cache_dir.path().join("analysis.json");
+        std::fs::write(
+            project.path().join("package.json"),
+            r#"{"dependencies": {"express": "^4.18.2"}}"#,
+        )
+        .unwrap();
+
+        let mut cache = AnalysisCache::open(&file, project.path());
+        cache
+            .analyze(&Ecosystem::Node, || {
+                NodeAnalyzer::new().analyze(project.path())
+            })
+            .unwrap();
+        cache.save().unwrap();
+
+        // A cache written under another virtualenv (or none) is not reused
+        let mut data: CacheFile =
+            serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        data.environment
+            .insert("VIRTUAL_ENV".to_string(), "/elsewhere/.venv".to_string());
+        std::fs::write(&file, serde_json::to_string(&data).unwrap()).unwrap();
+        let mut cache = AnalysisCache::open(&file, project.path());
+        let deps = cache
+            .analyze(&Ecosystem::Node, || {
+                NodeAnalyzer::new().analyze(project.path())
+            })
+            .unwrap();
+        assert_eq!(deps[0].name, "express");
+        assert_eq!(cache.hits, 0);
+    }
+}
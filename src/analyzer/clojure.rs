@@ -0,0 +1,232 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::Result;
+use regex::Regex;
+
+use crate::models::{Dependency, Ecosystem, LicenseRisk, LicenseSource, PolicyVerdict};
+
+/// Analyzer for Clojure projects using the Clojure CLI or Leiningen.
+///
+/// Supports two manifest formats:
+/// - `deps.edn` (`lib/name {:mvn/version "1.2.3"}` entries, including alias `:extra-deps`)
+/// - Leiningen `project.clj` (`:dependencies [[lib/name "1.2.3"]]` vectors, including profiles)
+///
+/// Clojure libraries are Maven artifacts, so names use the Java analyzer's
+/// `group:artifact` form and can be enriched from Maven Central.
+/// Git and local-root deps have no Maven coordinates and are skipped.
+pub struct ClojureAnalyzer;
+
+impl ClojureAnalyzer {
+    /// Create a new `ClojureAnalyzer`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl super::Analyzer for ClojureAnalyzer {
+    fn analyze(&self, path: &Path) -> Result<Vec<Dependency>> {
+        let mut deps: Vec<Dependency> = Vec::new();
+        let mut seen: HashSet<String> = HashSet::new();
+
+        let deps_edn = path.join("deps.edn");
+        if deps_edn.exists() {
+            for d in super::read_and_parse(&deps_edn, parse_deps_edn).unwrap_or_default() {
+                if seen.insert(format!("{}:{}", d.name, d.version)) {
+                    deps.push(d);
+                }
+            }
+        }
+
+        let project_clj = path.join("project.clj");
+        if project_clj.exists() {
+            for d in super::read_and_parse(&project_clj, parse_project_clj).unwrap_or_default() {
+                if seen.insert(format!("{}:{}", d.name, d.version)) {
+                    deps.push(d);
+                }
+            }
+        }
+
+        Ok(deps)
+    }
+}
+
+/// Parse a `deps.edn` file: every `lib {:mvn/version "x"}` entry anywhere in the map.
+fn parse_deps_edn(content: &str) -> Result<Vec<Dependency>> {
+    let content = strip_comments(content);
+    // Matches: org.clojure/clojure {:mvn/version "1.11.1" :exclusions [...]}
+    let re = Regex::new(
+        r#"([A-Za-z0-9_.\-]+(?:/[A-Za-z0-9_.\-]+)?)\s*\{[^{}]*?:mvn/version\s+"([^"]+)""#,
+    )?;
+    Ok(re
+        .captures_iter(&content)
+        .map(|caps| make_dep(&caps[1], &caps[2]))
+        .collect())
+}
+
+/// Parse a Leiningen `project.clj`: the `[lib "x"]` entries of every
+/// `:dependencies` vector (top level and profiles). `:plugins` are build tooling
+/// and ignored.
+fn parse_project_clj(content: &str) -> Result<Vec<Dependency>> {
+    let content = strip_comments(content);
+    // Matches: [org.clojure/clojure "1.11.1" :exclusions [...]]
+    let re = Regex::new(r#"\[\s*([A-Za-z0-9_.\-]+(?:/[A-Za-z0-9_.\-]+)?)\s+"([^"]+)""#)?;
+
+    let mut deps = Vec::new();
+    let mut rest = content.as_str();
+    while let Some(idx) = rest.find(":dependencies") {
+        rest = &rest[idx + ":dependencies".len()..];
+        let Some(vector) = balanced_vector(rest) else {
+            break;
+        };
+        // Skip the outer `[` so the first entry's `[` is matched by the regex
+        for caps in re.captures_iter(&vector[1..]) {
+            deps.push(make_dep(&caps[1], &caps[2]));
+        }
+        rest = &rest[vector.len()..];
+    }
+    Ok(deps)
+}
+
+/// The `[...]` vector starting at the first `[` of `s` (leading whitespace
+/// allowed), with brackets inside strings ignored. `None` when unbalanced or
+/// when `s` does not start with a vector.
+fn balanced_vector(s: &str) -> Option<&str> {
+    let start = s.len() - s.trim_start().len();
+    if !s[start..].starts_with('[') {
+        return None;
+    }
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in s[start..].char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&s[..start + i + 1]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Drop `;` line comments, ignoring semicolons inside strings.
+fn strip_comments(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    for line in content.lines() {
+        let mut in_string = false;
+        let mut escaped = false;
+        let mut end = line.len();
+        for (i, c) in line.char_indices() {
+            if in_string {
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' => escaped = true,
+                    '"' => in_string = false,
+                    _ => {}
+                }
+            } else if c == '"' {
+                in_string = true;
+            } else if c == ';' {
+                end = i;
+                break;
+            }
+        }
+        out.push_str(&line[..end]);
+        out.push('\n');
+    }
+    out
+}
+
+/// `group/artifact` → `group:artifact`; an unqualified `lib` means `lib/lib`.
+fn make_dep(lib: &str, version: &str) -> Dependency {
+    let (group, artifact) = lib.split_once('/').unwrap_or((lib, lib));
+    Dependency {
+        name: format!("{}:{}", group, artifact),
+        version: version.to_string(),
+        ecosystem: Ecosystem::Clojure,
+        license_raw: None,
+        license_spdx: None,
+        risk: LicenseRisk::Unknown,
+        verdict: PolicyVerdict::Warn,
+        source: LicenseSource::Unknown,
+        notes: Vec::new(),
+        is_new: false,
+        optional: false,
+        dev: false,
+        labels: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coords(deps: &[Dependency]) -> Vec<(&str, &str)> {
+        deps.iter()
+            .map(|d| (d.name.as_str(), d.version.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_deps_edn() {
+        let edn = r#"
+{:paths ["src"]
+ :deps {org.clojure/clojure {:mvn/version "1.11.1"}
+        cheshire {:mvn/version "5.12.0"}
+        ;; old-lib/old {:mvn/version "0.1.0"}
+        io.github.acme/tool {:git/url "https://github.com/acme/tool" :git/sha "abc123"}
+        ring/ring-core {:mvn/version "1.10.0" :exclusions [commons-io/commons-io]}}
+ :aliases {:test {:extra-deps {lambdaisland/kaocha {:mvn/version "1.87.1366"}}}}}
+"#;
+        let deps = parse_deps_edn(edn).unwrap();
+        assert_eq!(
+            coords(&deps),
+            [
+                ("org.clojure:clojure", "1.11.1"),
+                ("cheshire:cheshire", "5.12.0"),
+                ("ring:ring-core", "1.10.0"),
+                ("lambdaisland:kaocha", "1.87.1366"),
+            ]
+        );
+        assert!(deps.iter().all(|d| d.ecosystem == Ecosystem::Clojure));
+    }
+
+    #[test]
+    fn test_parse_project_clj() {
+        let clj = r#"
+(defproject my-app "0.1.0-SNAPSHOT"
+  :description "Example; with a semicolon"
+  :dependencies [[org.clojure/clojure "1.11.1"]
+                 [compojure "1.7.0" :exclusions [ring/ring-core]]
+                 ; [commented/out "9.9.9"]
+                 [ring/ring-defaults "0.4.0"]]
+  :plugins [[lein-ring "0.12.6"]]
+  :profiles {:dev {:dependencies [[ring/ring-mock "0.4.0"]]}})
+"#;
+        let deps = parse_project_clj(clj).unwrap();
+        assert_eq!(
+            coords(&deps),
+            [
+                ("org.clojure:clojure", "1.11.1"),
+                ("compojure:compojure", "1.7.0"),
+                ("ring:ring-defaults", "0.4.0"),
+                ("ring:ring-mock", "0.4.0"),
+            ]
+        );
+    }
+}
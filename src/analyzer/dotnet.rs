@@ -38,12 +38,10 @@ impl super::Analyzer for DotNetAnalyzer {
                     p.extension().and_then(|s| s.to_str()),
                     Some("csproj" | "fsproj")
                 ) {
-                    if let Ok(parsed) = parse_project_file(&p) {
-                        for d in parsed {
-                            let key = format!("{}:{}", d.name, d.version);
-                            if seen.insert(key) {
-                                deps.push(d);
-                            }
+                    for d in super::read_and_parse(&p, parse_project_file).unwrap_or_default() {
+                        let key = format!("{}:{}", d.name, d.version);
+                        if seen.insert(key) {
+                            deps.push(d);
                         }
                     }
                 }
@@ -53,12 +51,12 @@ impl super::Analyzer for DotNetAnalyzer {
         // Parse packages.config (legacy NuGet)
         let packages_config = path.join("packages.config");
         if packages_config.exists() {
-            if let Ok(parsed) = parse_packages_config(&packages_config) {
-                for d in parsed {
-                    let key = format!("{}:{}", d.name, d.version);
-                    if seen.insert(key) {
-                        deps.push(d);
-                    }
+            for d in
+                super::read_and_parse(&packages_config, parse_packages_config).unwrap_or_default()
+            {
+                let key = format!("{}:{}", d.name, d.version);
+                if seen.insert(key) {
+                    deps.push(d);
                 }
             }
         }
@@ -66,12 +64,10 @@ impl super::Analyzer for DotNetAnalyzer {
         // Parse paket.lock
         let paket_lock = path.join("paket.lock");
         if paket_lock.exists() {
-            if let Ok(parsed) = parse_paket_lock(&paket_lock) {
-                for d in parsed {
-                    let key = format!("{}:{}", d.name, d.version);
-                    if seen.insert(key) {
-                        deps.push(d);
-                    }
+            for d in super::read_and_parse(&paket_lock, parse_paket_lock).unwrap_or_default() {
+                let key = format!("{}:{}", d.name, d.version);
+                if seen.insert(key) {
+                    deps.push(d);
                 }
             }
         }
@@ -90,13 +86,17 @@ fn make_dep(name: &str, version: &str) -> Dependency {
         risk: LicenseRisk::Unknown,
         verdict: PolicyVerdict::Warn,
         source: LicenseSource::Unknown,
+        notes: Vec::new(),
+        is_new: false,
+        optional: false,
+        dev: false,
+        labels: Vec::new(),
     }
 }
 
 /// Parse `<PackageReference Include="..." Version="..." />` from `.csproj` / `.fsproj`.
-fn parse_project_file(path: &Path) -> Result<Vec<Dependency>> {
-    let content = std::fs::read_to_string(path)?;
-    let mut reader = Reader::from_str(&content);
+fn parse_project_file(content: &str) -> Result<Vec<Dependency>> {
+    let mut reader = Reader::from_str(content);
     reader.config_mut().trim_text(true);
 
     let mut deps = Vec::new();
@@ -135,9 +135,8 @@ fn parse_project_file(path: &Path) -> Result<Vec<Dependency>> {
 }
 
 /// Parse `<package id="..." version="..." />` from `packages.config`.
-fn parse_packages_config(path: &Path) -> Result<Vec<Dependency>> {
-    let content = std::fs::read_to_string(path)?;
-    let mut reader = Reader::from_str(&content);
+fn parse_packages_config(content: &str) -> Result<Vec<Dependency>> {
+    let mut reader = Reader::from_str(content);
     reader.config_mut().trim_text(true);
 
     let mut deps = Vec::new();
@@ -176,8 +175,7 @@ fn parse_packages_config(path: &Path) -> Result<Vec<Dependency>> {
 }
 
 /// Parse `paket.lock` — NUGET section entries like `    PackageName (1.2.3)`.
-fn parse_paket_lock(path: &Path) -> Result<Vec<Dependency>> {
-    let content = std::fs::read_to_string(path)?;
+fn parse_paket_lock(content: &str) -> Result<Vec<Dependency>> {
     // Matches lines like:     Newtonsoft.Json (13.0.1)
     let re = Regex::new(r"^\s{4}(\S+)\s+\(([^)]+)\)")?;
     let mut deps = Vec::new();
@@ -205,6 +203,7 @@ fn parse_paket_lock(path: &Path) -> Result<Vec<Dependency>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analyzer::read_and_parse;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -218,7 +217,7 @@ mod tests {
 </Project>"#;
         let mut f = NamedTempFile::with_suffix(".csproj").unwrap();
         write!(f, "{}", xml).unwrap();
-        let deps = parse_project_file(f.path()).unwrap();
+        let deps = read_and_parse(f.path(), parse_project_file).unwrap();
         assert_eq!(deps.len(), 2);
         assert_eq!(deps[0].name, "Newtonsoft.Json");
         assert_eq!(deps[0].version, "13.0.1");
@@ -235,7 +234,7 @@ mod tests {
 </packages>"#;
         let mut f = NamedTempFile::new().unwrap();
         write!(f, "{}", xml).unwrap();
-        let deps = parse_packages_config(f.path()).unwrap();
+        let deps = read_and_parse(f.path(), parse_packages_config).unwrap();
         assert_eq!(deps.len(), 2);
         assert_eq!(deps[0].name, "Newtonsoft.Json");
         assert_eq!(deps[0].version, "13.0.1");
@@ -256,7 +255,7 @@ GITHUB
 "#;
         let mut f = NamedTempFile::new().unwrap();
         write!(f, "{}", content).unwrap();
-        let deps = parse_paket_lock(f.path()).unwrap();
+        let deps = read_and_parse(f.path(), parse_paket_lock).unwrap();
         assert_eq!(deps.len(), 2);
         assert_eq!(deps[0].name, "Newtonsoft.Json");
         assert_eq!(deps[1].name, "Serilog");
@@ -0,0 +1,220 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::models::{Dependency, Ecosystem, LicenseRisk, LicenseSource, PolicyVerdict};
+
+/// Analyzer for Go modules.
+///
+/// Reads the `require` directives of `go.mod` (both the `require (...)` block and
+/// single-line `require path version` statements). When `go.sum` is present, a
+/// required version without a downloaded-module hash there is replaced by the
+/// one version that go.sum does hold content for.
+///
+/// Licenses are not recorded in either file, so every module stays unknown
+/// offline.
+pub struct GoAnalyzer;
+
+impl GoAnalyzer {
+    /// Create a new `GoAnalyzer`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl super::Analyzer for GoAnalyzer {
+    fn analyze(&self, path: &Path) -> Result<Vec<Dependency>> {
+        let go_mod = path.join("go.mod");
+        if !go_mod.exists() {
+            return Ok(Vec::new());
+        }
+        let Some(mut required) =
+            super::read_and_parse(&go_mod, |content| Ok(parse_requires(content)))
+        else {
+            return Ok(Vec::new());
+        };
+
+        // Without a readable go.sum the go.mod requirement versions are kept
+        let go_sum = path.join("go.sum");
+        if go_sum.exists() {
+            if let Some(downloaded) =
+                super::read_and_parse(&go_sum, |content| Ok(parse_go_sum(content)))
+            {
+                pin_versions(&mut required, &downloaded);
+            }
+        }
+
+        let mut seen: HashSet<String> = HashSet::new();
+        Ok(required
+            .into_iter()
+            .filter(|(module, version)| seen.insert(format!("{}@{}", module, version)))
+            .map(|(module, version)| make_dep(&module, &version))
+            .collect())
+    }
+}
+
+/// `(module, version)` of every `require` directive, in file order.
+fn parse_requires(content: &str) -> Vec<(String, String)> {
+    let mut requires = Vec::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+        if in_block {
+            if line == ")" {
+                in_block = false;
+            } else if let Some(req) = require_entry(line) {
+                requires.push(req);
+            }
+            continue;
+        }
+        let Some(rest) = line.strip_prefix("require") else {
+            continue;
+        };
+        let rest = rest.trim();
+        if rest == "(" {
+            in_block = true;
+        } else if let Some(req) = require_entry(rest) {
+            requires.push(req);
+        }
+    }
+    requires
+}
+
+/// `github.com/gin-gonic/gin v1.9.1` → `("github.com/gin-gonic/gin", "v1.9.1")`.
+fn require_entry(entry: &str) -> Option<(String, String)> {
+    let mut parts = entry.split_whitespace();
+    let module = parts.next()?.trim_matches('"');
+    let version = parts.next()?;
+    Some((module.to_string(), version.to_string()))
+}
+
+/// Versions per module whose full content is hashed in `go.sum` (lines without
+/// the `/go.mod` suffix, which only cover a module's go.mod file).
+fn parse_go_sum(content: &str) -> HashMap<String, Vec<String>> {
+    let mut downloaded: HashMap<String, Vec<String>> = HashMap::new();
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        let (Some(module), Some(version)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        if version.ends_with("/go.mod") {
+            continue;
+        }
+        let versions = downloaded.entry(module.to_string()).or_default();
+        if !versions.iter().any(|v| v == version) {
+            versions.push(version.to_string());
+        }
+    }
+    downloaded
+}
+
+/// Replace a required version that go.sum holds no content for with the single
+/// version it does; ambiguous or missing entries keep the go.mod version.
+fn pin_versions(required: &mut [(String, String)], downloaded: &HashMap<String, Vec<String>>) {
+    for (module, version) in required.iter_mut() {
+        match downloaded.get(module.as_str()).map(Vec::as_slice) {
+            Some(versions) if versions.contains(version) => {}
+            Some([pinned]) => *version = pinned.clone(),
+            _ => {}
+        }
+    }
+}
+
+fn make_dep(module: &str, version: &str) -> Dependency {
+    Dependency {
+        name: module.to_string(),
+        version: version.to_string(),
+        ecosystem: Ecosystem::Go,
+        license_raw: None,
+        license_spdx: None,
+        risk: LicenseRisk::Unknown,
+        verdict: PolicyVerdict::Warn,
+        source: LicenseSource::Unknown,
+        notes: Vec::new(),
+        is_new: false,
+        optional: false,
+        dev: false,
+        labels: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::Analyzer;
+
+    #[test]
+    fn test_parse_go_mod_requires() {
+        let go_mod = r#"
+module example.com/service
+
+go 1.21
+
+require github.com/google/uuid v1.3.0
+
+require (
+	github.com/gin-gonic/gin v1.9.1
+	golang.org/x/net v0.17.0 // indirect
+	// github.com/old/lib v0.1.0
+)
+
+replace example.com/internal => ../internal
+"#;
+        assert_eq!(
+            parse_requires(go_mod),
+            [
+                ("github.com/google/uuid".to_string(), "v1.3.0".to_string()),
+                ("github.com/gin-gonic/gin".to_string(), "v1.9.1".to_string()),
+                ("golang.org/x/net".to_string(), "v0.17.0".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_go_sum_pins_versions() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("go.mod"),
+            "module example.com/app\n\nrequire (\n\tgithub.com/gin-gonic/gin v1.9.1\n\tgithub.com/pkg/errors v0.9.0\n)\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("go.sum"),
+            "github.com/gin-gonic/gin v1.9.1 h1:abc=\n\
+             github.com/gin-gonic/gin v1.9.1/go.mod h1:def=\n\
+             github.com/pkg/errors v0.9.0/go.mod h1:ghi=\n\
+             github.com/pkg/errors v0.9.1 h1:jkl=\n",
+        )
+        .unwrap();
+
+        let deps = GoAnalyzer::new().analyze(tmp.path()).unwrap();
+        let found: Vec<(&str, &str)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("github.com/gin-gonic/gin", "v1.9.1"),
+                ("github.com/pkg/errors", "v0.9.1"),
+            ]
+        );
+        assert!(deps.iter().all(|d| d.ecosystem == Ecosystem::Go));
+    }
+
+    #[test]
+    fn test_unreadable_go_sum_keeps_go_mod_versions() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("go.mod"),
+            "module example.com/app\n\nrequire github.com/pkg/errors v0.9.0\n",
+        )
+        .unwrap();
+        std::fs::create_dir(tmp.path().join("go.sum")).unwrap();
+
+        let deps = GoAnalyzer::new().analyze(tmp.path()).unwrap();
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].version, "v0.9.0");
+    }
+}
@@ -0,0 +1,212 @@
+//! Package-level dependency graphs read from lockfiles, used by `--ignore-tree`
+//! to find the dependencies that are only pulled in through an ignored package.
+//!
+//! Only `Cargo.lock` and `package-lock.json` (lockfile v2/v3) record the edges
+//! between packages; other ecosystems have no graph, so ignoring a package there
+//! removes that package alone.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::models::Ecosystem;
+
+/// Edges between package names. Versions are not distinguished: two versions of
+/// the same package share one node.
+#[derive(Debug, Default)]
+pub struct DependencyGraph {
+    /// The project's own packages (workspace members, the npm root).
+    roots: Vec<String>,
+    edges: HashMap<String, HashSet<String>>,
+}
+
+impl DependencyGraph {
+    fn add_edge(&mut self, from: &str, to: &str) {
+        self.edges
+            .entry(from.to_string())
+            .or_default()
+            .insert(to.to_string());
+    }
+
+    /// `ignored` plus every package that can no longer be reached from the
+    /// roots once `ignored` is removed.
+    pub fn exclusive_subtree(&self, ignored: &str) -> HashSet<String> {
+        let mut removed: HashSet<String> = self
+            .reachable(None)
+            .difference(&self.reachable(Some(ignored)))
+            .cloned()
+            .collect();
+        removed.insert(ignored.to_string());
+        removed
+    }
+
+    /// Packages reachable from the roots without passing through `skip`.
+    fn reachable(&self, skip: Option<&str>) -> HashSet<String> {
+        let mut seen = HashSet::new();
+        let mut stack: Vec<&str> = self.roots.iter().map(String::as_str).collect();
+        while let Some(name) = stack.pop() {
+            if Some(name) == skip || !seen.insert(name.to_string()) {
+                continue;
+            }
+            if let Some(children) = self.edges.get(name) {
+                stack.extend(children.iter().map(String::as_str));
+            }
+        }
+        seen
+    }
+}
+
+/// Graphs for every lockfile directly under `path` that records edges.
+pub fn load(path: &Path) -> Result<Vec<(Ecosystem, DependencyGraph)>> {
+    let mut graphs = Vec::new();
+
+    let cargo_lock = path.join("Cargo.lock");
+    if cargo_lock.exists() {
+        if let Some(graph) = super::read_and_parse(&cargo_lock, from_cargo_lock) {
+            graphs.push((Ecosystem::Rust, graph));
+        }
+    }
+
+    let package_lock = path.join("package-lock.json");
+    if package_lock.exists() {
+        if let Some(graph) = super::read_and_parse(&package_lock, from_package_lock) {
+            graphs.push((Ecosystem::Node, graph));
+        }
+    }
+
+    Ok(graphs)
+}
+
+#[derive(Debug, Deserialize)]
+struct CargoLock {
+    #[serde(default)]
+    package: Vec<CargoLockPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CargoLockPackage {
+    name: String,
+    source: Option<String>,
+    /// `"name"`, `"name version"` or `"name version (source)"`.
+    #[serde(default)]
+    dependencies: Vec<String>,
+}
+
+/// Roots are the workspace members, i.e. packages without a `source`.
+fn from_cargo_lock(content: &str) -> Result<DependencyGraph> {
+    let lock: CargoLock = toml::from_str(content)?;
+    let mut graph = DependencyGraph::default();
+    for pkg in &lock.package {
+        if pkg.source.is_none() {
+            graph.roots.push(pkg.name.clone());
+        }
+        for dep in &pkg.dependencies {
+            let name = dep.split_whitespace().next().unwrap_or(dep);
+            graph.add_edge(&pkg.name, name);
+        }
+    }
+    Ok(graph)
+}
+
+/// The root is the `""` entry of `packages`; nested `node_modules/a/node_modules/b`
+/// entries are keyed by their last path segment.
+fn from_package_lock(content: &str) -> Result<DependencyGraph> {
+    let json: Value = serde_json::from_str(content)?;
+    let mut graph = DependencyGraph::default();
+    let Some(packages) = json.get("packages").and_then(Value::as_object) else {
+        return Ok(graph);
+    };
+
+    for (pkg_path, info) in packages {
+        let name = match pkg_path.rfind("node_modules/") {
+            Some(i) => &pkg_path[i + "node_modules/".len()..],
+            None if pkg_path.is_empty() => "",
+            // Workspace package sources ("packages/app") are linked under node_modules
+            None => continue,
+        };
+        if name.is_empty() {
+            graph.roots.push(String::new());
+        }
+        for key in [
+            "dependencies",
+            "devDependencies",
+            "optionalDependencies",
+            "peerDependencies",
+        ] {
+            // Only the root's devDependencies are installed
+            if key == "devDependencies" && !name.is_empty() {
+                continue;
+            }
+            if let Some(deps) = info.get(key).and_then(Value::as_object) {
+                for dep in deps.keys() {
+                    graph.add_edge(name, dep);
+                }
+            }
+        }
+    }
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(set: HashSet<String>) -> Vec<String> {
+        let mut names: Vec<String> = set.into_iter().collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_cargo_lock_exclusive_subtree() {
+        let lock = r#"
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = ["codegen", "serde"]
+
+[[package]]
+name = "codegen"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = ["only-codegen 2.0.0", "serde"]
+
+[[package]]
+name = "only-codegen"
+version = "2.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "serde"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+        let graph = from_cargo_lock(lock).unwrap();
+        assert_eq!(
+            names(graph.exclusive_subtree("codegen")),
+            ["codegen", "only-codegen"]
+        );
+        // Not in the graph: only the package itself
+        assert_eq!(names(graph.exclusive_subtree("missing")), ["missing"]);
+    }
+
+    #[test]
+    fn test_package_lock_exclusive_subtree() {
+        let lock = r#"{
+  "lockfileVersion": 3,
+  "packages": {
+    "": { "dependencies": { "express": "^4.0.0" }, "devDependencies": { "jest": "^29.0.0" } },
+    "node_modules/express": { "version": "4.18.2", "dependencies": { "debug": "2.6.9" } },
+    "node_modules/jest": { "version": "29.7.0", "dependencies": { "jest-cli": "29.7.0", "debug": "^4.0.0" } },
+    "node_modules/jest-cli": { "version": "29.7.0" },
+    "node_modules/jest/node_modules/debug": { "version": "4.3.4" },
+    "node_modules/debug": { "version": "2.6.9" }
+  }
+}"#;
+        let graph = from_package_lock(lock).unwrap();
+        assert_eq!(names(graph.exclusive_subtree("jest")), ["jest", "jest-cli"]);
+    }
+}
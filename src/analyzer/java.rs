@@ -1,17 +1,23 @@
-use std::collections::HashSet;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use quick_xml::events::Event;
 use quick_xml::Reader;
 use regex::Regex;
+use serde::Deserialize;
 
 use crate::models::{Dependency, Ecosystem, LicenseRisk, LicenseSource, PolicyVerdict};
+use crate::registry::maven::extract_license_from_pom;
 
 /// Analyzer for Java/Kotlin projects managed by Maven or Gradle.
 ///
-/// Parses `pom.xml`, `build.gradle` / `build.gradle.kts`, and `gradle.lockfile`.
-/// Dependencies are deduplicated by `group:artifact:version` key.
+/// Parses `pom.xml`, `build.gradle` / `build.gradle.kts` (plus scripts they
+/// `apply from:`, with `libs.*` aliases resolved from `gradle/libs.versions.toml`),
+/// and `gradle.lockfile`.
+/// Dependencies are deduplicated by `group:artifact:version` key. Licenses are
+/// read from the POMs of the local Maven repository when cached there (see
+/// [`license_from_m2_cache`]).
 pub struct JavaAnalyzer;
 
 impl JavaAnalyzer {
@@ -29,26 +35,35 @@ impl super::Analyzer for JavaAnalyzer {
         // Parse pom.xml
         let pom = path.join("pom.xml");
         if pom.exists() {
-            if let Ok(parsed) = parse_pom_xml(&pom) {
-                for d in parsed {
-                    let key = format!("{}:{}", d.name, d.version);
-                    if seen.insert(key) {
-                        deps.push(d);
-                    }
+            for d in super::read_and_parse(&pom, parse_pom_xml).unwrap_or_default() {
+                let key = format!("{}:{}", d.name, d.version);
+                if seen.insert(key) {
+                    deps.push(d);
                 }
             }
         }
 
+        // Version catalog referenced as `libs.*` by the build scripts
+        let catalog_file = path.join("gradle").join("libs.versions.toml");
+        super::track_input(&catalog_file);
+        let catalog = if catalog_file.exists() {
+            super::read_and_parse(&catalog_file, parse_version_catalog).unwrap_or_default()
+        } else {
+            VersionCatalog::default()
+        };
+
         // Parse build.gradle / build.gradle.kts
         for gradle_file in &["build.gradle", "build.gradle.kts"] {
             let gradle = path.join(gradle_file);
             if gradle.exists() {
-                if let Ok(parsed) = parse_build_gradle(&gradle) {
-                    for d in parsed {
-                        let key = format!("{}:{}", d.name, d.version);
-                        if seen.insert(key) {
-                            deps.push(d);
-                        }
+                for d in super::read_and_parse(&gradle, |content| {
+                    parse_build_gradle(content, &gradle, &catalog)
+                })
+                .unwrap_or_default()
+                {
+                    let key = format!("{}:{}", d.name, d.version);
+                    if seen.insert(key) {
+                        deps.push(d);
                     }
                 }
             }
@@ -57,12 +72,25 @@ impl super::Analyzer for JavaAnalyzer {
         // Parse gradle.lockfile if present
         let lockfile = path.join("gradle.lockfile");
         if lockfile.exists() {
-            if let Ok(parsed) = parse_gradle_lockfile(&lockfile) {
-                for d in parsed {
-                    let key = format!("{}:{}", d.name, d.version);
-                    if seen.insert(key) {
-                        deps.push(d);
-                    }
+            for d in super::read_and_parse(&lockfile, parse_gradle_lockfile).unwrap_or_default() {
+                let key = format!("{}:{}", d.name, d.version);
+                if seen.insert(key) {
+                    deps.push(d);
+                }
+            }
+        }
+
+        if let Some(repository) = m2_repository() {
+            for dep in &mut deps {
+                let Some((group, artifact)) = dep.name.split_once(':') else {
+                    continue;
+                };
+                if let Some(license) =
+                    license_from_m2_cache(&repository, group, artifact, &dep.version)
+                {
+                    dep.license_raw = Some(license.clone());
+                    dep.license_spdx = Some(license);
+                    dep.source = LicenseSource::Cache;
                 }
             }
         }
@@ -87,17 +115,99 @@ fn make_dep(group_id: &str, artifact_id: &str, version: &str) -> Dependency {
         risk: LicenseRisk::Unknown,
         verdict: PolicyVerdict::Warn,
         source: LicenseSource::Unknown,
+        notes: Vec::new(),
+        is_new: false,
+        optional: false,
+        dev: false,
+        labels: Vec::new(),
+    }
+}
+
+/// Look up the license of `group:artifact:version` in the local Maven
+/// `repository` (see [`m2_repository`]): the first `<license><name>` of
+/// `<group path>/<artifact>/<version>/<artifact>-<version>.pom`. `None` when the
+/// POM is not cached or declares no license; a parent POM's is not followed.
+fn license_from_m2_cache(
+    repository: &Path,
+    group: &str,
+    artifact: &str,
+    version: &str,
+) -> Option<String> {
+    if version == "*" || is_unresolved_version(version) {
+        return None;
+    }
+    let pom = repository
+        .join(group.replace('.', "/"))
+        .join(artifact)
+        .join(version)
+        .join(format!("{}-{}.pom", artifact, version));
+    extract_license_from_pom(&super::read_optional(&pom)?)
+}
+
+/// The local Maven repository: `<localRepository>` from `~/.m2/settings.xml`,
+/// else from the global `conf/settings.xml` of `$M2_HOME` / `$MAVEN_HOME`, else
+/// `~/.m2/repository`.
+fn m2_repository() -> Option<PathBuf> {
+    let home = dirs::home_dir()?;
+    let maven_homes: Vec<PathBuf> = ["M2_HOME", "MAVEN_HOME"]
+        .iter()
+        .filter_map(|var| std::env::var_os(var).map(PathBuf::from))
+        .collect();
+    Some(local_repository(&home, &maven_homes))
+}
+
+/// [`m2_repository`] for a given home directory and Maven installations;
+/// `${user.home}` in the setting expands to `home`.
+fn local_repository(home: &Path, maven_homes: &[PathBuf]) -> PathBuf {
+    let user_settings = home.join(".m2").join("settings.xml");
+    let global_settings = maven_homes
+        .iter()
+        .map(|dir| dir.join("conf").join("settings.xml"));
+    std::iter::once(user_settings)
+        .chain(global_settings)
+        .filter_map(|settings| super::read_optional(&settings))
+        .find_map(|xml| local_repository_setting(&xml))
+        .map(|dir| PathBuf::from(dir.replace("${user.home}", &home.to_string_lossy())))
+        .unwrap_or_else(|| home.join(".m2").join("repository"))
+}
+
+/// The `<localRepository>` of a Maven `settings.xml`, if set.
+fn local_repository_setting(xml: &str) -> Option<String> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+    let mut in_local_repository = false;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                in_local_repository = e.local_name().as_ref() == b"localRepository";
+            }
+            Ok(Event::Text(e)) if in_local_repository => {
+                let dir = e.unescape().ok()?.trim().to_string();
+                return (!dir.is_empty()).then_some(dir);
+            }
+            Ok(Event::End(_)) => in_local_repository = false,
+            Ok(Event::Eof) | Err(_) => return None,
+            _ => {}
+        }
     }
 }
 
 /// Parse `pom.xml` using quick-xml event API.
-fn parse_pom_xml(path: &Path) -> Result<Vec<Dependency>> {
-    let content = std::fs::read_to_string(path)?;
-    let mut reader = Reader::from_str(&content);
+///
+/// `test` / `provided` scoped dependencies are marked `dev` and
+/// `<optional>true</optional>` ones `optional`. `${...}` placeholders in
+/// coordinates are resolved from `<properties>` and the project's own
+/// `project.version` / `project.groupId` (inherited from `<parent>` when unset);
+/// undefined ones stay as written and get a note (see [`is_unresolved_version`]).
+fn parse_pom_xml(content: &str) -> Result<Vec<Dependency>> {
+    let mut reader = Reader::from_str(content);
     reader.config_mut().trim_text(true);
 
     let mut deps = Vec::new();
     let mut buf = Vec::new();
+    // Open elements, outermost first, to place `<properties>` entries
+    let mut stack: Vec<String> = Vec::new();
+    let mut properties: HashMap<String, String> = HashMap::new();
 
     let mut in_dependencies = false;
     let mut depth: u32 = 0;
@@ -108,6 +218,8 @@ fn parse_pom_xml(path: &Path) -> Result<Vec<Dependency>> {
     let mut group_id = String::new();
     let mut artifact_id = String::new();
     let mut version = String::new();
+    let mut scope = String::new();
+    let mut optional = String::new();
 
     loop {
         match reader.read_event_into(&mut buf) {
@@ -116,6 +228,7 @@ fn parse_pom_xml(path: &Path) -> Result<Vec<Dependency>> {
                 let name =
                     String::from_utf8_lossy(e.name().local_name().as_ref()).into_owned();
                 current_tag = name.clone();
+                stack.push(name.clone());
 
                 match name.as_str() {
                     "dependencies" if !in_dependency => {
@@ -127,6 +240,8 @@ fn parse_pom_xml(path: &Path) -> Result<Vec<Dependency>> {
                         group_id.clear();
                         artifact_id.clear();
                         version.clear();
+                        scope.clear();
+                        optional.clear();
                     }
                     _ => {}
                 }
@@ -137,7 +252,11 @@ fn parse_pom_xml(path: &Path) -> Result<Vec<Dependency>> {
 
                 if name == "dependency" && in_dependency {
                     if !artifact_id.is_empty() {
-                        deps.push(make_dep(&group_id, &artifact_id, &version));
+                        let mut dep = make_dep(&group_id, &artifact_id, &version);
+                        // `test` and `provided` artifacts are not shipped; no scope means `compile`
+                        dep.dev = matches!(scope.as_str(), "test" | "provided");
+                        dep.optional = optional == "true";
+                        deps.push(dep);
                     }
                     in_dependency = false;
                 } else if name == "dependencies" && depth == dependencies_depth {
@@ -146,16 +265,33 @@ fn parse_pom_xml(path: &Path) -> Result<Vec<Dependency>> {
 
                 depth = depth.saturating_sub(1);
                 current_tag.clear();
+                stack.pop();
             }
-            Ok(Event::Text(ref e)) => {
-                if in_dependency {
-                    let text = e.unescape().unwrap_or_default();
-                    match current_tag.as_str() {
-                        "groupId" => group_id = text.to_string(),
-                        "artifactId" => artifact_id = text.to_string(),
-                        "version" => version = text.to_string(),
-                        _ => {}
+            Ok(Event::Text(ref e)) if !in_dependency => {
+                let text = e.unescape().unwrap_or_default().trim().to_string();
+                let path: Vec<&str> = stack.iter().map(String::as_str).collect();
+                match path.as_slice() {
+                    ["project", "properties", key] => {
+                        properties.insert(key.to_string(), text);
+                    }
+                    ["project", key @ ("version" | "groupId")] => {
+                        properties.insert(format!("project.{}", key), text);
+                    }
+                    ["project", "parent", key @ ("version" | "groupId")] => {
+                        properties.insert(format!("project.parent.{}", key), text);
                     }
+                    _ => {}
+                }
+            }
+            Ok(Event::Text(ref e)) if in_dependency => {
+                let text = e.unescape().unwrap_or_default();
+                match current_tag.as_str() {
+                    "groupId" => group_id = text.to_string(),
+                    "artifactId" => artifact_id = text.to_string(),
+                    "version" => version = text.to_string(),
+                    "scope" => scope = text.trim().to_string(),
+                    "optional" => optional = text.trim().to_string(),
+                    _ => {}
                 }
             }
             Ok(Event::Eof) => break,
@@ -165,41 +301,315 @@ fn parse_pom_xml(path: &Path) -> Result<Vec<Dependency>> {
         buf.clear();
     }
 
+    // A module without its own version / groupId inherits the parent's
+    for key in ["version", "groupId"] {
+        if let Some(value) = properties.get(&format!("project.parent.{}", key)).cloned() {
+            properties
+                .entry(format!("project.{}", key))
+                .or_insert(value);
+        }
+    }
+    for dep in &mut deps {
+        dep.name = resolve_properties(&dep.name, &properties);
+        dep.version = resolve_properties(&dep.version, &properties);
+        if is_unresolved_version(&dep.version) {
+            dep.notes.push(format!(
+                "version {} uses a property not defined in pom.xml",
+                dep.version
+            ));
+        }
+    }
+
     Ok(deps)
 }
 
-/// Parse `build.gradle` or `build.gradle.kts` with regex.
-fn parse_build_gradle(path: &Path) -> Result<Vec<Dependency>> {
-    let content = std::fs::read_to_string(path)?;
+/// Replace every `${name}` in `value` defined in `properties`; values may refer to
+/// other properties. Undefined placeholders are kept.
+fn resolve_properties(value: &str, properties: &HashMap<String, String>) -> String {
+    let mut resolved = value.to_string();
+    // Bounded, so properties defined in terms of each other cannot loop forever
+    for _ in 0..10 {
+        let mut changed = false;
+        for (key, replacement) in properties {
+            let placeholder = format!("${{{}}}", key);
+            if resolved.contains(&placeholder) {
+                resolved = resolved.replace(&placeholder, replacement);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    resolved
+}
+
+/// Whether a Maven version still holds a `${...}` placeholder, which no
+/// repository URL can be built from; `--online` skips such artifacts.
+pub fn is_unresolved_version(version: &str) -> bool {
+    version.contains("${")
+}
+
+/// Parse `build.gradle` or `build.gradle.kts` with regex, following any
+/// `apply from:` script includes.
+fn parse_build_gradle(
+    content: &str,
+    path: &Path,
+    catalog: &VersionCatalog,
+) -> Result<Vec<Dependency>> {
+    let mut visited = HashSet::new();
+    parse_gradle_script(content, path, catalog, &mut visited)
+}
+
+/// Parse one Gradle script and recurse into the local scripts it applies.
+///
+/// Included paths are resolved relative to the including file. Remote (`http(s)://`)
+/// scripts and missing files are skipped, and an included script that can't be
+/// read or parsed is skipped with a warning; `visited` guards against include cycles.
+fn parse_gradle_script(
+    content: &str,
+    path: &Path,
+    catalog: &VersionCatalog,
+    visited: &mut HashSet<PathBuf>,
+) -> Result<Vec<Dependency>> {
+    let canonical = path.canonicalize()?;
+    if !visited.insert(canonical) {
+        return Ok(Vec::new());
+    }
+
     let mut deps = Vec::new();
 
+    // Matches: apply from: 'dependencies.gradle'
+    //          apply(from = "gradle/dependencies.gradle.kts")
+    let re_apply = Regex::new(r#"apply\s*\(?\s*from\s*[:=]\s*['"]([^'"]+)['"]"#)?;
+    let base = path.parent().unwrap_or(Path::new("."));
+    for caps in re_apply.captures_iter(content) {
+        let target = &caps[1];
+        if target.contains("://") {
+            continue;
+        }
+        let included = base.join(target);
+        super::track_input(&included);
+        if included.is_file() {
+            deps.extend(
+                super::read_and_parse(&included, |content| {
+                    parse_gradle_script(content, &included, catalog, visited)
+                })
+                .unwrap_or_default(),
+            );
+        }
+    }
+
     // Matches: implementation 'group:artifact:version'
     //          implementation "group:artifact:version"
+    //          implementation("group:artifact:version")   (Kotlin DSL)
     let re_shorthand =
-        Regex::new(r#"(?:implementation|api|compileOnly|runtimeOnly|testImplementation)\s+['"]([^'"]+):([^'"]+):([^'"]+)['"]"#)?;
+        Regex::new(r#"(implementation|api|compileOnly|runtimeOnly|testImplementation)(?:\s+|\s*\(\s*)['"]([^'"]+):([^'"]+):([^'"]+)['"]"#)?;
 
-    for caps in re_shorthand.captures_iter(&content) {
-        let group = &caps[1];
-        let artifact = &caps[2];
-        let version = caps[3].trim_end_matches('"').trim_end_matches('\'');
-        deps.push(make_dep(group, artifact, version));
+    for caps in re_shorthand.captures_iter(content) {
+        let group = &caps[2];
+        let artifact = &caps[3];
+        let version = caps[4].trim_end_matches('"').trim_end_matches('\'');
+        deps.push(gradle_dep(&caps[1], make_dep(group, artifact, version)));
     }
 
     // Matches: group: 'com.example', name: 'foo', version: '1.0'
     let re_map = Regex::new(
-        r#"(?:implementation|api|compileOnly|runtimeOnly|testImplementation)\s+group:\s*['"]([^'"]+)['"]\s*,\s*name:\s*['"]([^'"]+)['"]\s*,\s*version:\s*['"]([^'"]+)['"]"#,
+        r#"(implementation|api|compileOnly|runtimeOnly|testImplementation)\s+group:\s*['"]([^'"]+)['"]\s*,\s*name:\s*['"]([^'"]+)['"]\s*,\s*version:\s*['"]([^'"]+)['"]"#,
+    )?;
+
+    for caps in re_map.captures_iter(content) {
+        deps.push(gradle_dep(&caps[1], make_dep(&caps[2], &caps[3], &caps[4])));
+    }
+
+    // Matches: implementation(kotlin("stdlib"))
+    //          testImplementation(kotlin("test", "1.9.22"))
+    // `kotlin("x")` is `org.jetbrains.kotlin:kotlin-x`; without a version it
+    // follows the Kotlin plugin, which is not resolved here.
+    let re_kotlin = Regex::new(
+        r#"(implementation|api|compileOnly|runtimeOnly|testImplementation)\s*\(\s*kotlin\s*\(\s*"([^"]+)"\s*(?:,\s*"([^"]+)"\s*)?\)"#,
     )?;
 
-    for caps in re_map.captures_iter(&content) {
-        deps.push(make_dep(&caps[1], &caps[2], &caps[3]));
+    for caps in re_kotlin.captures_iter(content) {
+        let artifact = format!("kotlin-{}", &caps[2]);
+        let version = caps.get(3).map_or("*", |m| m.as_str());
+        let dep = make_dep("org.jetbrains.kotlin", &artifact, version);
+        deps.push(gradle_dep(&caps[1], dep));
+    }
+
+    // Matches: implementation(libs.guava)
+    //          testImplementation libs.junit.jupiter
+    //          implementation(platform(libs.spring.boot.bom))
+    //          implementation(libs.bundles.ktor)
+    let re_catalog = Regex::new(
+        r#"(implementation|api|compileOnly|runtimeOnly|testImplementation)\s*\(?\s*(?:platform\s*\(\s*)?libs\.([A-Za-z0-9_.]+)"#,
+    )?;
+
+    for caps in re_catalog.captures_iter(content) {
+        let configuration = &caps[1];
+        deps.extend(
+            catalog
+                .resolve(&caps[2])
+                .into_iter()
+                .map(|dep| gradle_dep(configuration, dep)),
+        );
     }
 
     Ok(deps)
 }
 
+/// Mark a dependency declared in a Gradle `configuration` as `dev` when that
+/// configuration only serves tests (`testImplementation`).
+fn gradle_dep(configuration: &str, mut dep: Dependency) -> Dependency {
+    dep.dev = configuration == "testImplementation";
+    dep
+}
+
+/// `gradle/libs.versions.toml`, keyed by accessor path: alias `guava-core` is
+/// `libs.guava.core` in build scripts, so `-` and `_` are stored as `.`.
+#[derive(Debug, Default)]
+struct VersionCatalog {
+    /// Accessor → (group, artifact, version); `*` when the catalog sets no version.
+    libraries: HashMap<String, (String, String, String)>,
+    /// `bundles.<accessor>` → library accessors.
+    bundles: HashMap<String, Vec<String>>,
+}
+
+impl VersionCatalog {
+    /// The dependencies behind `libs.<accessor>`: one library or a whole bundle.
+    fn resolve(&self, accessor: &str) -> Vec<Dependency> {
+        let libraries: Vec<&str> = match accessor.strip_prefix("bundles.") {
+            Some(bundle) => self
+                .bundles
+                .get(bundle)
+                .map(|b| b.iter().map(String::as_str).collect())
+                .unwrap_or_default(),
+            None => vec![accessor],
+        };
+        libraries
+            .into_iter()
+            .filter_map(|alias| self.libraries.get(alias))
+            .map(|(group, artifact, version)| make_dep(group, artifact, version))
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CatalogFile {
+    #[serde(default)]
+    versions: HashMap<String, CatalogVersion>,
+    #[serde(default)]
+    libraries: HashMap<String, CatalogLibrary>,
+    #[serde(default)]
+    bundles: HashMap<String, Vec<String>>,
+}
+
+/// `"1.0"` or a rich version `{ strictly = "…", require = "…", prefer = "…" }`;
+/// in `[libraries]` also `{ ref = "alias" }` (written `version.ref = "alias"`).
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum CatalogVersion {
+    Plain(String),
+    Rich {
+        #[serde(rename = "ref")]
+        reference: Option<String>,
+        strictly: Option<String>,
+        require: Option<String>,
+        prefer: Option<String>,
+    },
+}
+
+impl CatalogVersion {
+    /// The version itself; `None` for a `ref` to `[versions]`.
+    fn pinned(&self) -> Option<String> {
+        match self {
+            CatalogVersion::Plain(version) => Some(version.clone()),
+            CatalogVersion::Rich {
+                strictly,
+                require,
+                prefer,
+                ..
+            } => strictly.clone().or(require.clone()).or(prefer.clone()),
+        }
+    }
+}
+
+/// `"group:artifact:version"`, or a table with `module = "group:artifact"` or
+/// `group` + `name`, and an optional `version`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum CatalogLibrary {
+    Notation(String),
+    Table {
+        module: Option<String>,
+        group: Option<String>,
+        name: Option<String>,
+        version: Option<CatalogVersion>,
+    },
+}
+
+/// Parse a Gradle version catalog (`[versions]`, `[libraries]`, `[bundles]`).
+fn parse_version_catalog(content: &str) -> Result<VersionCatalog> {
+    let file: CatalogFile = toml::from_str(content)?;
+    let accessor = |alias: &str| alias.replace(['-', '_'], ".");
+    let version_of = |version: &CatalogVersion| match version {
+        CatalogVersion::Rich {
+            reference: Some(alias),
+            ..
+        } => file.versions.get(alias).and_then(CatalogVersion::pinned),
+        version => version.pinned(),
+    };
+
+    let mut catalog = VersionCatalog::default();
+    for (alias, library) in &file.libraries {
+        let coordinates = match library {
+            CatalogLibrary::Notation(notation) => {
+                let mut parts = notation.splitn(3, ':');
+                match (parts.next(), parts.next(), parts.next()) {
+                    (Some(group), Some(artifact), version) => Some((
+                        group.to_string(),
+                        artifact.to_string(),
+                        version.map(str::to_string),
+                    )),
+                    _ => None,
+                }
+            }
+            CatalogLibrary::Table {
+                module,
+                group,
+                name,
+                version,
+            } => {
+                let coordinates = match (module, group, name) {
+                    (Some(module), _, _) => module
+                        .split_once(':')
+                        .map(|(g, a)| (g.to_string(), a.to_string())),
+                    (None, Some(group), Some(name)) => Some((group.clone(), name.clone())),
+                    _ => None,
+                };
+                coordinates.map(|(group, artifact)| {
+                    (group, artifact, version.as_ref().and_then(version_of))
+                })
+            }
+        };
+        if let Some((group, artifact, version)) = coordinates {
+            let version = version.unwrap_or_else(|| "*".to_string());
+            catalog
+                .libraries
+                .insert(accessor(alias), (group, artifact, version));
+        }
+    }
+    for (bundle, aliases) in &file.bundles {
+        catalog.bundles.insert(
+            accessor(bundle),
+            aliases.iter().map(|a| accessor(a)).collect(),
+        );
+    }
+    Ok(catalog)
+}
+
 /// Parse `gradle.lockfile` — format: `group:artifact:version=...`
-fn parse_gradle_lockfile(path: &Path) -> Result<Vec<Dependency>> {
-    let content = std::fs::read_to_string(path)?;
+fn parse_gradle_lockfile(content: &str) -> Result<Vec<Dependency>> {
     let re = Regex::new(r"^([^:]+):([^:]+):([^=\s]+)")?;
     let mut deps = Vec::new();
 
@@ -219,6 +629,8 @@ fn parse_gradle_lockfile(path: &Path) -> Result<Vec<Dependency>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analyzer::read_and_parse;
+    use crate::analyzer::Analyzer;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -242,12 +654,185 @@ mod tests {
 
         let mut f = NamedTempFile::new().unwrap();
         write!(f, "{}", xml).unwrap();
-        let deps = parse_pom_xml(f.path()).unwrap();
+        let deps = read_and_parse(f.path(), parse_pom_xml).unwrap();
         assert_eq!(deps.len(), 2);
         assert_eq!(deps[0].name, "org.apache.commons:commons-lang3");
         assert_eq!(deps[0].version, "3.12.0");
     }
 
+    #[test]
+    fn test_parse_pom_xml_scopes_and_optional() {
+        let xml = r#"<?xml version="1.0"?>
+<project>
+  <dependencies>
+    <dependency>
+      <groupId>com.google.guava</groupId>
+      <artifactId>guava</artifactId>
+      <version>32.1.2-jre</version>
+    </dependency>
+    <dependency>
+      <groupId>org.slf4j</groupId>
+      <artifactId>slf4j-api</artifactId>
+      <version>2.0.9</version>
+      <scope>compile</scope>
+    </dependency>
+    <dependency>
+      <groupId>javax.servlet</groupId>
+      <artifactId>javax.servlet-api</artifactId>
+      <version>4.0.1</version>
+      <scope>provided</scope>
+    </dependency>
+    <dependency>
+      <groupId>org.junit.jupiter</groupId>
+      <artifactId>junit-jupiter</artifactId>
+      <version>5.10.0</version>
+      <scope>test</scope>
+    </dependency>
+    <dependency>
+      <groupId>com.h2database</groupId>
+      <artifactId>h2</artifactId>
+      <version>2.2.224</version>
+      <scope>runtime</scope>
+      <optional>true</optional>
+    </dependency>
+  </dependencies>
+</project>"#;
+
+        let mut f = NamedTempFile::new().unwrap();
+        write!(f, "{}", xml).unwrap();
+        let deps = read_and_parse(f.path(), parse_pom_xml).unwrap();
+        let flags: Vec<(&str, bool, bool)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.dev, d.optional))
+            .collect();
+        assert_eq!(
+            flags,
+            [
+                ("com.google.guava:guava", false, false),
+                ("org.slf4j:slf4j-api", false, false),
+                ("javax.servlet:javax.servlet-api", true, false),
+                ("org.junit.jupiter:junit-jupiter", true, false),
+                ("com.h2database:h2", false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_pom_xml_resolves_properties() {
+        let xml = r#"<?xml version="1.0"?>
+<project>
+  <parent>
+    <groupId>com.example</groupId>
+    <artifactId>parent</artifactId>
+    <version>1.4.0</version>
+  </parent>
+  <artifactId>service</artifactId>
+  <properties>
+    <spring.version>6.0.11</spring.version>
+    <spring-context.version>${spring.version}</spring-context.version>
+  </properties>
+  <dependencies>
+    <dependency>
+      <groupId>org.springframework</groupId>
+      <artifactId>spring-core</artifactId>
+      <version>${spring.version}</version>
+    </dependency>
+    <dependency>
+      <groupId>org.springframework</groupId>
+      <artifactId>spring-context</artifactId>
+      <version>${spring-context.version}</version>
+    </dependency>
+    <dependency>
+      <groupId>${project.groupId}</groupId>
+      <artifactId>common</artifactId>
+      <version>${project.version}</version>
+    </dependency>
+    <dependency>
+      <groupId>io.netty</groupId>
+      <artifactId>netty-all</artifactId>
+      <version>${netty.version}</version>
+    </dependency>
+  </dependencies>
+</project>"#;
+
+        let mut f = NamedTempFile::new().unwrap();
+        write!(f, "{}", xml).unwrap();
+        let deps = read_and_parse(f.path(), parse_pom_xml).unwrap();
+        let coords: Vec<(&str, &str)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str()))
+            .collect();
+        assert_eq!(
+            coords,
+            [
+                ("org.springframework:spring-core", "6.0.11"),
+                ("org.springframework:spring-context", "6.0.11"),
+                ("com.example:common", "1.4.0"),
+                ("io.netty:netty-all", "${netty.version}"),
+            ]
+        );
+        assert!(deps[..3].iter().all(|d| d.notes.is_empty()));
+        assert!(is_unresolved_version(&deps[3].version));
+        assert_eq!(deps[3].notes.len(), 1);
+    }
+
+    #[test]
+    fn test_license_from_m2_cache() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let home = tmp.path();
+        let custom = home.join("maven-repo");
+        std::fs::create_dir_all(home.join(".m2")).unwrap();
+        std::fs::write(
+            home.join(".m2").join("settings.xml"),
+            r#"<settings xmlns="http://maven.apache.org/SETTINGS/1.0.0">
+  <localRepository>${user.home}/maven-repo</localRepository>
+</settings>"#,
+        )
+        .unwrap();
+        assert_eq!(local_repository(home, &[]), custom);
+
+        let dir = custom.join("org/apache/commons/commons-lang3/3.12.0");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("commons-lang3-3.12.0.pom"),
+            r#"<project>
+  <licenses>
+    <license>
+      <name>Apache License, Version 2.0</name>
+      <url>https://www.apache.org/licenses/LICENSE-2.0.txt</url>
+    </license>
+  </licenses>
+</project>"#,
+        )
+        .unwrap();
+
+        let license = |version: &str| {
+            license_from_m2_cache(&custom, "org.apache.commons", "commons-lang3", version)
+        };
+        assert_eq!(
+            license("3.12.0").as_deref(),
+            Some("Apache License, Version 2.0")
+        );
+        assert_eq!(license("3.13.0"), None);
+        assert_eq!(license("${lang3.version}"), None);
+    }
+
+    #[test]
+    fn test_local_repository_defaults() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (home, maven) = (tmp.path().join("home"), tmp.path().join("maven"));
+        assert_eq!(local_repository(&home, &[]), home.join(".m2/repository"));
+
+        // A global settings file applies when the user has none
+        std::fs::create_dir_all(maven.join("conf")).unwrap();
+        std::fs::write(
+            maven.join("conf/settings.xml"),
+            "<settings><localRepository>/opt/m2</localRepository></settings>",
+        )
+        .unwrap();
+        assert_eq!(local_repository(&home, &[maven]), PathBuf::from("/opt/m2"));
+    }
+
     #[test]
     fn test_parse_build_gradle() {
         let content = r#"
@@ -259,7 +844,157 @@ dependencies {
 "#;
         let mut f = NamedTempFile::new().unwrap();
         write!(f, "{}", content).unwrap();
-        let deps = parse_build_gradle(f.path()).unwrap();
+        let deps = read_and_parse(f.path(), |content| {
+            parse_build_gradle(content, f.path(), &VersionCatalog::default())
+        })
+        .unwrap();
         assert_eq!(deps.len(), 3);
+        let dev: Vec<bool> = deps.iter().map(|d| d.dev).collect();
+        assert_eq!(dev, [false, false, true]);
+    }
+
+    #[test]
+    fn test_parse_build_gradle_kotlin_dsl() {
+        let content = r#"
+plugins {
+    kotlin("jvm") version "1.9.22"
+}
+
+dependencies {
+    implementation("com.squareup.okhttp3:okhttp:4.12.0")
+    api ( "org.slf4j:slf4j-api:2.0.9" )
+    testImplementation("org.junit.jupiter:junit-jupiter:5.10.1")
+    implementation(kotlin("stdlib"))
+    testImplementation(kotlin("test", "1.9.22"))
+}
+"#;
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("build.gradle.kts");
+        std::fs::write(&path, content).unwrap();
+
+        let deps = read_and_parse(&path, |content| {
+            parse_build_gradle(content, &path, &VersionCatalog::default())
+        })
+        .unwrap();
+        let found: Vec<(&str, &str)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("com.squareup.okhttp3:okhttp", "4.12.0"),
+                ("org.slf4j:slf4j-api", "2.0.9"),
+                ("org.junit.jupiter:junit-jupiter", "5.10.1"),
+                ("org.jetbrains.kotlin:kotlin-stdlib", "*"),
+                ("org.jetbrains.kotlin:kotlin-test", "1.9.22"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_build_gradle_version_catalog() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(tmp.path().join("gradle")).unwrap();
+        std::fs::write(
+            tmp.path().join("gradle/libs.versions.toml"),
+            r#"
+[versions]
+ktor = "2.3.4"
+junit = { strictly = "5.10.0" }
+
+[libraries]
+guava = "com.google.guava:guava:32.1.2-jre"
+ktor-server-core = { module = "io.ktor:ktor-server-core", version.ref = "ktor" }
+ktor-server-netty = { group = "io.ktor", name = "ktor-server-netty", version.ref = "ktor" }
+junit-jupiter = { module = "org.junit.jupiter:junit-jupiter", version.ref = "junit" }
+spring-boot-bom = { module = "org.springframework.boot:spring-boot-dependencies", version = "3.1.4" }
+slf4j = { module = "org.slf4j:slf4j-api" }
+
+[bundles]
+ktor = ["ktor-server-core", "ktor-server-netty"]
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("build.gradle.kts"),
+            r#"
+dependencies {
+    implementation(platform(libs.spring.boot.bom))
+    implementation(libs.guava)
+    implementation(libs.bundles.ktor)
+    implementation(libs.slf4j)
+    testImplementation(libs.junit.jupiter)
+    implementation(libs.not.in.catalog)
+}
+"#,
+        )
+        .unwrap();
+
+        let deps = JavaAnalyzer::new().analyze(tmp.path()).unwrap();
+        let coords: Vec<(&str, &str)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str()))
+            .collect();
+        assert_eq!(
+            coords,
+            [
+                ("org.springframework.boot:spring-boot-dependencies", "3.1.4"),
+                ("com.google.guava:guava", "32.1.2-jre"),
+                ("io.ktor:ktor-server-core", "2.3.4"),
+                ("io.ktor:ktor-server-netty", "2.3.4"),
+                ("org.slf4j:slf4j-api", "*"),
+                ("org.junit.jupiter:junit-jupiter", "5.10.0"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_build_gradle_follows_apply_from() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(tmp.path().join("gradle")).unwrap();
+        std::fs::write(
+            tmp.path().join("build.gradle"),
+            r#"
+apply from: 'gradle/dependencies.gradle'
+apply from: 'missing.gradle'
+apply from: 'https://example.com/remote.gradle'
+
+dependencies {
+    implementation 'org.slf4j:slf4j-api:2.0.9'
+}
+"#,
+        )
+        .unwrap();
+        // Includes itself and the root build file to exercise the cycle guard
+        std::fs::write(
+            tmp.path().join("gradle").join("dependencies.gradle"),
+            r#"
+apply from: 'dependencies.gradle'
+apply from: '../build.gradle'
+
+dependencies {
+    implementation "com.google.guava:guava:31.1-jre"
+    api group: 'org.apache.commons', name: 'commons-lang3', version: '3.12.0'
+}
+"#,
+        )
+        .unwrap();
+
+        let file = tmp.path().join("build.gradle");
+        let deps = read_and_parse(&file, |content| {
+            parse_build_gradle(content, &file, &VersionCatalog::default())
+        })
+        .unwrap();
+        let mut names: Vec<&str> = deps.iter().map(|d| d.name.as_str()).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "com.google.guava:guava",
+                "org.apache.commons:commons-lang3",
+                "org.slf4j:slf4j-api"
+            ]
+        );
     }
 }
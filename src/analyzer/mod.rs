@@ -1,13 +1,23 @@
-use std::path::Path;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 
+use crate::diagnostics::Diagnostic;
 use crate::models::Dependency;
 
+pub mod cache;
+pub mod clojure;
 pub mod dotnet;
+pub mod go;
+pub mod graph;
 pub mod java;
 pub mod node;
+pub mod ocaml;
+pub mod php;
 pub mod python;
+pub mod ruby;
 pub mod rust;
 
 /// Common interface for all ecosystem-specific dependency analyzers.
@@ -22,3 +32,62 @@ pub trait Analyzer {
     /// Parse manifests under `path` and return the discovered dependencies.
     fn analyze(&self, path: &Path) -> Result<Vec<Dependency>>;
 }
+
+/// Read the manifest at `file` and parse its contents with `parse`.
+///
+/// Every analyzer reads its manifests through this. An unreadable or malformed
+/// manifest is reported as a warning diagnostic naming the file and skipped
+/// (`None`), so the rest of the project is still scanned.
+fn read_and_parse<T>(file: &Path, parse: impl FnOnce(&str) -> Result<T>) -> Option<T> {
+    track_input(file);
+    let result = std::fs::read_to_string(file)
+        .context("failed to read")
+        .and_then(|content| parse(&content).context("failed to parse"));
+    match result {
+        Ok(parsed) => Some(parsed),
+        Err(e) => {
+            Diagnostic::warning(format!("skipping {}: {:#}", file.display(), e))
+                .file(file)
+                .emit();
+            None
+        }
+    }
+}
+
+/// Read a file the analysis may use but does not need (an installed package's
+/// metadata, a local registry cache, …); `None` when it is missing or unreadable.
+fn read_optional(file: &Path) -> Option<String> {
+    track_input(file);
+    std::fs::read_to_string(file).ok()
+}
+
+/// List the directory `dir`, noting it as an input of the running analyzer.
+fn read_dir(dir: &Path) -> std::io::Result<std::fs::ReadDir> {
+    track_input(dir);
+    std::fs::read_dir(dir)
+}
+
+thread_local! {
+    /// Paths noted by [`track_input`] while [`recording_inputs`] runs on this thread.
+    static INPUTS: RefCell<Option<BTreeSet<PathBuf>>> = const { RefCell::new(None) };
+}
+
+/// Note that the running analyzer's output depends on `path`: a file it reads or
+/// checks for, or a directory it lists. [`read_and_parse`], [`read_optional`] and
+/// [`read_dir`] note theirs; the analysis cache re-parses when any of them change.
+fn track_input(path: &Path) {
+    INPUTS.with_borrow_mut(|inputs| {
+        if let Some(inputs) = inputs {
+            inputs.insert(path.to_path_buf());
+        }
+    });
+}
+
+/// Run `analyze` on this thread, returning its result and every path it noted
+/// with [`track_input`].
+pub fn recording_inputs<T>(analyze: impl FnOnce() -> T) -> (T, BTreeSet<PathBuf>) {
+    let outer = INPUTS.replace(Some(BTreeSet::new()));
+    let result = analyze();
+    let inputs = INPUTS.replace(outer).unwrap_or_default();
+    (result, inputs)
+}
@@ -1,18 +1,24 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::path::Path;
 
 use anyhow::Result;
 use regex::Regex;
+use serde::Deserialize;
 use serde_json::Value;
 
 use crate::models::{Dependency, Ecosystem, LicenseRisk, LicenseSource, PolicyVerdict};
+use crate::registry::npm::license_field;
 
-/// Analyzer for Node.js projects managed by npm, Yarn, or pnpm.
+/// Analyzer for Node.js projects managed by npm, Yarn, or pnpm, and for Deno projects.
 ///
 /// Parses lock files in priority order:
-/// `package-lock.json` (v2/v3) → `yarn.lock` → `package.json` (fallback).
+/// `package-lock.json` (v2/v3) → `yarn.lock` → `pnpm-lock.yaml` → `deno.lock` →
+/// `package.json` / `deno.json` (fallback).
 /// License information embedded in `package-lock.json` or local `node_modules`
 /// is extracted and stored on the [`Dependency`](crate::models::Dependency).
+///
+/// Deno `npm:` packages are Node dependencies; `jsr:` packages use
+/// [`Ecosystem::Deno`].
 pub struct NodeAnalyzer;
 
 impl NodeAnalyzer {
@@ -30,12 +36,12 @@ impl super::Analyzer for NodeAnalyzer {
         // package-lock.json (most precise — pinned versions with optional license field)
         let lock = path.join("package-lock.json");
         if lock.exists() {
-            if let Ok(parsed) = parse_package_lock_json(&lock, path) {
-                for d in parsed {
-                    let key = format!("{}@{}", d.name, d.version);
-                    if seen.insert(key) {
-                        deps.push(d);
-                    }
+            for d in super::read_and_parse(&lock, |content| parse_package_lock_json(content, path))
+                .unwrap_or_default()
+            {
+                let key = format!("{}@{}", d.name, d.version);
+                if seen.insert(key) {
+                    deps.push(d);
                 }
             }
         }
@@ -43,12 +49,36 @@ impl super::Analyzer for NodeAnalyzer {
         // yarn.lock
         let yarn = path.join("yarn.lock");
         if yarn.exists() {
-            if let Ok(parsed) = parse_yarn_lock(&yarn) {
-                for d in parsed {
-                    let key = format!("{}@{}", d.name, d.version);
-                    if seen.insert(key) {
-                        deps.push(d);
-                    }
+            for d in super::read_and_parse(&yarn, parse_yarn_lock).unwrap_or_default() {
+                let key = format!("{}@{}", d.name, d.version);
+                if seen.insert(key) {
+                    deps.push(d);
+                }
+            }
+        }
+
+        // pnpm-lock.yaml
+        let pnpm = path.join("pnpm-lock.yaml");
+        if pnpm.exists() {
+            for d in super::read_and_parse(&pnpm, |content| parse_pnpm_lock(content, path))
+                .unwrap_or_default()
+            {
+                let key = format!("{}@{}", d.name, d.version);
+                if seen.insert(key) {
+                    deps.push(d);
+                }
+            }
+        }
+
+        // deno.lock (`npm:` and `jsr:` packages, pinned)
+        let deno_lock = path.join("deno.lock");
+        if deno_lock.exists() {
+            for d in super::read_and_parse(&deno_lock, |content| parse_deno_lock(content, path))
+                .unwrap_or_default()
+            {
+                let key = format!("{}@{}", d.name, d.version);
+                if seen.insert(key) {
+                    deps.push(d);
                 }
             }
         }
@@ -56,12 +86,21 @@ impl super::Analyzer for NodeAnalyzer {
         // package.json (no pinned versions, fall back to declared range)
         let pkg = path.join("package.json");
         if pkg.exists() && deps.is_empty() {
-            if let Ok(parsed) = parse_package_json(&pkg) {
-                for d in parsed {
-                    let key = format!("{}@{}", d.name, d.version);
-                    if seen.insert(key) {
-                        deps.push(d);
-                    }
+            for d in super::read_and_parse(&pkg, parse_package_json).unwrap_or_default() {
+                let key = format!("{}@{}", d.name, d.version);
+                if seen.insert(key) {
+                    deps.push(d);
+                }
+            }
+        }
+
+        // deno.json import map (declared ranges, when there is no deno.lock)
+        let deno_json = path.join("deno.json");
+        if deno_json.exists() && deps.is_empty() {
+            for d in super::read_and_parse(&deno_json, parse_deno_json).unwrap_or_default() {
+                let key = format!("{}@{}", d.name, d.version);
+                if seen.insert(key) {
+                    deps.push(d);
                 }
             }
         }
@@ -86,14 +125,19 @@ fn make_dep(name: String, version: String, license: Option<String>) -> Dependenc
         risk: LicenseRisk::Unknown,
         verdict: PolicyVerdict::Warn,
         source,
+        notes: Vec::new(),
+        is_new: false,
+        optional: false,
+        dev: false,
+        labels: Vec::new(),
     }
 }
 
-/// Parse `package-lock.json` v2/v3 (the `packages` map).
+/// Parse `package-lock.json` v2/v3 (the `packages` map), including the `dev`,
+/// `optional`, and `devOptional` flags npm records per entry.
 /// Also tries to read `node_modules/{pkg}/package.json` for offline license data.
-fn parse_package_lock_json(lock_path: &Path, project_root: &Path) -> Result<Vec<Dependency>> {
-    let content = std::fs::read_to_string(lock_path)?;
-    let json: Value = serde_json::from_str(&content)?;
+fn parse_package_lock_json(content: &str, project_root: &Path) -> Result<Vec<Dependency>> {
+    let json: Value = serde_json::from_str(content)?;
     let mut deps = Vec::new();
 
     if let Some(packages) = json.get("packages").and_then(|v| v.as_object()) {
@@ -117,10 +161,7 @@ fn parse_package_lock_json(lock_path: &Path, project_root: &Path) -> Result<Vec<
                 .to_string();
 
             // License may be present in lock entry
-            let license_in_lock = info
-                .get("license")
-                .and_then(|v| v.as_str())
-                .map(str::to_string);
+            let license_in_lock = license_field(info);
 
             // Try reading from node_modules for more complete info
             let license = license_in_lock.or_else(|| {
@@ -130,7 +171,13 @@ fn parse_package_lock_json(lock_path: &Path, project_root: &Path) -> Result<Vec<
                 read_license_from_package_json(&nm_pkg_json)
             });
 
-            deps.push(make_dep(name, version, license));
+            let flag = |key: &str| info.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+            let dev_optional = flag("devOptional");
+
+            let mut dep = make_dep(name, version, license);
+            dep.dev = flag("dev") || dev_optional;
+            dep.optional = flag("optional") || dev_optional;
+            deps.push(dep);
         }
     }
 
@@ -138,14 +185,13 @@ fn parse_package_lock_json(lock_path: &Path, project_root: &Path) -> Result<Vec<
 }
 
 fn read_license_from_package_json(path: &Path) -> Option<String> {
-    let content = std::fs::read_to_string(path).ok()?;
+    let content = super::read_optional(path)?;
     let json: Value = serde_json::from_str(&content).ok()?;
-    json.get("license").and_then(|v| v.as_str()).map(str::to_string)
+    license_field(&json)
 }
 
 /// Parse `yarn.lock` — custom line-based format.
-fn parse_yarn_lock(path: &Path) -> Result<Vec<Dependency>> {
-    let content = std::fs::read_to_string(path)?;
+fn parse_yarn_lock(content: &str) -> Result<Vec<Dependency>> {
     let mut deps = Vec::new();
     let mut lines = content.lines().peekable();
 
@@ -193,10 +239,238 @@ fn parse_yarn_lock(path: &Path) -> Result<Vec<Dependency>> {
     Ok(deps)
 }
 
+#[derive(Debug, Default, Deserialize)]
+struct PnpmLock {
+    #[serde(default)]
+    importers: BTreeMap<String, PnpmImporter>,
+    #[serde(default)]
+    packages: BTreeMap<String, PnpmPackage>,
+    /// v9: the resolved dependency graph, keyed like `packages` plus peer suffixes.
+    #[serde(default)]
+    snapshots: BTreeMap<String, PnpmPackage>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PnpmImporter {
+    #[serde(default)]
+    dependencies: BTreeMap<String, PnpmImporterDep>,
+    #[serde(default)]
+    optional_dependencies: BTreeMap<String, PnpmImporterDep>,
+    #[serde(default)]
+    dev_dependencies: BTreeMap<String, PnpmImporterDep>,
+}
+
+/// `version` (v5) or `{ specifier, version }` (v6+).
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum PnpmImporterDep {
+    Version(String),
+    Spec { version: String },
+}
+
+impl PnpmImporterDep {
+    fn version(&self) -> &str {
+        match self {
+            PnpmImporterDep::Version(version) | PnpmImporterDep::Spec { version } => version,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PnpmPackage {
+    /// v5/v6 only; v9 dropped the field.
+    #[serde(default)]
+    dev: bool,
+    #[serde(default)]
+    dependencies: BTreeMap<String, String>,
+    #[serde(default)]
+    optional_dependencies: BTreeMap<String, String>,
+}
+
+/// Parse `pnpm-lock.yaml` — the keys of its `packages` map.
+///
+/// Keys are `/name@version` (lockfile v6), `name@version` (v9) or `/name/version`
+/// (v5); peer-dependency suffixes such as `(react@18.2.0)` or `_react@18.2.0` are
+/// dropped. A `dev: true` field (v5/v6) marks `dev`; v9 has no such field, so a
+/// package there is `dev` when the `snapshots` graph reaches it only from the
+/// importers' `devDependencies`. Licenses are read from pnpm's virtual store in
+/// `node_modules/.pnpm`.
+fn parse_pnpm_lock(content: &str, project_root: &Path) -> Result<Vec<Dependency>> {
+    let lock: PnpmLock = serde_yaml::from_str(content)?;
+    let dev_only = pnpm_dev_only(&lock);
+
+    let mut deps = Vec::new();
+    for (key, package) in &lock.packages {
+        let Some((name, version)) = pnpm_package_key(key) else {
+            continue;
+        };
+        let store_dir = format!("{}@{}", name.replace('/', "+"), version);
+        let license = read_license_from_package_json(
+            &project_root
+                .join("node_modules/.pnpm")
+                .join(store_dir)
+                .join("node_modules")
+                .join(&name)
+                .join("package.json"),
+        );
+        let dev = package.dev || dev_only.contains(&(name.clone(), version.clone()));
+        let mut dep = make_dep(name, version, license);
+        dep.dev = dev;
+        deps.push(dep);
+    }
+
+    Ok(deps)
+}
+
+/// `(name, version)` of the v9 `snapshots` reachable from the importers'
+/// `devDependencies` but not from their `dependencies` / `optionalDependencies`.
+fn pnpm_dev_only(lock: &PnpmLock) -> HashSet<(String, String)> {
+    let reachable = |roots: Vec<String>| {
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut stack = roots;
+        while let Some(key) = stack.pop() {
+            let Some(snapshot) = lock.snapshots.get(&key) else {
+                continue;
+            };
+            if !seen.insert(key) {
+                continue;
+            }
+            for (name, version) in snapshot
+                .dependencies
+                .iter()
+                .chain(&snapshot.optional_dependencies)
+            {
+                stack.push(format!("{}@{}", name, version));
+            }
+        }
+        seen
+    };
+
+    let root = |(name, dep): (&String, &PnpmImporterDep)| format!("{}@{}", name, dep.version());
+    let prod_roots = lock
+        .importers
+        .values()
+        .flat_map(|i| i.dependencies.iter().chain(&i.optional_dependencies))
+        .map(root)
+        .collect();
+    let dev_roots = lock
+        .importers
+        .values()
+        .flat_map(|i| &i.dev_dependencies)
+        .map(root)
+        .collect();
+
+    let prod: HashSet<(String, String)> = reachable(prod_roots)
+        .iter()
+        .filter_map(|key| pnpm_package_key(key))
+        .collect();
+    reachable(dev_roots)
+        .iter()
+        .filter_map(|key| pnpm_package_key(key))
+        .filter(|id| !prod.contains(id))
+        .collect()
+}
+
+/// Name and version of a `pnpm-lock.yaml` package key (see [`parse_pnpm_lock`]).
+fn pnpm_package_key(key: &str) -> Option<(String, String)> {
+    let key = key.strip_prefix('/').unwrap_or(key);
+    let key = key.split('(').next().unwrap_or(key);
+    // The name ends at the first `@` (v6/v9) or `/` (v5) after any `@scope/`
+    let name_start = match key.strip_prefix('@') {
+        Some(scoped) => scoped.find('/')? + 2,
+        None => 0,
+    };
+    let sep = name_start + key[name_start..].find(['@', '/'])?;
+    let (name, version) = (&key[..sep], &key[sep + 1..]);
+    // v5 peer suffix: `18.2.0_react@18.2.0`
+    let version = version.split('_').next().unwrap_or(version);
+    if name.is_empty() || version.is_empty() {
+        return None;
+    }
+    Some((name.to_string(), version.to_string()))
+}
+
+/// Parse `deno.lock` (v3: sections under `packages`; v4+: top-level `npm` / `jsr`).
+/// Keys are resolved `name@version`, with npm peer-dependency suffixes
+/// (`react-dom@18.2.0_react@18.2.0`) dropped. `remote` URL imports are not packages
+/// and are skipped.
+fn parse_deno_lock(content: &str, project_root: &Path) -> Result<Vec<Dependency>> {
+    let json: Value = serde_json::from_str(content)?;
+    let sections = json.get("packages").unwrap_or(&json);
+    let mut deps = Vec::new();
+
+    for (section, ecosystem) in [("npm", Ecosystem::Node), ("jsr", Ecosystem::Deno)] {
+        let Some(packages) = sections.get(section).and_then(|v| v.as_object()) else {
+            continue;
+        };
+        for key in packages.keys() {
+            let Some((name, version)) = split_name_version(key) else {
+                continue;
+            };
+            let version = version.split('_').next().unwrap_or(version);
+            // With `nodeModulesDir`, npm packages are installed locally
+            let license = (ecosystem == Ecosystem::Node)
+                .then(|| {
+                    read_license_from_package_json(
+                        &project_root
+                            .join("node_modules")
+                            .join(name)
+                            .join("package.json"),
+                    )
+                })
+                .flatten();
+            let mut dep = make_dep(name.to_string(), version.to_string(), license);
+            dep.ecosystem = ecosystem.clone();
+            deps.push(dep);
+        }
+    }
+
+    Ok(deps)
+}
+
+/// Parse the `imports` map of `deno.json` for `npm:` and `jsr:` specifiers;
+/// versions are the declared ranges. URL imports are skipped.
+fn parse_deno_json(content: &str) -> Result<Vec<Dependency>> {
+    let json: Value = serde_json::from_str(content)?;
+    let mut deps = Vec::new();
+
+    if let Some(imports) = json.get("imports").and_then(|v| v.as_object()) {
+        for specifier in imports.values().filter_map(|v| v.as_str()) {
+            let (spec, ecosystem) = if let Some(spec) = specifier.strip_prefix("npm:") {
+                (spec, Ecosystem::Node)
+            } else if let Some(spec) = specifier.strip_prefix("jsr:") {
+                (spec, Ecosystem::Deno)
+            } else {
+                continue;
+            };
+            let spec = spec.trim_start_matches('/');
+            let (name, version) = match split_name_version(spec) {
+                // Drop a subpath: `preact@10/hooks`, `@std/path@^1.0.0/posix`
+                Some((name, version)) => (name, version.split('/').next().unwrap_or(version)),
+                // Unversioned: `npm:react`, `jsr:@std/path`
+                None => (spec, "*"),
+            };
+            let version = version.trim_start_matches(|c: char| !c.is_ascii_digit() && c != '*');
+            let mut dep = make_dep(name.to_string(), version.to_string(), None);
+            dep.ecosystem = ecosystem;
+            deps.push(dep);
+        }
+    }
+
+    Ok(deps)
+}
+
+/// Split `name@version` / `@scope/name@version` at the version separator.
+fn split_name_version(spec: &str) -> Option<(&str, &str)> {
+    let at = spec.get(1..)?.find('@')? + 1;
+    Some((&spec[..at], &spec[at + 1..]))
+}
+
 /// Parse `package.json` — extract `dependencies` and `devDependencies`.
-fn parse_package_json(path: &Path) -> Result<Vec<Dependency>> {
-    let content = std::fs::read_to_string(path)?;
-    let json: Value = serde_json::from_str(&content)?;
+fn parse_package_json(content: &str) -> Result<Vec<Dependency>> {
+    let json: Value = serde_json::from_str(content)?;
     let mut deps = Vec::new();
 
     for section in &["dependencies", "devDependencies"] {
@@ -207,7 +481,9 @@ fn parse_package_json(path: &Path) -> Result<Vec<Dependency>> {
                     .unwrap_or("*")
                     .trim_start_matches(|c: char| !c.is_ascii_digit() && c != '*')
                     .to_string();
-                deps.push(make_dep(name.clone(), version, None));
+                let mut dep = make_dep(name.clone(), version, None);
+                dep.dev = *section == "devDependencies";
+                deps.push(dep);
             }
         }
     }
@@ -218,6 +494,7 @@ fn parse_package_json(path: &Path) -> Result<Vec<Dependency>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analyzer::read_and_parse;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -235,7 +512,7 @@ mod tests {
 }"#;
         let mut f = NamedTempFile::new().unwrap();
         write!(f, "{}", json).unwrap();
-        let deps = parse_package_json(f.path()).unwrap();
+        let deps = read_and_parse(f.path(), parse_package_json).unwrap();
         assert_eq!(deps.len(), 3);
     }
 
@@ -258,9 +535,337 @@ mod tests {
 }"#;
         let mut f = NamedTempFile::new().unwrap();
         write!(f, "{}", json).unwrap();
-        let deps = parse_package_lock_json(f.path(), Path::new("/tmp")).unwrap();
+        let deps = read_and_parse(f.path(), |content| {
+            parse_package_lock_json(content, Path::new("/tmp"))
+        })
+        .unwrap();
         assert_eq!(deps.len(), 2);
         assert_eq!(deps[0].name, "express");
         assert_eq!(deps[0].license_raw, Some("MIT".to_string()));
     }
+
+    #[test]
+    fn test_truncated_package_lock_falls_back_to_package_json() {
+        use crate::analyzer::Analyzer;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("package-lock.json"), "{\n  \"packages\": {").unwrap();
+        std::fs::write(
+            tmp.path().join("package.json"),
+            r#"{ "dependencies": { "express": "^4.18.0" } }"#,
+        )
+        .unwrap();
+
+        let deps = NodeAnalyzer::new().analyze(tmp.path()).unwrap();
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "express");
+        assert_eq!(deps[0].version, "4.18.0");
+    }
+
+    #[test]
+    fn test_parse_package_lock_legacy_license_shapes() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let json = r#"{
+  "lockfileVersion": 3,
+  "packages": {
+    "node_modules/old-object": {
+      "version": "0.1.0",
+      "license": { "type": "BSD-3-Clause", "url": "https://example.com" }
+    },
+    "node_modules/old-array": { "version": "0.2.0" }
+  }
+}"#;
+        let lock = tmp.path().join("package-lock.json");
+        std::fs::write(&lock, json).unwrap();
+        let installed = tmp.path().join("node_modules/old-array");
+        std::fs::create_dir_all(&installed).unwrap();
+        std::fs::write(
+            installed.join("package.json"),
+            r#"{"licenses": [{"type": "MIT"}, {"type": "Apache-2.0"}]}"#,
+        )
+        .unwrap();
+
+        let deps = read_and_parse(&lock, |content| {
+            parse_package_lock_json(content, tmp.path())
+        })
+        .unwrap();
+        let license = |name: &str| {
+            deps.iter()
+                .find(|d| d.name == name)
+                .and_then(|d| d.license_raw.clone())
+        };
+        assert_eq!(license("old-object").as_deref(), Some("BSD-3-Clause"));
+        assert_eq!(license("old-array").as_deref(), Some("MIT OR Apache-2.0"));
+    }
+
+    #[test]
+    fn test_parse_package_lock_dev_and_optional_flags() {
+        let json = r#"{
+  "lockfileVersion": 3,
+  "packages": {
+    "": { "name": "my-app", "version": "1.0.0" },
+    "node_modules/express": { "version": "4.18.2", "license": "MIT" },
+    "node_modules/jest": { "version": "29.7.0", "license": "MIT", "dev": true },
+    "node_modules/fsevents": { "version": "2.3.3", "license": "MIT", "optional": true },
+    "node_modules/chokidar-fsevents": { "version": "1.0.0", "devOptional": true }
+  }
+}"#;
+        let mut f = NamedTempFile::new().unwrap();
+        write!(f, "{}", json).unwrap();
+        let deps = read_and_parse(f.path(), |content| {
+            parse_package_lock_json(content, Path::new("/tmp"))
+        })
+        .unwrap();
+        let flags: Vec<(&str, bool, bool)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.dev, d.optional))
+            .collect();
+        assert_eq!(
+            flags,
+            [
+                ("chokidar-fsevents", true, true),
+                ("express", false, false),
+                ("fsevents", false, true),
+                ("jest", true, false),
+            ]
+        );
+
+        let out = serde_json::to_value(&deps[2]).unwrap();
+        assert_eq!(out["optional"], true);
+        assert_eq!(out["dev"], false);
+    }
+
+    #[test]
+    fn test_parse_pnpm_lock() {
+        let lock = r#"lockfileVersion: '6.0'
+
+importers:
+  .:
+    dependencies:
+      express:
+        specifier: ^4.18.2
+        version: 4.18.2
+
+packages:
+
+  /express@4.18.2:
+    resolution: {integrity: sha512-abc}
+    engines: {node: '>= 0.10.0'}
+    dependencies:
+      debug: 2.6.9
+    dev: false
+
+  /@babel/core@7.23.0:
+    resolution: {integrity: sha512-def}
+    dev: true
+
+  /react-dom@18.2.0(react@18.2.0):
+    resolution: {integrity: sha512-ghi}
+    dev: false
+"#;
+        let tmp = tempfile::TempDir::new().unwrap();
+        let lock_path = tmp.path().join("pnpm-lock.yaml");
+        std::fs::write(&lock_path, lock).unwrap();
+        // License from pnpm's virtual store
+        let store = tmp
+            .path()
+            .join("node_modules/.pnpm/@babel+core@7.23.0/node_modules/@babel/core");
+        std::fs::create_dir_all(&store).unwrap();
+        std::fs::write(store.join("package.json"), r#"{"license": "MIT"}"#).unwrap();
+
+        let deps =
+            read_and_parse(&lock_path, |content| parse_pnpm_lock(content, tmp.path())).unwrap();
+        let found: Vec<(&str, &str, bool, Option<&str>)> = deps
+            .iter()
+            .map(|d| {
+                (
+                    d.name.as_str(),
+                    d.version.as_str(),
+                    d.dev,
+                    d.license_raw.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("@babel/core", "7.23.0", true, Some("MIT")),
+                ("express", "4.18.2", false, None),
+                ("react-dom", "18.2.0", false, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_pnpm_lock_v9_dev_from_snapshots() {
+        let lock = r#"lockfileVersion: '9.0'
+
+settings:
+  autoInstallPeers: true
+
+importers:
+
+  .:
+    dependencies:
+      express:
+        specifier: ^4.18.2
+        version: 4.18.2
+    devDependencies:
+      '@types/express':
+        specifier: ^4.17.21
+        version: 4.17.21
+      react-dom:
+        specifier: ^18.2.0
+        version: 18.2.0(react@18.2.0)
+
+packages:
+
+  '@types/express@4.17.21':
+    resolution: {integrity: sha512-aaa}
+
+  debug@2.6.9:
+    resolution: {integrity: sha512-bbb}
+
+  express@4.18.2:
+    resolution: {integrity: sha512-ccc}
+    engines: {node: '>= 0.10.0'}
+
+  react-dom@18.2.0:
+    resolution: {integrity: sha512-ddd}
+    peerDependencies:
+      react: ^18.2.0
+
+  react@18.2.0:
+    resolution: {integrity: sha512-eee}
+
+snapshots:
+
+  '@types/express@4.17.21':
+    dependencies:
+      debug: 2.6.9
+
+  debug@2.6.9: {}
+
+  express@4.18.2:
+    dependencies:
+      debug: 2.6.9
+
+  react-dom@18.2.0(react@18.2.0):
+    dependencies:
+      react: 18.2.0
+
+  react@18.2.0: {}
+"#;
+        let tmp = tempfile::TempDir::new().unwrap();
+        let lock_path = tmp.path().join("pnpm-lock.yaml");
+        std::fs::write(&lock_path, lock).unwrap();
+
+        let deps =
+            read_and_parse(&lock_path, |content| parse_pnpm_lock(content, tmp.path())).unwrap();
+        let found: Vec<(&str, &str, bool)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str(), d.dev))
+            .collect();
+        // `debug` is also reached from `express`, so it stays a production dependency
+        assert_eq!(
+            found,
+            [
+                ("@types/express", "4.17.21", true),
+                ("debug", "2.6.9", false),
+                ("express", "4.18.2", false),
+                ("react-dom", "18.2.0", true),
+                ("react", "18.2.0", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pnpm_package_keys() {
+        let key = |k: &str| pnpm_package_key(k).map(|(n, v)| format!("{}={}", n, v));
+        // v9
+        assert_eq!(key("@scope/pkg@1.2.3").as_deref(), Some("@scope/pkg=1.2.3"));
+        assert_eq!(key("lodash@4.17.21").as_deref(), Some("lodash=4.17.21"));
+        // v5
+        assert_eq!(key("/lodash/4.17.21").as_deref(), Some("lodash=4.17.21"));
+        assert_eq!(
+            key("/@emotion/react/11.10.5_@types+react@18.0.26").as_deref(),
+            Some("@emotion/react=11.10.5")
+        );
+        assert_eq!(
+            key("/react-dom/18.2.0_react@18.2.0").as_deref(),
+            Some("react-dom=18.2.0")
+        );
+        assert_eq!(key("@scope").as_deref(), None);
+    }
+
+    #[test]
+    fn test_parse_deno_lock() {
+        let json = r#"{
+  "version": "4",
+  "specifiers": {
+    "jsr:@std/path@1": "1.0.8",
+    "npm:@types/node@*": "22.5.4",
+    "npm:react-dom@18.2.0": "18.2.0_react@18.2.0",
+    "npm:react@18.2.0": "18.2.0"
+  },
+  "jsr": {
+    "@std/path@1.0.8": { "integrity": "sha512-…" }
+  },
+  "npm": {
+    "@types/node@22.5.4": { "integrity": "sha512-…" },
+    "react-dom@18.2.0_react@18.2.0": { "integrity": "sha512-…" },
+    "react@18.2.0": { "integrity": "sha512-…" }
+  },
+  "remote": {
+    "https://deno.land/std@0.200.0/path/mod.ts": "f06503…"
+  }
+}"#;
+        let mut f = NamedTempFile::new().unwrap();
+        write!(f, "{}", json).unwrap();
+        let deps = read_and_parse(f.path(), |content| {
+            parse_deno_lock(content, Path::new("/tmp"))
+        })
+        .unwrap();
+        let found: Vec<(&str, &str, String)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str(), d.ecosystem.to_string()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("@types/node", "22.5.4", "Node".to_string()),
+                ("react-dom", "18.2.0", "Node".to_string()),
+                ("react", "18.2.0", "Node".to_string()),
+                ("@std/path", "1.0.8", "Deno".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_deno_json_imports() {
+        let json = r#"{
+  "imports": {
+    "react": "npm:react@^18.2.0",
+    "preact/hooks": "npm:preact@10/hooks",
+    "@std/path": "jsr:@std/path@^1.0.0",
+    "oak": "https://deno.land/x/oak@v12.6.1/mod.ts"
+  }
+}"#;
+        let mut f = NamedTempFile::new().unwrap();
+        write!(f, "{}", json).unwrap();
+        let mut found: Vec<(String, String)> = read_and_parse(f.path(), parse_deno_json)
+            .unwrap()
+            .into_iter()
+            .map(|d| (d.name, d.version))
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            [
+                ("@std/path".to_string(), "1.0.0".to_string()),
+                ("preact".to_string(), "10".to_string()),
+                ("react".to_string(), "18.2.0".to_string()),
+            ]
+        );
+    }
 }
@@ -0,0 +1,399 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use regex::Regex;
+
+use crate::models::{Dependency, Ecosystem, LicenseRisk, LicenseSource, PolicyVerdict};
+
+/// Analyzer for OCaml projects using opam and dune.
+///
+/// Supports two manifest formats:
+/// - `*.opam` files (`depends: [ "lwt" {>= "5.0"} ]`)
+/// - `dune-project` (`(package (depends (lwt (>= 5.0))))` stanzas)
+///
+/// Neither pins exact versions, so the version field holds the constraint
+/// (e.g. `>= 5.0 & < 6.0`), `*` when unconstrained, or the version itself for
+/// `= "x"` pins. `{with-test}` / `{with-doc}` dependencies are marked `dev`.
+/// The `ocaml` compiler package and packages defined by the project itself are
+/// skipped.
+pub struct OCamlAnalyzer;
+
+impl OCamlAnalyzer {
+    /// Create a new `OCamlAnalyzer`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl super::Analyzer for OCamlAnalyzer {
+    fn analyze(&self, path: &Path) -> Result<Vec<Dependency>> {
+        let opam_files = opam_files(path);
+        let dune_project = path.join("dune-project");
+        // (package names it defines, its dependencies)
+        let dune = if dune_project.exists() {
+            super::read_and_parse(&dune_project, |content| {
+                Ok((dune_package_names(content), parse_dune_project(content)))
+            })
+        } else {
+            None
+        };
+
+        // Packages defined by this project depend on each other; they are not third-party.
+        let mut local: HashSet<String> = opam_files
+            .iter()
+            .filter_map(|p| p.file_stem().and_then(|s| s.to_str()).map(str::to_string))
+            .collect();
+        if let Some((names, _)) = &dune {
+            local.extend(names.iter().cloned());
+        }
+
+        let mut parsed = Vec::new();
+        // `dune-project` usually generates the `.opam` files, so parse those first
+        // and let them win on duplicates.
+        for file in &opam_files {
+            parsed.extend(super::read_and_parse(file, parse_opam).unwrap_or_default());
+        }
+        if let Some((_, deps)) = dune {
+            parsed.extend(deps);
+        }
+
+        let mut seen: HashSet<String> = HashSet::new();
+        Ok(parsed
+            .into_iter()
+            .filter(|d| !local.contains(&d.name) && seen.insert(d.name.clone()))
+            .collect())
+    }
+}
+
+/// `*.opam` files directly under `path`, sorted by name.
+fn opam_files(path: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|s| s.to_str()) == Some("opam") && p.is_file())
+        .collect();
+    files.sort();
+    files
+}
+
+/// Parse the `depends: [...]` field of an opam file.
+fn parse_opam(content: &str) -> Result<Vec<Dependency>> {
+    let content = strip_comments(content);
+    let Some(start) = content.find("depends:") else {
+        return Ok(Vec::new());
+    };
+    let rest = &content[start + "depends:".len()..];
+    let Some(list) = balanced(rest, '[', ']') else {
+        return Ok(Vec::new());
+    };
+
+    // Matches: "lwt" {>= "5.0" & < "6.0"}  (the filter block is optional)
+    let re = Regex::new(r#""([^"]+)"\s*(?:\{([^}]*)\})?"#)?;
+    let mut deps = Vec::new();
+    for caps in re.captures_iter(&list[1..list.len() - 1]) {
+        let name = &caps[1];
+        if name == "ocaml" {
+            continue;
+        }
+        let filter = caps.get(2).map_or("", |m| m.as_str());
+        let mut constraint = Vec::new();
+        let mut dev = false;
+        for token in filter.split_whitespace() {
+            let dangling = matches!(
+                constraint.last().map(String::as_str),
+                None | Some("&" | "|")
+            );
+            match token {
+                "with-test" | "with-doc" => dev = true,
+                // Filter keywords carry no version information; neither do the
+                // connectives left dangling by dropping them
+                "build" | "post" | "dev" | "with-dev-setup" => {}
+                "&" | "|" if dangling => {}
+                other => constraint.push(other.trim_matches('"').to_string()),
+            }
+        }
+        while constraint.last().is_some_and(|t| t == "&" || t == "|") {
+            constraint.pop();
+        }
+        deps.push(make_dep(name, &version_from(&constraint.join(" ")), dev));
+    }
+    Ok(deps)
+}
+
+/// Parse the `(depends ...)` fields of every `(package ...)` stanza in a `dune-project`.
+fn parse_dune_project(content: &str) -> Vec<Dependency> {
+    let mut deps = Vec::new();
+    for stanza in sexps(&strip_comments(content)) {
+        let Sexp::List(items) = stanza else { continue };
+        if items.first() != Some(&Sexp::Atom("package".to_string())) {
+            continue;
+        }
+        for field in &items[1..] {
+            let Sexp::List(field) = field else { continue };
+            if field.first() != Some(&Sexp::Atom("depends".to_string())) {
+                continue;
+            }
+            for entry in &field[1..] {
+                let (name, constraints) = match entry {
+                    Sexp::Atom(name) => (name.as_str(), &[][..]),
+                    Sexp::List(list) => match list.first() {
+                        Some(Sexp::Atom(name)) => (name.as_str(), &list[1..]),
+                        _ => continue,
+                    },
+                };
+                if name == "ocaml" {
+                    continue;
+                }
+                let dev = constraints
+                    .iter()
+                    .any(|c| matches!(c, Sexp::Atom(a) if a == ":with-test" || a == ":with-doc"));
+                let rendered: Vec<String> =
+                    constraints.iter().filter_map(render_constraint).collect();
+                deps.push(make_dep(name, &version_from(&rendered.join(" & ")), dev));
+            }
+        }
+    }
+    deps
+}
+
+/// Names declared by `(package (name x))` stanzas in a `dune-project`.
+fn dune_package_names(content: &str) -> Vec<String> {
+    sexps(&strip_comments(content))
+        .into_iter()
+        .filter_map(|stanza| match stanza {
+            Sexp::List(items) if items.first() == Some(&Sexp::Atom("package".to_string())) => {
+                items.into_iter().find_map(|field| match field {
+                    Sexp::List(mut f) if f.len() == 2 && f[0] == Sexp::Atom("name".to_string()) => {
+                        match f.pop() {
+                            Some(Sexp::Atom(name)) => Some(name),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Render a dune constraint such as `(>= 1.0)` or `(and (>= 1.0) (< 2.0))` in
+/// opam syntax; `:with-test`-style variables yield `None`.
+fn render_constraint(sexp: &Sexp) -> Option<String> {
+    match sexp {
+        Sexp::Atom(_) => None,
+        Sexp::List(items) => match items.as_slice() {
+            [Sexp::Atom(op), rest @ ..] if op == "and" || op == "or" => {
+                let sep = if op == "and" { " & " } else { " | " };
+                let parts: Vec<String> = rest.iter().filter_map(render_constraint).collect();
+                (!parts.is_empty()).then(|| parts.join(sep))
+            }
+            [Sexp::Atom(op), Sexp::Atom(version)] => Some(format!("{} {}", op, version)),
+            _ => None,
+        },
+    }
+}
+
+/// `= 1.2.3` is an exact version; an empty constraint is `*`; anything else is
+/// kept as the constraint text.
+fn version_from(constraint: &str) -> String {
+    match constraint.strip_prefix("= ") {
+        Some(exact) if !exact.contains(' ') => exact.to_string(),
+        _ if constraint.is_empty() => "*".to_string(),
+        _ => constraint.to_string(),
+    }
+}
+
+/// A minimal s-expression, enough for `dune-project` files.
+#[derive(Debug, PartialEq)]
+enum Sexp {
+    Atom(String),
+    List(Vec<Sexp>),
+}
+
+/// Parse all top-level s-expressions in `content`. Unbalanced trailing input is dropped.
+fn sexps(content: &str) -> Vec<Sexp> {
+    let mut stack: Vec<Vec<Sexp>> = vec![Vec::new()];
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '(' => stack.push(Vec::new()),
+            ')' => {
+                if stack.len() > 1 {
+                    let list = stack.pop().unwrap_or_default();
+                    if let Some(parent) = stack.last_mut() {
+                        parent.push(Sexp::List(list));
+                    }
+                }
+            }
+            '"' => {
+                let mut s = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => s.extend(chars.next()),
+                        '"' => break,
+                        _ => s.push(c),
+                    }
+                }
+                if let Some(top) = stack.last_mut() {
+                    top.push(Sexp::Atom(s));
+                }
+            }
+            c if c.is_whitespace() => {}
+            c => {
+                let mut s = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' || c == '"' {
+                        break;
+                    }
+                    s.push(c);
+                    chars.next();
+                }
+                if let Some(top) = stack.last_mut() {
+                    top.push(Sexp::Atom(s));
+                }
+            }
+        }
+    }
+    stack.swap_remove(0)
+}
+
+/// The `open ... close` block starting at the first non-whitespace character
+/// of `s`, with delimiters inside strings ignored.
+fn balanced(s: &str, open: char, close: char) -> Option<&str> {
+    let s = s.trim_start();
+    if !s.starts_with(open) {
+        return None;
+    }
+    let mut depth = 0usize;
+    let mut in_string = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            c if c == open => depth += 1,
+            c if c == close => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&s[..i + 1]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Drop `#` (opam) and `;` (dune) line comments, ignoring those inside strings.
+fn strip_comments(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    for line in content.lines() {
+        let mut in_string = false;
+        let mut end = line.len();
+        for (i, c) in line.char_indices() {
+            match c {
+                '"' => in_string = !in_string,
+                '#' | ';' if !in_string => {
+                    end = i;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        out.push_str(&line[..end]);
+        out.push('\n');
+    }
+    out
+}
+
+fn make_dep(name: &str, version: &str, dev: bool) -> Dependency {
+    Dependency {
+        name: name.to_string(),
+        version: version.to_string(),
+        ecosystem: Ecosystem::OCaml,
+        license_raw: None,
+        license_spdx: None,
+        risk: LicenseRisk::Unknown,
+        verdict: PolicyVerdict::Warn,
+        source: LicenseSource::Unknown,
+        notes: Vec::new(),
+        is_new: false,
+        optional: false,
+        dev,
+        labels: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(deps: &[Dependency]) -> Vec<(&str, &str, bool)> {
+        deps.iter()
+            .map(|d| (d.name.as_str(), d.version.as_str(), d.dev))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_opam_depends_with_constraints() {
+        let opam = r#"
+opam-version: "2.0"
+synopsis: "An example web service"
+license: "ISC"
+depends: [
+  "ocaml" {>= "4.14"}
+  "dune" {>= "3.0"}
+  "lwt" {>= "5.6.0" & < "6.0.0"}
+  "cohttp-lwt-unix" {= "5.3.0"}
+  "yojson"
+  # "old-dep" {>= "1.0"}
+  "alcotest" {with-test & >= "1.7.0"}
+  "odoc" {with-doc}
+]
+build: [["dune" "build" "-p" name "-j" jobs]]
+"#;
+        let deps = parse_opam(opam).unwrap();
+        assert_eq!(
+            entries(&deps),
+            [
+                ("dune", ">= 3.0", false),
+                ("lwt", ">= 5.6.0 & < 6.0.0", false),
+                ("cohttp-lwt-unix", "5.3.0", false),
+                ("yojson", "*", false),
+                ("alcotest", ">= 1.7.0", true),
+                ("odoc", "*", true),
+            ]
+        );
+        assert!(deps.iter().all(|d| d.ecosystem == Ecosystem::OCaml));
+    }
+
+    #[test]
+    fn test_parse_dune_project_depends() {
+        let dune = r#"
+(lang dune 3.0)
+(generate_opam_files true)
+; comment (depends (ignored))
+(package
+ (name webapp)
+ (depends
+  (ocaml (>= 4.14))
+  dune
+  (lwt (and (>= 5.6.0) (< 6.0.0)))
+  (alcotest :with-test)))
+"#;
+        let deps = parse_dune_project(dune);
+        assert_eq!(
+            entries(&deps),
+            [
+                ("dune", "*", false),
+                ("lwt", ">= 5.6.0 & < 6.0.0", false),
+                ("alcotest", "*", true),
+            ]
+        );
+        assert_eq!(dune_package_names(dune), ["webapp"]);
+    }
+}
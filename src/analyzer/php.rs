@@ -0,0 +1,211 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::models::{Dependency, Ecosystem, LicenseRisk, LicenseSource, PolicyVerdict};
+
+/// Analyzer for PHP projects managed by Composer.
+///
+/// Parses `composer.lock` (pinned versions and licenses of `packages` and
+/// `packages-dev`) and falls back to the `require` / `require-dev` constraints
+/// of `composer.json` when there is no lockfile. Platform requirements (`php`,
+/// `ext-*`, `lib-*`, `composer-*`) are not packages and are skipped.
+///
+/// Composer lists licenses as an array; several entries are alternatives and
+/// become an `OR` expression.
+pub struct ComposerAnalyzer;
+
+impl ComposerAnalyzer {
+    /// Create a new `ComposerAnalyzer`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl super::Analyzer for ComposerAnalyzer {
+    fn analyze(&self, path: &Path) -> Result<Vec<Dependency>> {
+        let lock = path.join("composer.lock");
+        let manifest = path.join("composer.json");
+        let parsed = if lock.exists() {
+            super::read_and_parse(&lock, parse_composer_lock)
+        } else if manifest.exists() {
+            super::read_and_parse(&manifest, parse_composer_json)
+        } else {
+            None
+        }
+        .unwrap_or_default();
+
+        let mut seen: HashSet<String> = HashSet::new();
+        Ok(parsed
+            .into_iter()
+            .filter(|d| seen.insert(format!("{}@{}", d.name, d.version)))
+            .collect())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ComposerLock {
+    #[serde(default)]
+    packages: Vec<LockedPackage>,
+    #[serde(default, rename = "packages-dev")]
+    packages_dev: Vec<LockedPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LockedPackage {
+    name: String,
+    version: String,
+    #[serde(default)]
+    license: Vec<String>,
+}
+
+fn parse_composer_lock(content: &str) -> Result<Vec<Dependency>> {
+    let lock: ComposerLock = serde_json::from_str(content)?;
+    let mut deps = Vec::new();
+    for (packages, dev) in [(lock.packages, false), (lock.packages_dev, true)] {
+        for pkg in packages {
+            let license = (!pkg.license.is_empty()).then(|| pkg.license.join(" OR "));
+            let mut dep = make_dep(pkg.name, pkg.version, license);
+            dep.dev = dev;
+            deps.push(dep);
+        }
+    }
+    Ok(deps)
+}
+
+/// `require` / `require-dev` of `composer.json`, with the constraint as version.
+fn parse_composer_json(content: &str) -> Result<Vec<Dependency>> {
+    let json: Value = serde_json::from_str(content)?;
+    let mut deps = Vec::new();
+    for section in ["require", "require-dev"] {
+        let Some(packages) = json.get(section).and_then(Value::as_object) else {
+            continue;
+        };
+        for (name, constraint) in packages {
+            if is_platform_package(name) {
+                continue;
+            }
+            let version = constraint.as_str().unwrap_or("*").to_string();
+            let mut dep = make_dep(name.clone(), version, None);
+            dep.dev = section == "require-dev";
+            deps.push(dep);
+        }
+    }
+    Ok(deps)
+}
+
+/// PHP itself, extensions and system libraries: real packages are `vendor/name`.
+fn is_platform_package(name: &str) -> bool {
+    !name.contains('/')
+}
+
+fn make_dep(name: String, version: String, license: Option<String>) -> Dependency {
+    let source = if license.is_some() {
+        LicenseSource::Manifest
+    } else {
+        LicenseSource::Unknown
+    };
+    Dependency {
+        name,
+        version,
+        ecosystem: Ecosystem::Php,
+        license_raw: license.clone(),
+        license_spdx: license,
+        risk: LicenseRisk::Unknown,
+        verdict: PolicyVerdict::Warn,
+        source,
+        notes: Vec::new(),
+        is_new: false,
+        optional: false,
+        dev: false,
+        labels: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::analyzer::Analyzer;
+
+    #[test]
+    fn test_parse_composer_lock() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("composer.lock"),
+            r#"{
+  "packages": [
+    { "name": "monolog/monolog", "version": "3.4.0", "license": ["MIT"] },
+    { "name": "symfony/polyfill-php80", "version": "v1.28.0", "license": ["MIT", "Apache-2.0"] },
+    { "name": "acme/internal", "version": "1.0.0" }
+  ],
+  "packages-dev": [
+    { "name": "phpunit/phpunit", "version": "10.3.5", "license": ["BSD-3-Clause"] }
+  ]
+}"#,
+        )
+        .unwrap();
+        // Ignored while a lockfile exists
+        std::fs::write(
+            tmp.path().join("composer.json"),
+            r#"{"require": {"guzzlehttp/guzzle": "^7.0"}}"#,
+        )
+        .unwrap();
+
+        let deps = ComposerAnalyzer::new().analyze(tmp.path()).unwrap();
+        let found: Vec<(&str, &str, Option<&str>, bool)> = deps
+            .iter()
+            .map(|d| {
+                (
+                    d.name.as_str(),
+                    d.version.as_str(),
+                    d.license_spdx.as_deref(),
+                    d.dev,
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("monolog/monolog", "3.4.0", Some("MIT"), false),
+                (
+                    "symfony/polyfill-php80",
+                    "v1.28.0",
+                    Some("MIT OR Apache-2.0"),
+                    false
+                ),
+                ("acme/internal", "1.0.0", None, false),
+                ("phpunit/phpunit", "10.3.5", Some("BSD-3-Clause"), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_composer_json_fallback_skips_platform_packages() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("composer.json"),
+            r#"{
+  "require": { "php": ">=8.1", "ext-json": "*", "guzzlehttp/guzzle": "^7.0" },
+  "require-dev": { "phpunit/phpunit": "^10.0" }
+}"#,
+        )
+        .unwrap();
+
+        let deps = ComposerAnalyzer::new().analyze(tmp.path()).unwrap();
+        let found: Vec<(&str, &str, bool)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str(), d.dev))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("guzzlehttp/guzzle", "^7.0", false),
+                ("phpunit/phpunit", "^10.0", true),
+            ]
+        );
+    }
+}
@@ -1,16 +1,24 @@
-use std::collections::HashSet;
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use regex::Regex;
 use serde::Deserialize;
 
+use crate::license::detector::license_from_dir;
+use crate::license::spdx::normalize;
 use crate::models::{Dependency, Ecosystem, LicenseRisk, LicenseSource, PolicyVerdict};
+use crate::registry::pypi::classifier_to_spdx;
 
 /// Analyzer for Python projects.
 ///
 /// Searches for manifests in priority order:
-/// `Pipfile.lock` (pinned) → `requirements.txt` → `pyproject.toml`.
+/// `conda-lock.yml` (pinned conda and pip packages) → `Pipfile.lock` (pinned) →
+/// `poetry.lock` (pinned) → `requirements.txt` → `pyproject.toml` (PEP 621, Poetry,
+/// PDM dev groups, Hatch environments, setuptools dynamic dependencies).
+/// Licenses are filled offline from `*.dist-info/METADATA` (or, failing that, the
+/// license files next to it) when the project has a virtualenv (`.venv`, `venv`,
+/// or `$VIRTUAL_ENV`).
 /// Results are deduplicated by package name (case-insensitive).
 pub struct PythonAnalyzer;
 
@@ -26,12 +34,30 @@ impl super::Analyzer for PythonAnalyzer {
         let mut deps: Vec<Dependency> = Vec::new();
         let mut seen: HashSet<String> = HashSet::new();
 
+        // conda-lock.yml (fully resolved conda environment — pinned versions)
+        let conda_lock = path.join("conda-lock.yml");
+        if conda_lock.exists() {
+            for d in super::read_and_parse(&conda_lock, parse_conda_lock).unwrap_or_default() {
+                seen.insert(d.name.to_lowercase());
+                deps.push(d);
+            }
+        }
+
         // Pipfile.lock (most precise — pinned versions)
         let pipfile_lock = path.join("Pipfile.lock");
         if pipfile_lock.exists() {
-            if let Ok(parsed) = parse_pipfile_lock(&pipfile_lock) {
-                for d in parsed {
-                    seen.insert(d.name.to_lowercase());
+            for d in super::read_and_parse(&pipfile_lock, parse_pipfile_lock).unwrap_or_default() {
+                if seen.insert(d.name.to_lowercase()) {
+                    deps.push(d);
+                }
+            }
+        }
+
+        // poetry.lock (pinned)
+        let poetry_lock = path.join("poetry.lock");
+        if poetry_lock.exists() {
+            for d in super::read_and_parse(&poetry_lock, parse_poetry_lock).unwrap_or_default() {
+                if seen.insert(d.name.to_lowercase()) {
                     deps.push(d);
                 }
             }
@@ -40,12 +66,14 @@ impl super::Analyzer for PythonAnalyzer {
         // requirements.txt
         let requirements = path.join("requirements.txt");
         if requirements.exists() {
-            if let Ok(parsed) = parse_requirements_txt(&requirements) {
-                for d in parsed {
-                    if !seen.contains(&d.name.to_lowercase()) {
-                        seen.insert(d.name.to_lowercase());
-                        deps.push(d);
-                    }
+            for d in super::read_and_parse(&requirements, |content| {
+                parse_requirements_txt(content, &requirements)
+            })
+            .unwrap_or_default()
+            {
+                if !seen.contains(&d.name.to_lowercase()) {
+                    seen.insert(d.name.to_lowercase());
+                    deps.push(d);
                 }
             }
         }
@@ -53,16 +81,39 @@ impl super::Analyzer for PythonAnalyzer {
         // pyproject.toml
         let pyproject = path.join("pyproject.toml");
         if pyproject.exists() {
-            if let Ok(parsed) = parse_pyproject_toml(&pyproject) {
-                for d in parsed {
-                    if !seen.contains(&d.name.to_lowercase()) {
-                        seen.insert(d.name.to_lowercase());
-                        deps.push(d);
-                    }
+            for d in super::read_and_parse(&pyproject, |content| {
+                parse_pyproject_toml(content, &pyproject)
+            })
+            .unwrap_or_default()
+            {
+                if !seen.contains(&d.name.to_lowercase()) {
+                    seen.insert(d.name.to_lowercase());
+                    deps.push(d);
                 }
             }
         }
 
+        // Offline license data from an installed virtualenv, when one is present
+        let installed = installed_licenses(&site_packages_dirs(path));
+        for dep in deps.iter_mut().filter(|d| d.license_raw.is_none()) {
+            let Some(versions) = installed.get(&normalize_name(&dep.name)) else {
+                continue;
+            };
+            // Only the installed copy of the pinned version counts; an unpinned
+            // requirement takes whichever version is installed. Otherwise the
+            // license is left for `--online` to look up for the pinned version.
+            let Some((_, license)) = versions
+                .iter()
+                .find(|(version, _)| *version == dep.version)
+                .or_else(|| (dep.version == "*").then(|| &versions[0]))
+            else {
+                continue;
+            };
+            dep.license_raw = Some(license.clone());
+            dep.license_spdx = Some(license.clone());
+            dep.source = LicenseSource::Cache;
+        }
+
         Ok(deps)
     }
 }
@@ -77,18 +128,189 @@ fn make_dep(name: String, version: String) -> Dependency {
         risk: LicenseRisk::Unknown,
         verdict: PolicyVerdict::Warn,
         source: LicenseSource::Unknown,
+        notes: Vec::new(),
+        is_new: false,
+        optional: false,
+        dev: false,
+        labels: Vec::new(),
+    }
+}
+
+/// PEP 503 normalized project name: lowercase, runs of `-`, `_`, `.` become `-`.
+fn normalize_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for c in name.chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !out.ends_with('-') {
+                out.push('-');
+            }
+        } else {
+            out.push(c.to_ascii_lowercase());
+        }
+    }
+    out
+}
+
+/// `site-packages` directories of the project's virtualenv(s):
+/// `.venv`, `venv`, and the active `$VIRTUAL_ENV`.
+fn site_packages_dirs(project_root: &Path) -> Vec<PathBuf> {
+    let mut venvs = vec![project_root.join(".venv"), project_root.join("venv")];
+    if let Some(active) = std::env::var_os("VIRTUAL_ENV") {
+        venvs.push(PathBuf::from(active));
+    }
+
+    let mut dirs = Vec::new();
+    for venv in &venvs {
+        super::track_input(venv);
+        if !venv.is_dir() {
+            continue;
+        }
+        // Windows layout
+        let windows = venv.join("Lib").join("site-packages");
+        super::track_input(&windows);
+        if windows.is_dir() {
+            dirs.push(windows);
+        }
+        // POSIX layout: lib/python3.X/site-packages
+        let Ok(entries) = super::read_dir(&venv.join("lib")) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let site = entry.path().join("site-packages");
+            if entry.file_name().to_string_lossy().starts_with("python") && site.is_dir() {
+                dirs.push(site);
+            }
+        }
+    }
+    dirs
+}
+
+/// `(version, license)` of every installed distribution, keyed by normalized
+/// project name, in `site_packages` order (several virtualenvs may install
+/// different versions of a package).
+fn installed_licenses(site_packages: &[PathBuf]) -> HashMap<String, Vec<(String, String)>> {
+    let mut licenses = HashMap::new();
+    for dir in site_packages {
+        let Ok(entries) = super::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            // `{name}-{version}.dist-info`; the name itself never contains `-`
+            let Some(stem) = file_name.strip_suffix(".dist-info") else {
+                continue;
+            };
+            let (name, version) = stem.split_once('-').unwrap_or((stem, ""));
+            let Some(metadata) = super::read_optional(&entry.path().join("METADATA")) else {
+                continue;
+            };
+            // Wheels ship their license files in the dist-info directory
+            // (under `licenses/` since metadata 2.4)
+            let license = license_from_metadata(&metadata)
+                .or_else(|| license_from_dir(&entry.path()))
+                .or_else(|| license_from_dir(&entry.path().join("licenses")));
+            if let Some(license) = license {
+                licenses
+                    .entry(normalize_name(name))
+                    .or_insert_with(Vec::new)
+                    .push((version.to_string(), license));
+            }
+        }
+    }
+    licenses
+}
+
+/// Extract a license from core-metadata headers, preferring `License-Expression`,
+/// then a one-line `License`, then `License ::` trove classifiers.
+fn license_from_metadata(metadata: &str) -> Option<String> {
+    let mut expression = None;
+    let mut license = None;
+    let mut license_is_multiline = false;
+    let mut classifiers = Vec::new();
+    let mut last_header = "";
+
+    // Headers end at the first blank line; the body is the long description
+    for line in metadata.lines().take_while(|l| !l.is_empty()) {
+        if line.starts_with([' ', '\t']) {
+            // Continuation line: a `License` spanning lines is a full license text
+            if last_header == "License" {
+                license_is_multiline = true;
+            }
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        last_header = key;
+        match key {
+            "License-Expression" => expression = Some(value.to_string()),
+            "License" => license = Some(value.to_string()),
+            "Classifier" => {
+                if let Some(name) = value.strip_prefix("License :: ") {
+                    let name = name.rsplit(" :: ").next().unwrap_or(name);
+                    classifiers.push(classifier_to_spdx(name));
+                }
+            }
+            _ => {}
+        }
     }
+
+    let license = license
+        .filter(|l| !license_is_multiline && !l.is_empty() && !l.eq_ignore_ascii_case("UNKNOWN"));
+    expression
+        .or(license.map(|l| normalize(&l)))
+        .or_else(|| (!classifiers.is_empty()).then(|| classifiers.join(" OR ")))
 }
 
-/// Parse `requirements.txt` — handles `name==version` and `name>=version` lines.
-fn parse_requirements_txt(path: &Path) -> Result<Vec<Dependency>> {
-    let content = std::fs::read_to_string(path)?;
-    let re = Regex::new(r"^([A-Za-z0-9_\-\.]+)\s*==\s*([^\s;]+)")?;
+/// Parse `requirements.txt` — `name==version` lines (`>=`, `~=` and `>` record their
+/// lower bound as the version), VCS / URL installs (see [`url_requirement`]) and the
+/// files included with `-r` / `--requirement`, resolved relative to the including
+/// file. Local editables (`-e .`) are skipped.
+fn parse_requirements_txt(content: &str, path: &Path) -> Result<Vec<Dependency>> {
+    parse_requirements_file(content, path, &mut HashSet::new())
+}
+
+/// [`parse_requirements_txt`] for one file; `visited` holds the files already
+/// read so include cycles end. An unreadable include is skipped with a warning.
+fn parse_requirements_file(
+    content: &str,
+    path: &Path,
+    visited: &mut HashSet<PathBuf>,
+) -> Result<Vec<Dependency>> {
+    visited.insert(path.canonicalize().unwrap_or_else(|_| path.to_path_buf()));
+    let re = Regex::new(r"^([A-Za-z0-9_\-\.]+)(?:\[[^\]]*\])?\s*(?:==|>=|~=|>)\s*([^\s;,]+)")?;
     let mut deps = Vec::new();
 
     for line in content.lines() {
         let line = line.trim();
-        if line.is_empty() || line.starts_with('#') || line.starts_with('-') {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(include) = requirements_include(line) {
+            let file = path.parent().unwrap_or(Path::new(".")).join(include);
+            let key = file.canonicalize().unwrap_or_else(|_| file.clone());
+            if !visited.contains(&key) {
+                deps.extend(
+                    super::read_and_parse(&file, |content| {
+                        parse_requirements_file(content, &file, visited)
+                    })
+                    .unwrap_or_default(),
+                );
+            }
+            continue;
+        }
+        let spec = line
+            .strip_prefix("--editable")
+            .or_else(|| line.strip_prefix("-e"))
+            .unwrap_or(line)
+            .trim_start_matches(['=', ' ']);
+        if let Some(dep) = url_requirement(spec)? {
+            deps.push(dep);
+            continue;
+        }
+        // Other options (`-c`, `--index-url`, …) and local editables (`-e .`)
+        if line.starts_with('-') {
             continue;
         }
         if let Some(caps) = re.captures(line) {
@@ -101,10 +323,52 @@ fn parse_requirements_txt(path: &Path) -> Result<Vec<Dependency>> {
     Ok(deps)
 }
 
-/// Parse `Pipfile.lock` — JSON with `default` and `develop` sections.
-fn parse_pipfile_lock(path: &Path) -> Result<Vec<Dependency>> {
-    let content = std::fs::read_to_string(path)?;
-    let json: serde_json::Value = serde_json::from_str(&content)?;
+/// The file named by a `-r file`, `-rfile`, `--requirement file` or
+/// `--requirement=file` line.
+fn requirements_include(line: &str) -> Option<&str> {
+    let rest = line
+        .strip_prefix("--requirement")
+        .or_else(|| line.strip_prefix("-r"))?;
+    let file = rest.trim_start_matches(['=', ' ', '\t']).trim();
+    (!file.is_empty()).then_some(file)
+}
+
+/// A VCS or direct-URL requirement: `pkg @ https://…/pkg-1.0.whl`, or a URL with an
+/// `#egg=pkg` fragment such as `git+https://github.com/org/pkg.git#egg=pkg`.
+///
+/// The version is recorded as `*` with a note naming the source. Returns `None`
+/// for anything else, including local paths (`-e .`), which are first-party code.
+fn url_requirement(spec: &str) -> Result<Option<Dependency>> {
+    let pep508 = Regex::new(r"^([A-Za-z0-9_\-\.]+)(?:\[[^\]]*\])?\s*@\s*(\S+)")?;
+    let egg = Regex::new(r"#(?:.*&)?egg=([A-Za-z0-9_\-\.]+)")?;
+
+    let (name, url) = if let Some(caps) = pep508.captures(spec) {
+        (caps[1].to_string(), caps[2].to_string())
+    } else if spec.contains("://") {
+        let url = spec.split_whitespace().next().unwrap_or(spec);
+        match egg.captures(url) {
+            Some(caps) => (caps[1].to_string(), url.to_string()),
+            None => return Ok(None),
+        }
+    } else {
+        return Ok(None);
+    };
+
+    let location = url.split('#').next().unwrap_or(&url);
+    let kind = match location.split_once('+') {
+        Some(("git" | "hg" | "svn" | "bzr", _)) => "VCS",
+        _ => "direct URL",
+    };
+    let mut dep = make_dep(name, "*".to_string());
+    dep.notes
+        .push(format!("{} install from {}", kind, location));
+    Ok(Some(dep))
+}
+
+/// Parse `Pipfile.lock` — JSON with `default` and `develop` sections; `develop`
+/// packages are marked `dev`.
+fn parse_pipfile_lock(content: &str) -> Result<Vec<Dependency>> {
+    let json: serde_json::Value = serde_json::from_str(content)?;
     let mut deps = Vec::new();
 
     for section in &["default", "develop"] {
@@ -116,7 +380,9 @@ fn parse_pipfile_lock(path: &Path) -> Result<Vec<Dependency>> {
                     .unwrap_or("*")
                     .trim_start_matches("==")
                     .to_string();
-                deps.push(make_dep(name.clone(), version));
+                let mut dep = make_dep(name.clone(), version);
+                dep.dev = *section == "develop";
+                deps.push(dep);
             }
         }
     }
@@ -124,34 +390,307 @@ fn parse_pipfile_lock(path: &Path) -> Result<Vec<Dependency>> {
     Ok(deps)
 }
 
-/// Parse `pyproject.toml` — extract `[project].dependencies`.
+/// Parse `conda-lock.yml` — the `package:` list of a conda-lock (v1) lockfile.
+///
+/// Each entry has `name`, `version` and `manager`: `pip` entries are PyPI
+/// packages (so `--online` queries PyPI), `conda` entries become
+/// [`Ecosystem::Conda`] packages. The lock repeats packages per platform; the
+/// first entry per manager and name wins. `category: dev` marks `dev` and
+/// `optional: true` marks `optional`.
+fn parse_conda_lock(content: &str) -> Result<Vec<Dependency>> {
+    let lock: CondaLock = serde_yaml::from_str(content)?;
+
+    let mut seen = HashSet::new();
+    let mut deps = Vec::new();
+    for pkg in lock.package {
+        let version = pkg.version.to_string();
+        let manager = pkg.manager.as_deref().unwrap_or("conda");
+        if !seen.insert((manager.to_string(), pkg.name.to_lowercase())) {
+            continue;
+        }
+        let mut dep = make_dep(pkg.name, version);
+        if manager != "pip" {
+            dep.ecosystem = Ecosystem::Conda;
+        }
+        dep.dev = pkg.category.as_deref() == Some("dev");
+        dep.optional = pkg.optional;
+        deps.push(dep);
+    }
+    Ok(deps)
+}
+
+#[derive(Debug, Deserialize)]
+struct CondaLock {
+    #[serde(default)]
+    package: Vec<CondaLockPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CondaLockPackage {
+    name: String,
+    version: CondaVersion,
+    /// `"conda"` or `"pip"`.
+    manager: Option<String>,
+    /// `"main"` or `"dev"`.
+    category: Option<String>,
+    #[serde(default)]
+    optional: bool,
+}
+
+/// A `version:` value; YAML reads an unquoted `2` or `1.5` as a number.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum CondaVersion {
+    Text(String),
+    Number(serde_yaml::Number),
+}
+
+impl std::fmt::Display for CondaVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CondaVersion::Text(v) => f.write_str(v),
+            CondaVersion::Number(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PoetryLock {
+    #[serde(default)]
+    package: Vec<PoetryLockPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PoetryLockPackage {
+    name: String,
+    version: String,
+    /// `"main"` or `"dev"`; only written by Poetry before 1.2.
+    category: Option<String>,
+    #[serde(default)]
+    optional: bool,
+}
+
+/// Parse `poetry.lock` — every `[[package]]` entry, pinned.
+fn parse_poetry_lock(content: &str) -> Result<Vec<Dependency>> {
+    let lock: PoetryLock = toml::from_str(content)?;
+    Ok(lock
+        .package
+        .into_iter()
+        .map(|pkg| {
+            let mut dep = make_dep(pkg.name, pkg.version);
+            dep.dev = pkg.category.as_deref() == Some("dev");
+            dep.optional = pkg.optional;
+            dep
+        })
+        .collect())
+}
+
+/// Parse `pyproject.toml` — extract `[project].dependencies` plus Poetry / PDM / Hatch tables.
+/// Dynamic dependencies are read from the files named in `[tool.setuptools.dynamic]`.
 #[derive(Debug, Deserialize)]
 struct Pyproject {
     project: Option<PyprojectProject>,
+    #[serde(default)]
+    tool: PyprojectTool,
 }
 
 #[derive(Debug, Deserialize)]
 struct PyprojectProject {
     #[serde(default)]
     dependencies: Vec<String>,
+    /// Fields filled in by the build backend, e.g. `["dependencies"]`.
+    #[serde(default)]
+    dynamic: Vec<String>,
 }
 
-fn parse_pyproject_toml(path: &Path) -> Result<Vec<Dependency>> {
-    let content = std::fs::read_to_string(path)?;
-    let pyproject: Pyproject = toml::from_str(&content)?;
+#[derive(Debug, Default, Deserialize)]
+struct PyprojectTool {
+    poetry: Option<PoetryTool>,
+    pdm: Option<PdmTool>,
+    hatch: Option<HatchTool>,
+    setuptools: Option<SetuptoolsTool>,
+}
+
+/// `[tool.setuptools.dynamic]` — where setuptools reads dynamic fields from.
+#[derive(Debug, Deserialize)]
+struct SetuptoolsTool {
+    #[serde(default)]
+    dynamic: SetuptoolsDynamic,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SetuptoolsDynamic {
+    /// `dependencies = { file = ["requirements.txt"] }`
+    dependencies: Option<SetuptoolsFiles>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SetuptoolsFiles {
+    file: OneOrMany,
+}
+
+/// A single path or a list of paths.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// `[tool.poetry]` — dependency tables of name → constraint string or table
+/// (`{ version = "^2.0", optional = true }`, `{ git = "…" }`).
+#[derive(Debug, Deserialize)]
+struct PoetryTool {
+    #[serde(default)]
+    dependencies: BTreeMap<String, toml::Value>,
+    /// Pre-1.2 layout of the `dev` group.
+    #[serde(default, rename = "dev-dependencies")]
+    dev_dependencies: BTreeMap<String, toml::Value>,
+    #[serde(default)]
+    group: BTreeMap<String, PoetryGroup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PoetryGroup {
+    #[serde(default)]
+    dependencies: BTreeMap<String, toml::Value>,
+}
+
+/// A `[tool.poetry]` dependency entry. Only exact versions (`"1.2.3"`, `"==1.2.3"`)
+/// are kept, anything else is `*`; git sources get a note and local `path`
+/// dependencies are first-party code and skipped.
+fn poetry_dep(name: &str, spec: &toml::Value) -> Option<Dependency> {
+    let table = spec.as_table();
+    let field = |key: &str| table.and_then(|t| t.get(key));
+    if field("path").is_some() {
+        return None;
+    }
+    let constraint = spec
+        .as_str()
+        .or_else(|| field("version").and_then(toml::Value::as_str))
+        .unwrap_or("*")
+        .trim();
+    let exact = constraint.trim_start_matches("==").trim();
+    let version = if exact.starts_with(|c: char| c.is_ascii_digit())
+        && exact.chars().all(|c| c.is_ascii_alphanumeric() || c == '.')
+    {
+        exact.to_string()
+    } else {
+        "*".to_string()
+    };
+
+    let mut dep = make_dep(name.to_string(), version);
+    dep.optional = field("optional")
+        .and_then(toml::Value::as_bool)
+        .unwrap_or(false);
+    if let Some(git) = field("git").and_then(toml::Value::as_str) {
+        dep.notes.push(format!("VCS install from {}", git));
+    }
+    Some(dep)
+}
+
+/// `[tool.pdm.dev-dependencies]` — group name → requirement strings.
+#[derive(Debug, Deserialize)]
+struct PdmTool {
+    #[serde(default, rename = "dev-dependencies")]
+    dev_dependencies: BTreeMap<String, Vec<String>>,
+}
+
+/// `[tool.hatch.envs.<env>]` — per-environment requirement strings.
+#[derive(Debug, Deserialize)]
+struct HatchTool {
+    #[serde(default)]
+    envs: BTreeMap<String, HatchEnv>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HatchEnv {
+    #[serde(default)]
+    dependencies: Vec<String>,
+    #[serde(default, rename = "extra-dependencies")]
+    extra_dependencies: Vec<String>,
+}
+
+fn parse_pyproject_toml(content: &str, path: &Path) -> Result<Vec<Dependency>> {
+    let pyproject: Pyproject = toml::from_str(content)?;
 
     let re = Regex::new(r"^([A-Za-z0-9_\-\.]+)\s*(?:==\s*([^\s;,\[]+))?")?;
+    let parse = |dep_str: &str| {
+        re.captures(dep_str.trim()).map(|caps| {
+            let name = caps[1].to_string();
+            let version = caps
+                .get(2)
+                .map(|m| m.as_str().to_string())
+                .unwrap_or_else(|| "*".to_string());
+            make_dep(name, version)
+        })
+    };
     let mut deps = Vec::new();
 
     if let Some(project) = pyproject.project {
-        for dep_str in &project.dependencies {
-            if let Some(caps) = re.captures(dep_str) {
-                let name = caps[1].to_string();
-                let version = caps
-                    .get(2)
-                    .map(|m| m.as_str().to_string())
-                    .unwrap_or_else(|| "*".to_string());
-                deps.push(make_dep(name, version));
+        deps.extend(project.dependencies.iter().filter_map(|d| parse(d)));
+
+        // `dynamic = ["dependencies"]`: setuptools reads them from requirements files
+        let files = pyproject
+            .tool
+            .setuptools
+            .as_ref()
+            .and_then(|s| s.dynamic.dependencies.as_ref())
+            .filter(|_| project.dynamic.iter().any(|f| f == "dependencies"));
+        if let Some(files) = files {
+            let files = match &files.file {
+                OneOrMany::One(file) => std::slice::from_ref(file),
+                OneOrMany::Many(files) => files.as_slice(),
+            };
+            let dir = path.parent().unwrap_or(Path::new("."));
+            for file in files {
+                let file = dir.join(file);
+                deps.extend(
+                    super::read_and_parse(&file, |content| parse_requirements_txt(content, &file))
+                        .unwrap_or_default(),
+                );
+            }
+        }
+    }
+
+    if let Some(poetry) = pyproject.tool.poetry {
+        // `python` is the interpreter constraint, not a package
+        for (name, spec) in poetry.dependencies.iter().filter(|(n, _)| *n != "python") {
+            deps.extend(poetry_dep(name, spec));
+        }
+        let groups = poetry
+            .group
+            .iter()
+            .map(|(group, g)| (group.as_str(), &g.dependencies))
+            .chain(std::iter::once(("dev", &poetry.dev_dependencies)));
+        for (group, table) in groups {
+            for (name, spec) in table {
+                if let Some(mut dep) = poetry_dep(name, spec) {
+                    dep.dev = true;
+                    dep.notes.push(format!("dev (Poetry group `{}`)", group));
+                    deps.push(dep);
+                }
+            }
+        }
+    }
+
+    // Tool-specific tables hold development / environment-only dependencies
+    if let Some(pdm) = pyproject.tool.pdm {
+        for (group, reqs) in &pdm.dev_dependencies {
+            for mut dep in reqs.iter().filter_map(|d| parse(d)) {
+                dep.dev = true;
+                dep.notes.push(format!("dev (PDM group `{}`)", group));
+                deps.push(dep);
+            }
+        }
+    }
+    if let Some(hatch) = pyproject.tool.hatch {
+        for (env, cfg) in &hatch.envs {
+            let reqs = cfg.dependencies.iter().chain(&cfg.extra_dependencies);
+            for mut dep in reqs.filter_map(|d| parse(d)) {
+                dep.dev = true;
+                dep.notes.push(format!("dev (Hatch env `{}`)", env));
+                deps.push(dep);
             }
         }
     }
@@ -162,9 +701,88 @@ fn parse_pyproject_toml(path: &Path) -> Result<Vec<Dependency>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analyzer::read_and_parse;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
+    #[test]
+    fn test_parse_conda_lock() {
+        let mut f = NamedTempFile::new().unwrap();
+        write!(
+            f,
+            r#"version: 1
+metadata:
+  channels:
+  - url: conda-forge
+  platforms:
+  - linux-64
+  - osx-arm64
+package:
+- name: python
+  version: 3.11.5
+  manager: conda
+  platform: linux-64
+  dependencies:
+    openssl: '>=3.1.2,<4.0a0'
+  url: https://conda.anaconda.org/conda-forge/linux-64/python-3.11.5.conda
+  hash:
+    md5: 7b0b6ff7f2e2d3ab0e6b5e3e2f1c6b0a
+  category: main
+  optional: false
+- name: numpy
+  version: 1.26.0
+  manager: conda
+  platform: linux-64
+  dependencies: {{}}
+  category: main
+  optional: false
+- name: numpy
+  version: 1.26.0
+  manager: conda
+  platform: osx-arm64
+  category: main
+  optional: false
+- name: requests
+  version: 2.31.0
+  manager: pip
+  platform: linux-64
+  dependencies:
+    urllib3: '>=1.21.1,<3'
+  url: https://files.pythonhosted.org/packages/requests-2.31.0-py3-none-any.whl
+  category: main
+  optional: false
+- name: pytest
+  version: "7.4.2"
+  manager: pip
+  platform: linux-64
+  category: dev
+  optional: true
+- name: idna
+  version: 3
+  manager: pip
+  platform: linux-64
+"#
+        )
+        .unwrap();
+
+        let deps = read_and_parse(f.path(), parse_conda_lock).unwrap();
+        let entries: Vec<(&str, &str, &Ecosystem, bool)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str(), &d.ecosystem, d.dev))
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                ("python", "3.11.5", &Ecosystem::Conda, false),
+                ("numpy", "1.26.0", &Ecosystem::Conda, false),
+                ("requests", "2.31.0", &Ecosystem::Python, false),
+                ("pytest", "7.4.2", &Ecosystem::Python, true),
+                ("idna", "3", &Ecosystem::Python, false),
+            ]
+        );
+        assert!(deps[3].optional && !deps[2].optional);
+    }
+
     #[test]
     fn test_parse_requirements_txt() {
         let mut f = NamedTempFile::new().unwrap();
@@ -173,10 +791,439 @@ mod tests {
         writeln!(f, "flask>=2.0.0").unwrap();
         writeln!(f, "numpy==1.24.0 ; python_version >= '3.8'").unwrap();
 
-        let deps = parse_requirements_txt(f.path()).unwrap();
-        assert_eq!(deps.len(), 2);
+        let deps = read_and_parse(f.path(), |content| {
+            parse_requirements_txt(content, f.path())
+        })
+        .unwrap();
+        assert_eq!(deps.len(), 3);
         assert_eq!(deps[0].name, "requests");
         assert_eq!(deps[0].version, "2.28.1");
-        assert_eq!(deps[1].name, "numpy");
+        assert_eq!(deps[1].name, "flask");
+        assert_eq!(deps[1].version, "2.0.0");
+        assert_eq!(deps[2].name, "numpy");
+    }
+
+    #[test]
+    fn test_parse_requirements_txt_includes_and_specifiers() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(tmp.path().join("requirements")).unwrap();
+        std::fs::write(
+            tmp.path().join("requirements.txt"),
+            "-r requirements/base.txt\nflask>=2.0.0,<3\ndjango~=4.1\nuvicorn[standard]>0.20\nhttpx\n",
+        )
+        .unwrap();
+        // Includes resolve next to the including file; the cycle back is not followed
+        std::fs::write(
+            tmp.path().join("requirements/base.txt"),
+            "requests==2.31.0\n--requirement=../requirements.txt\n",
+        )
+        .unwrap();
+
+        let file = tmp.path().join("requirements.txt");
+        let deps = read_and_parse(&file, |content| parse_requirements_txt(content, &file)).unwrap();
+        let found: Vec<(&str, &str)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("requests", "2.31.0"),
+                ("flask", "2.0.0"),
+                ("django", "4.1"),
+                ("uvicorn", "0.20"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_requirements_include_is_skipped() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let file = tmp.path().join("requirements.txt");
+        std::fs::write(&file, "requests==2.31.0\n-r missing.txt\n").unwrap();
+
+        let deps = read_and_parse(&file, |content| parse_requirements_txt(content, &file)).unwrap();
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "requests");
+    }
+
+    #[test]
+    fn test_parse_requirements_txt_editable_vcs_and_url_installs() {
+        let mut f = NamedTempFile::new().unwrap();
+        writeln!(f, "-e .").unwrap();
+        writeln!(f, "--editable ./libs/shared").unwrap();
+        writeln!(f, "-e git+https://github.com/org/pkg.git@v1.2#egg=pkg").unwrap();
+        writeln!(
+            f,
+            "git+https://github.com/org/other.git#subdirectory=py&egg=other-lib"
+        )
+        .unwrap();
+        writeln!(
+            f,
+            "wheelpkg @ https://example.com/wheelpkg-1.0-py3-none-any.whl"
+        )
+        .unwrap();
+        writeln!(f, "extra[socks] @ git+https://github.com/org/extra.git").unwrap();
+        writeln!(f, "requests==2.28.1").unwrap();
+
+        let deps = read_and_parse(f.path(), |content| {
+            parse_requirements_txt(content, f.path())
+        })
+        .unwrap();
+        let names: Vec<(&str, &str)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("pkg", "*"),
+                ("other-lib", "*"),
+                ("wheelpkg", "*"),
+                ("extra", "*"),
+                ("requests", "2.28.1"),
+            ]
+        );
+        assert_eq!(
+            deps[0].notes,
+            ["VCS install from git+https://github.com/org/pkg.git@v1.2"]
+        );
+        assert_eq!(
+            deps[2].notes,
+            ["direct URL install from https://example.com/wheelpkg-1.0-py3-none-any.whl"]
+        );
+        assert_eq!(
+            deps[3].notes,
+            ["VCS install from git+https://github.com/org/extra.git"]
+        );
+        assert!(deps[4].notes.is_empty());
+    }
+
+    #[test]
+    fn test_parse_pyproject_pdm_dev_dependencies() {
+        let mut f = NamedTempFile::new().unwrap();
+        write!(
+            f,
+            r#"
+[project]
+name = "app"
+dependencies = ["httpx==0.27.0"]
+
+[tool.pdm.dev-dependencies]
+test = ["pytest>=8.0", "pytest-cov==5.0.0"]
+lint = ["ruff"]
+"#
+        )
+        .unwrap();
+
+        let deps =
+            read_and_parse(f.path(), |content| parse_pyproject_toml(content, f.path())).unwrap();
+        let names: Vec<&str> = deps.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["httpx", "ruff", "pytest", "pytest-cov"]);
+        assert!(deps[0].notes.is_empty());
+        assert_eq!(deps[3].version, "5.0.0");
+        assert_eq!(deps[3].notes, ["dev (PDM group `test`)"]);
+        assert!(deps[3].dev && !deps[0].dev);
+    }
+
+    #[test]
+    fn test_parse_pyproject_hatch_envs() {
+        let mut f = NamedTempFile::new().unwrap();
+        write!(
+            f,
+            r#"
+[project]
+name = "app"
+
+[tool.hatch.envs.default]
+dependencies = ["coverage[toml]>=6.5", "pytest"]
+
+[tool.hatch.envs.docs]
+extra-dependencies = ["mkdocs==1.6.0"]
+"#
+        )
+        .unwrap();
+
+        let deps =
+            read_and_parse(f.path(), |content| parse_pyproject_toml(content, f.path())).unwrap();
+        let names: Vec<&str> = deps.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["coverage", "pytest", "mkdocs"]);
+        assert_eq!(deps[2].notes, ["dev (Hatch env `docs`)"]);
+    }
+
+    #[test]
+    fn test_parse_pyproject_setuptools_dynamic_dependencies() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("pyproject.toml"),
+            r#"
+[project]
+name = "app"
+dynamic = ["dependencies"]
+
+[tool.setuptools.dynamic]
+dependencies = { file = ["requirements.txt", "requirements-extra.txt"] }
+"#,
+        )
+        .unwrap();
+        std::fs::write(tmp.path().join("requirements.txt"), "requests==2.31.0\n").unwrap();
+        std::fs::write(tmp.path().join("requirements-extra.txt"), "click==8.1.7\n").unwrap();
+
+        let file = tmp.path().join("pyproject.toml");
+        let deps = read_and_parse(&file, |content| parse_pyproject_toml(content, &file)).unwrap();
+        let names: Vec<&str> = deps.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["requests", "click"]);
+        assert_eq!(deps[0].version, "2.31.0");
+    }
+
+    #[test]
+    fn test_parse_poetry_lock() {
+        let mut f = NamedTempFile::new().unwrap();
+        write!(
+            f,
+            r#"
+[[package]]
+name = "requests"
+version = "2.31.0"
+description = "Python HTTP for Humans."
+optional = false
+python-versions = ">=3.7"
+
+[[package]]
+name = "pytest"
+version = "7.4.2"
+category = "dev"
+optional = false
+
+[[package]]
+name = "PyYAML"
+version = "6.0.1"
+optional = true
+
+[metadata]
+lock-version = "2.0"
+"#
+        )
+        .unwrap();
+
+        let deps = read_and_parse(f.path(), parse_poetry_lock).unwrap();
+        let found: Vec<(&str, &str, bool, bool)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str(), d.dev, d.optional))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("requests", "2.31.0", false, false),
+                ("pytest", "7.4.2", true, false),
+                ("PyYAML", "6.0.1", false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_pipfile_lock_marks_develop_as_dev() {
+        let mut f = NamedTempFile::new().unwrap();
+        write!(
+            f,
+            r#"{{
+  "_meta": {{ "hash": {{ "sha256": "abc" }} }},
+  "default": {{ "requests": {{ "version": "==2.31.0" }} }},
+  "develop": {{ "pytest": {{ "version": "==7.4.3" }} }}
+}}"#
+        )
+        .unwrap();
+
+        let deps = read_and_parse(f.path(), parse_pipfile_lock).unwrap();
+        let found: Vec<(&str, &str, bool)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str(), d.dev))
+            .collect();
+        assert_eq!(
+            found,
+            [("requests", "2.31.0", false), ("pytest", "7.4.3", true)]
+        );
+    }
+
+    #[test]
+    fn test_parse_pyproject_poetry_dependencies() {
+        let mut f = NamedTempFile::new().unwrap();
+        write!(
+            f,
+            r#"
+[tool.poetry]
+name = "app"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.10"
+requests = "2.31.0"
+click = "^8.1"
+rich = {{ version = "==13.5.2", optional = true }}
+internal = {{ path = "../internal" }}
+toolkit = {{ git = "https://github.com/acme/toolkit.git" }}
+
+[tool.poetry.dev-dependencies]
+pytest = "^7.4"
+
+[tool.poetry.group.docs.dependencies]
+mkdocs = "1.5.3"
+"#
+        )
+        .unwrap();
+
+        let deps =
+            read_and_parse(f.path(), |content| parse_pyproject_toml(content, f.path())).unwrap();
+        let found: Vec<(&str, &str, bool)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str(), d.dev))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("click", "*", false),
+                ("requests", "2.31.0", false),
+                ("rich", "13.5.2", false),
+                ("toolkit", "*", false),
+                ("mkdocs", "1.5.3", true),
+                ("pytest", "*", true),
+            ]
+        );
+        assert!(deps[2].optional);
+        assert_eq!(
+            deps[3].notes,
+            ["VCS install from https://github.com/acme/toolkit.git"]
+        );
+        assert_eq!(deps[4].notes, ["dev (Poetry group `docs`)"]);
+    }
+
+    #[test]
+    fn test_license_read_from_venv_dist_info() {
+        use crate::analyzer::Analyzer;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("requirements.txt"),
+            "Flask_Login==0.6.3\nrequests==2.31.0\nsix==1.16.0\n",
+        )
+        .unwrap();
+
+        let site = tmp
+            .path()
+            .join(".venv")
+            .join("lib")
+            .join("python3.12")
+            .join("site-packages");
+        let write_metadata = |dist: &str, metadata: &str| {
+            let dir = site.join(dist);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("METADATA"), metadata).unwrap();
+        };
+        write_metadata(
+            "flask_login-0.6.3.dist-info",
+            "Metadata-Version: 2.4\nName: Flask-Login\nLicense-Expression: MIT\nLicense: ignored\n\nLicense: body text\n",
+        );
+        write_metadata(
+            "requests-2.31.0.dist-info",
+            "Metadata-Version: 2.1\nName: requests\nLicense: Apache 2.0\n",
+        );
+        write_metadata(
+            "six-1.16.0.dist-info",
+            "Metadata-Version: 2.1\nName: six\nLicense: UNKNOWN\nClassifier: License :: OSI Approved :: MIT License\n",
+        );
+
+        let deps = PythonAnalyzer::new().analyze(tmp.path()).unwrap();
+        let found: Vec<(&str, Option<&str>)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.license_raw.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("Flask_Login", Some("MIT")),
+                ("requests", Some("Apache-2.0")),
+                ("six", Some("MIT")),
+            ]
+        );
+        assert!(deps
+            .iter()
+            .all(|d| matches!(d.source, LicenseSource::Cache)));
+    }
+
+    #[test]
+    fn test_installed_license_prefers_pinned_version() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (old, new) = (tmp.path().join("old"), tmp.path().join("new"));
+        for (site, version, license) in [(&old, "1.0.0", "GPL-3.0"), (&new, "2.0.0", "MIT")] {
+            let dir = site.join(format!("relicensed-{}.dist-info", version));
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                dir.join("METADATA"),
+                format!("Name: relicensed\nLicense-Expression: {}\n", license),
+            )
+            .unwrap();
+        }
+
+        let installed = installed_licenses(&[old, new]);
+        assert_eq!(
+            installed["relicensed"],
+            [
+                ("1.0.0".to_string(), "GPL-3.0".to_string()),
+                ("2.0.0".to_string(), "MIT".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_installed_other_version_is_ignored_for_pinned_requirements() {
+        use crate::analyzer::Analyzer;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("pyproject.toml"),
+            "[project]\nname = \"app\"\ndependencies = [\"pinned==2.0.0\", \"loose\"]\n",
+        )
+        .unwrap();
+        let site = tmp
+            .path()
+            .join(".venv")
+            .join("lib")
+            .join("python3.12")
+            .join("site-packages");
+        for name in ["pinned", "loose"] {
+            let dir = site.join(format!("{}-1.0.0.dist-info", name));
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                dir.join("METADATA"),
+                format!("Name: {}\nLicense-Expression: GPL-3.0\n", name),
+            )
+            .unwrap();
+        }
+
+        let deps = PythonAnalyzer::new().analyze(tmp.path()).unwrap();
+        let found: Vec<(&str, &str, Option<&str>)> = deps
+            .iter()
+            .map(|d| {
+                (
+                    d.name.as_str(),
+                    d.version.as_str(),
+                    d.license_raw.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [("pinned", "2.0.0", None), ("loose", "*", Some("GPL-3.0")),]
+        );
+        assert!(matches!(deps[0].source, LicenseSource::Unknown));
+    }
+
+    #[test]
+    fn test_multiline_license_field_falls_back_to_classifier() {
+        let metadata = "Name: x\nLicense: Copyright (c) Someone\n        Permission is hereby granted\nClassifier: License :: OSI Approved :: BSD License\n";
+        assert_eq!(
+            license_from_metadata(metadata).as_deref(),
+            Some("BSD-3-Clause")
+        );
     }
 }
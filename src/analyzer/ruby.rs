@@ -0,0 +1,150 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::diagnostics::Diagnostic;
+use crate::models::{Dependency, Ecosystem, LicenseRisk, LicenseSource, PolicyVerdict};
+
+/// Analyzer for Ruby projects managed by Bundler.
+///
+/// Reads the `GEM` section of `Gemfile.lock`, where every resolved gem is listed
+/// as `    rails (7.0.4)` under `specs:`. The more deeply indented lines below a
+/// gem are its own dependency constraints and are skipped; each of those gems
+/// has its own top-level entry. Gems from `GIT` and `PATH` sources are not read.
+///
+/// A `Gemfile` without a lockfile has no resolved versions and yields nothing.
+pub struct RubyAnalyzer;
+
+impl RubyAnalyzer {
+    /// Create a new `RubyAnalyzer`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl super::Analyzer for RubyAnalyzer {
+    fn analyze(&self, path: &Path) -> Result<Vec<Dependency>> {
+        let lockfile = path.join("Gemfile.lock");
+        if !lockfile.exists() {
+            if path.join("Gemfile").exists() {
+                Diagnostic::note("Gemfile has no Gemfile.lock; run `bundle lock` to scan its gems")
+                    .file(&path.join("Gemfile"))
+                    .emit();
+            }
+            return Ok(Vec::new());
+        }
+
+        let mut seen: HashSet<String> = HashSet::new();
+        Ok(
+            super::read_and_parse(&lockfile, |content| Ok(parse_gemfile_lock(content)))
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|d| seen.insert(format!("{}@{}", d.name, d.version)))
+                .collect(),
+        )
+    }
+}
+
+/// The top-level gems of the `GEM` section.
+fn parse_gemfile_lock(content: &str) -> Vec<Dependency> {
+    let mut deps = Vec::new();
+    let mut in_gem = false;
+    for line in content.lines() {
+        // Section headers (`GEM`, `PLATFORMS`, `DEPENDENCIES`, …) are not indented
+        if !line.starts_with(' ') {
+            in_gem = line.trim_end() == "GEM";
+            continue;
+        }
+        // Exactly four spaces: a resolved gem; six or more: one of its constraints
+        let Some(spec) = line.strip_prefix("    ") else {
+            continue;
+        };
+        if !in_gem || spec.starts_with(' ') {
+            continue;
+        }
+        if let Some((name, version)) = spec
+            .trim_end()
+            .strip_suffix(')')
+            .and_then(|s| s.split_once(" ("))
+        {
+            deps.push(make_dep(name, version));
+        }
+    }
+    deps
+}
+
+fn make_dep(name: &str, version: &str) -> Dependency {
+    Dependency {
+        name: name.to_string(),
+        version: version.to_string(),
+        ecosystem: Ecosystem::Ruby,
+        license_raw: None,
+        license_spdx: None,
+        risk: LicenseRisk::Unknown,
+        verdict: PolicyVerdict::Warn,
+        source: LicenseSource::Unknown,
+        notes: Vec::new(),
+        is_new: false,
+        optional: false,
+        dev: false,
+        labels: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GEMFILE_LOCK: &str = "\
+GIT
+  remote: https://github.com/acme/private-gem.git
+  revision: 0123456789abcdef
+  specs:
+    private-gem (0.3.0)
+
+GEM
+  remote: https://rubygems.org/
+  specs:
+    actionpack (7.0.4)
+      rack (~> 2.0, >= 2.2.0)
+      rails-html-sanitizer (~> 1.0, >= 1.2.0)
+    nokogiri (1.15.4-x86_64-linux)
+      racc (~> 1.4)
+    racc (1.7.1)
+    rack (2.2.8)
+    rails (7.0.4)
+      actionpack (= 7.0.4)
+
+PLATFORMS
+  x86_64-linux
+
+DEPENDENCIES
+  nokogiri
+  private-gem!
+  rails (~> 7.0)
+
+BUNDLED WITH
+   2.4.19
+";
+
+    #[test]
+    fn test_parse_gemfile_lock() {
+        let deps = parse_gemfile_lock(GEMFILE_LOCK);
+        let found: Vec<(&str, &str)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("actionpack", "7.0.4"),
+                ("nokogiri", "1.15.4-x86_64-linux"),
+                ("racc", "1.7.1"),
+                ("rack", "2.2.8"),
+                ("rails", "7.0.4"),
+            ]
+        );
+        assert!(deps.iter().all(|d| d.ecosystem == Ecosystem::Ruby));
+    }
+}
@@ -1,8 +1,12 @@
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Read;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use serde::Deserialize;
 
+use crate::license::detect_from_text;
+use crate::license::detector::license_from_dir;
 use crate::models::{Dependency, Ecosystem, LicenseRisk, LicenseSource, PolicyVerdict};
 
 #[derive(Debug, Deserialize)]
@@ -22,11 +26,40 @@ struct CargoLockPackage {
 #[derive(Debug, Deserialize)]
 struct CrateManifest {
     package: Option<CratePackage>,
+    /// `[patch.<registry>]` tables, keyed by registry name or URL.
+    #[serde(default)]
+    patch: HashMap<String, HashMap<String, toml::Value>>,
+    /// Legacy `[replace]` table keyed by `"name:version"`.
+    #[serde(default)]
+    replace: HashMap<String, toml::Value>,
+}
+
+/// The dependency tables of a `Cargo.toml`, read when there is no `Cargo.lock`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ManifestDependencies {
+    #[serde(default)]
+    dependencies: BTreeMap<String, toml::Value>,
+    #[serde(default)]
+    dev_dependencies: BTreeMap<String, toml::Value>,
+    #[serde(default)]
+    build_dependencies: BTreeMap<String, toml::Value>,
+    workspace: Option<ManifestWorkspace>,
+}
+
+/// `[workspace.dependencies]`, inherited by `foo = { workspace = true }`.
+#[derive(Debug, Deserialize)]
+struct ManifestWorkspace {
+    #[serde(default)]
+    dependencies: BTreeMap<String, toml::Value>,
 }
 
 #[derive(Debug, Deserialize)]
 struct CratePackage {
     license: Option<String>,
+    /// Path (relative to the manifest) of a license text used instead of `license`.
+    #[serde(rename = "license-file")]
+    license_file: Option<String>,
 }
 
 /// Look up the `license` field for a crate from the local Cargo registry cache.
@@ -34,38 +67,377 @@ struct CratePackage {
 /// Cargo stores downloaded crate sources at:
 /// `$CARGO_HOME/registry/src/<registry-hash>/<name>-<version>/Cargo.toml`
 ///
-/// Returns `None` if the crate is not cached locally or has no `license` field.
+/// When the sources were never unpacked (or were cleaned), the downloaded
+/// `$CARGO_HOME/registry/cache/<registry-hash>/<name>-<version>.crate` archive is
+/// read instead. Returns `None` if the crate is not cached locally or its license
+/// cannot be determined (see [`license_from_local_crate`]).
 fn license_from_cargo_cache(name: &str, version: &str) -> Option<String> {
+    let registry = cargo_registry()?;
+    license_from_registry_src(&registry.join("src"), name, version)
+        .or_else(|| license_from_crate_archives(&registry.join("cache"), name, version))
+}
+
+/// `$CARGO_HOME/registry` (default `~/.cargo/registry`).
+fn cargo_registry() -> Option<PathBuf> {
     let cargo_home = std::env::var_os("CARGO_HOME")
-        .map(std::path::PathBuf::from)
+        .map(PathBuf::from)
         .or_else(|| dirs::home_dir().map(|h| h.join(".cargo")))?;
+    Some(cargo_home.join("registry"))
+}
 
-    let registry_src = cargo_home.join("registry").join("src");
+/// `$CARGO_HOME/registry/src` (default `~/.cargo/registry/src`), where Cargo
+/// unpacks downloaded crate sources. The directory may not exist.
+pub fn cargo_registry_src() -> Option<PathBuf> {
+    Some(cargo_registry()?.join("src"))
+}
+
+/// Search every registry under `registry_src` for `<name>-<version>/Cargo.toml`.
+fn license_from_registry_src(registry_src: &Path, name: &str, version: &str) -> Option<String> {
     let crate_dir_name = format!("{}-{}", name, version);
 
     // registry/src contains one subdirectory per registry host
     // (e.g. `index.crates.io-6f17d22bba15001f`).
-    for entry in std::fs::read_dir(&registry_src).ok()?.flatten() {
-        let cargo_toml = entry.path().join(&crate_dir_name).join("Cargo.toml");
-        if !cargo_toml.exists() {
-            continue;
+    for entry in super::read_dir(registry_src).ok()?.flatten() {
+        let crate_dir = entry.path().join(&crate_dir_name);
+        if let Some(license) = license_from_local_crate(&crate_dir) {
+            return Some(license);
+        }
+    }
+
+    None
+}
+
+/// Search every registry under `registry_cache` for a `<name>-<version>.crate` archive.
+fn license_from_crate_archives(registry_cache: &Path, name: &str, version: &str) -> Option<String> {
+    let crate_dir_name = format!("{}-{}", name, version);
+    for entry in super::read_dir(registry_cache).ok()?.flatten() {
+        let archive = entry.path().join(format!("{}.crate", crate_dir_name));
+        if let Some(license) = license_from_crate_archive(&archive, Path::new(&crate_dir_name)) {
+            return Some(license);
         }
-        if let Ok(content) = std::fs::read_to_string(&cargo_toml) {
-            if let Ok(manifest) = toml::from_str::<CrateManifest>(&content) {
-                if let Some(license) = manifest.package.and_then(|p| p.license) {
-                    return Some(license);
+    }
+    None
+}
+
+/// Like [`license_from_local_crate`], for a `.crate` archive (a gzipped tarball
+/// whose files live under `<name>-<version>/`).
+fn license_from_crate_archive(archive: &Path, crate_dir: &Path) -> Option<String> {
+    let content = read_archived_file(archive, &crate_dir.join("Cargo.toml"))?;
+    let package = toml::from_str::<CrateManifest>(&content).ok()?.package?;
+    if let Some(license) = package.license {
+        return Some(license);
+    }
+    let text = read_archived_file(archive, &crate_dir.join(package.license_file?))?;
+    detect_from_text(&text)
+}
+
+fn read_archived_file(archive: &Path, path: &Path) -> Option<String> {
+    super::track_input(archive);
+    let file = std::fs::File::open(archive).ok()?;
+    let mut tar = tar::Archive::new(flate2::read::GzDecoder::new(file));
+    for entry in tar.entries().ok()? {
+        let mut entry = entry.ok()?;
+        if entry.path().ok()? == path {
+            let mut content = String::new();
+            entry.read_to_string(&mut content).ok()?;
+            return Some(content);
+        }
+    }
+    None
+}
+
+/// One version line of a registry index entry. The index carries no license.
+#[derive(Debug, Deserialize)]
+struct IndexVersion {
+    #[serde(default)]
+    yanked: bool,
+}
+
+/// Whether `name@version` is marked yanked in Cargo's local index cache of any
+/// registry under `registry_index` (`$CARGO_HOME/registry/index`). Unknown
+/// crates and unreadable caches count as not yanked.
+fn is_yanked(registry_index: &Path, name: &str, version: &str) -> bool {
+    let Ok(entries) = super::read_dir(registry_index) else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        index_cache_version(&entry.path().join(".cache"), name, version).is_some_and(|v| v.yanked)
+    })
+}
+
+/// Read `version` from Cargo's cached index file for `name` under `cache`.
+///
+/// The file is laid out as one cache-version byte and a 4-byte index version,
+/// followed by NUL-terminated strings: a header, then `version`, `json` pairs.
+/// Paths follow the index layout (`1/a`, `2/ab`, `3/a/abc`, `se/rd/serde`).
+fn index_cache_version(cache: &Path, name: &str, version: &str) -> Option<IndexVersion> {
+    let name = name.to_lowercase();
+    let relative = match name.len() {
+        0 => return None,
+        1 => format!("1/{}", name),
+        2 => format!("2/{}", name),
+        3 => format!("3/{}/{}", name.get(..1)?, name),
+        _ => format!("{}/{}/{}", name.get(..2)?, name.get(2..4)?, name),
+    };
+    let file = cache.join(relative);
+    super::track_input(&file);
+    let data = std::fs::read(file).ok()?;
+    let mut fields = data.get(5..)?.split(|b| *b == 0).skip(1);
+    while let (Some(vers), Some(json)) = (fields.next(), fields.next()) {
+        if vers == version.as_bytes() {
+            return serde_json::from_slice(json).ok();
+        }
+    }
+    None
+}
+
+/// Crates overridden via `[patch]` / `[replace]` in the project's `Cargo.toml`,
+/// mapped to the local source directory when the override is a `path` dependency.
+fn patched_crates(project_root: &Path) -> HashMap<String, Option<PathBuf>> {
+    let mut patched = HashMap::new();
+    let Some(content) = super::read_optional(&project_root.join("Cargo.toml")) else {
+        return patched;
+    };
+    let Ok(manifest) = toml::from_str::<CrateManifest>(&content) else {
+        return patched;
+    };
+
+    let entries = manifest
+        .patch
+        .into_values()
+        .flatten()
+        .chain(manifest.replace.into_iter().map(|(key, value)| {
+            // `[replace]` keys are package ids: "name:version"
+            let name = key.split(':').next().unwrap_or(&key).to_string();
+            (name, value)
+        }));
+
+    for (key, value) in entries {
+        // `foo = { package = "real-name", ... }` renames the patched crate
+        let name = value
+            .get("package")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .unwrap_or(key);
+        let local = value
+            .get("path")
+            .and_then(|v| v.as_str())
+            .map(|p| project_root.join(p));
+        patched.insert(name, local);
+    }
+
+    patched
+}
+
+/// Read the `license` field from a local crate directory's `Cargo.toml`.
+///
+/// Crates that only set `license-file` (historically e.g. `ring`) get an SPDX
+/// id detected from that file's text instead; crates that set neither, from a
+/// `LICENSE` / `COPYING` file in the crate directory.
+fn license_from_local_crate(dir: &Path) -> Option<String> {
+    let content = super::read_optional(&dir.join("Cargo.toml"))?;
+    let package = toml::from_str::<CrateManifest>(&content).ok()?.package?;
+    if let Some(license) = package.license {
+        return Some(license);
+    }
+    package
+        .license_file
+        .and_then(|file| super::read_optional(&dir.join(file)))
+        .and_then(|text| detect_from_text(&text))
+        .or_else(|| license_from_dir(dir))
+}
+
+/// Short label for a `Cargo.lock` `source` value.
+fn source_kind(source: Option<&str>) -> &'static str {
+    match source {
+        Some(s) if s.starts_with("git+") => "git",
+        Some(_) => "registry",
+        None => "path",
+    }
+}
+
+/// Resolve `Cargo.lock` packages into dependencies, honouring `[patch]` overrides.
+///
+/// Packages without a `source` are workspace members and skipped — unless the crate
+/// is patched to a local path. When a patched crate appears both from the registry
+/// and from its override at the same version, only the override is kept. Patched
+/// crates carry a note because the scanned license may not match the patched source.
+fn resolve_packages(
+    packages: Vec<CargoLockPackage>,
+    patched: &HashMap<String, Option<PathBuf>>,
+) -> Vec<Dependency> {
+    let registry_index = cargo_registry().map(|r| r.join("index"));
+    let overridden: std::collections::HashSet<(String, String)> = packages
+        .iter()
+        .filter(|p| patched.contains_key(&p.name))
+        .filter(|p| source_kind(p.source.as_deref()) != "registry")
+        .map(|p| (p.name.clone(), p.version.clone()))
+        .collect();
+
+    packages
+        .into_iter()
+        .filter(|p| p.source.is_some() || patched.contains_key(&p.name))
+        .filter(|p| {
+            // Drop the registry copy of a crate whose override resolved to the same version
+            !(source_kind(p.source.as_deref()) == "registry"
+                && overridden.contains(&(p.name.clone(), p.version.clone())))
+        })
+        .map(|p| {
+            let kind = source_kind(p.source.as_deref());
+            let is_patched = patched.contains_key(&p.name) && kind != "registry";
+
+            let local_license = if is_patched && kind == "path" {
+                patched
+                    .get(&p.name)
+                    .cloned()
+                    .flatten()
+                    .and_then(|dir| license_from_local_crate(&dir))
+            } else {
+                None
+            };
+
+            let (license, source) = match local_license {
+                Some(l) => (Some(l), LicenseSource::Manifest),
+                None => {
+                    let cached = license_from_cargo_cache(&p.name, &p.version);
+                    let source = if cached.is_some() {
+                        LicenseSource::Cache
+                    } else {
+                        LicenseSource::Unknown
+                    };
+                    (cached, source)
                 }
+            };
+
+            let mut notes = Vec::new();
+            if is_patched {
+                notes.push(format!(
+                    "patched via [patch] ({} source) — license may differ from the registry release",
+                    kind
+                ));
             }
+            if kind == "registry"
+                && registry_index
+                    .as_deref()
+                    .is_some_and(|index| is_yanked(index, &p.name, &p.version))
+            {
+                notes.push("yanked from the registry (per the local Cargo index cache)".to_string());
+            }
+
+            Dependency {
+                name: p.name,
+                version: p.version,
+                ecosystem: Ecosystem::Rust,
+                license_spdx: license.clone(),
+                license_raw: license,
+                risk: LicenseRisk::Unknown,
+                verdict: PolicyVerdict::Warn,
+                source,
+                notes,
+                is_new: false,
+                optional: false,
+                dev: false,
+                labels: Vec::new(),
+            }
+        })
+        .collect()
+}
+
+/// Parse the dependency tables of a `Cargo.toml` for a project without a
+/// `Cargo.lock`: `[dependencies]`, `[dev-dependencies]` (marked `dev`) and
+/// `[build-dependencies]`.
+///
+/// The version is the declared requirement (`"1.0"`, `"^0.4.20"`, `*` for git
+/// dependencies); path-only dependencies are local crates and skipped. The
+/// license comes from the Cargo cache when the requirement names one exact version.
+fn parse_cargo_manifest(content: &str) -> Result<Vec<Dependency>> {
+    let manifest: ManifestDependencies = toml::from_str(content)?;
+    let inherited = manifest
+        .workspace
+        .map(|w| w.dependencies)
+        .unwrap_or_default();
+
+    let tables = [
+        (&manifest.dependencies, false),
+        (&manifest.dev_dependencies, true),
+        (&manifest.build_dependencies, false),
+    ];
+    let mut seen = HashSet::new();
+    let mut deps = Vec::new();
+    for (table, dev) in tables {
+        for (key, spec) in table {
+            let declared = match spec.get("workspace").and_then(|v| v.as_bool()) {
+                Some(true) => inherited.get(key).unwrap_or(spec),
+                _ => spec,
+            };
+            let Some(version) = declared_requirement(declared) else {
+                continue;
+            };
+            // `foo = { package = "real-name", ... }` renames the dependency
+            let name = declared
+                .get("package")
+                .and_then(|v| v.as_str())
+                .unwrap_or(key);
+            if !seen.insert(name.to_string()) {
+                continue;
+            }
+
+            let license = exact_version(&version).and_then(|v| license_from_cargo_cache(name, v));
+            let source = if license.is_some() {
+                LicenseSource::Cache
+            } else {
+                LicenseSource::Unknown
+            };
+            deps.push(Dependency {
+                name: name.to_string(),
+                version,
+                ecosystem: Ecosystem::Rust,
+                license_spdx: license.clone(),
+                license_raw: license,
+                risk: LicenseRisk::Unknown,
+                verdict: PolicyVerdict::Warn,
+                source,
+                notes: Vec::new(),
+                is_new: false,
+                optional: spec.get("optional").and_then(|v| v.as_bool()) == Some(true),
+                dev,
+                labels: Vec::new(),
+            });
         }
     }
+    Ok(deps)
+}
 
-    None
+/// The version requirement of a `Cargo.toml` dependency: the string form, the
+/// `version` key, or `*` for a git dependency; `None` for a path-only one.
+fn declared_requirement(spec: &toml::Value) -> Option<String> {
+    if let Some(version) = spec.as_str() {
+        return Some(version.to_string());
+    }
+    let table = spec.as_table()?;
+    match table.get("version").and_then(|v| v.as_str()) {
+        Some(version) => Some(version.to_string()),
+        None => table.contains_key("git").then(|| "*".to_string()),
+    }
+}
+
+/// The version a requirement such as `1.0.200`, `=1.0.200` or `^1.0.200` starts
+/// from, when it names a full `major.minor.patch` version.
+fn exact_version(requirement: &str) -> Option<&str> {
+    let version = requirement.trim().trim_start_matches(['=', '^']).trim();
+    let is_number = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    let parts: Vec<&str> = version.split('.').collect();
+    (parts.len() == 3 && parts.iter().all(|p| is_number(p))).then_some(version)
 }
 
 /// Analyzer for Rust projects managed by Cargo.
 ///
 /// Parses `Cargo.lock` and returns all external crate dependencies,
 /// filtering out local workspace members (entries with no `source` field).
+/// Crates overridden through `[patch]` / `[replace]` in `Cargo.toml` are kept
+/// once and annotated (see [`resolve_packages`]). Without a lockfile, the
+/// dependencies declared in `Cargo.toml` are reported instead (see
+/// [`parse_cargo_manifest`]).
 pub struct RustAnalyzer;
 
 impl RustAnalyzer {
@@ -79,36 +451,23 @@ impl super::Analyzer for RustAnalyzer {
     fn analyze(&self, path: &Path) -> Result<Vec<Dependency>> {
         let lock_path = path.join("Cargo.lock");
         if !lock_path.exists() {
+            let manifest_path = path.join("Cargo.toml");
+            if manifest_path.exists() {
+                return Ok(
+                    super::read_and_parse(&manifest_path, parse_cargo_manifest).unwrap_or_default()
+                );
+            }
             return Ok(Vec::new());
         }
 
-        let content = std::fs::read_to_string(&lock_path)?;
-        let lock: CargoLock = toml::from_str(&content)?;
-
-        let deps = lock
-            .package
-            .into_iter()
-            // Skip local workspace members (they have no `source`)
-            .filter(|p| p.source.is_some())
-            .map(|p| {
-                let license = license_from_cargo_cache(&p.name, &p.version);
-                let source = if license.is_some() {
-                    LicenseSource::Cache
-                } else {
-                    LicenseSource::Unknown
-                };
-                Dependency {
-                    name: p.name,
-                    version: p.version,
-                    ecosystem: Ecosystem::Rust,
-                    license_spdx: license.clone(),
-                    license_raw: license,
-                    risk: LicenseRisk::Unknown,
-                    verdict: PolicyVerdict::Warn,
-                    source,
-                }
-            })
-            .collect();
+        let Some(lock) = super::read_and_parse(&lock_path, |content| {
+            Ok(toml::from_str::<CargoLock>(content)?)
+        }) else {
+            return Ok(Vec::new());
+        };
+
+        let patched = patched_crates(path);
+        let deps = resolve_packages(lock.package, &patched);
 
         Ok(deps)
     }
@@ -117,6 +476,8 @@ impl super::Analyzer for RustAnalyzer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analyzer::Analyzer;
+
     #[test]
     fn test_parse_cargo_lock() {
         let content = r#"
@@ -145,4 +506,252 @@ checksum = "def456"
         assert_eq!(external[0].name, "serde");
         assert_eq!(external[1].name, "tokio");
     }
+
+    #[test]
+    fn test_manifest_dependencies_without_lockfile() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("Cargo.toml"),
+            r#"
+[package]
+name = "my-lib"
+version = "0.1.0"
+
+[dependencies]
+serde = { version = "1.0", features = ["derive"] }
+log = "^0.4.20"
+rand = { version = "0.8", optional = true }
+local-helper = { path = "../helper" }
+forked = { git = "https://github.com/acme/forked" }
+json = { package = "serde_json", version = "=1.0.100" }
+anyhow = { workspace = true }
+
+[dev-dependencies]
+tempfile = "3"
+serde = "1.0"
+
+[build-dependencies]
+cc = "1.0.83"
+
+[workspace.dependencies]
+anyhow = "1.0.75"
+"#,
+        )
+        .unwrap();
+
+        let deps = RustAnalyzer::new().analyze(tmp.path()).unwrap();
+        let found: Vec<(&str, &str, bool, bool)> = deps
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_str(), d.dev, d.optional))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("anyhow", "1.0.75", false, false),
+                ("forked", "*", false, false),
+                ("serde_json", "=1.0.100", false, false),
+                ("log", "^0.4.20", false, false),
+                ("rand", "0.8", false, true),
+                ("serde", "1.0", false, false),
+                ("tempfile", "3", true, false),
+                ("cc", "1.0.83", false, false),
+            ]
+        );
+        assert_eq!(exact_version("=1.0.100"), Some("1.0.100"));
+        assert_eq!(exact_version("1.0"), None);
+        assert_eq!(exact_version(">=1.0.0, <2"), None);
+    }
+
+    #[test]
+    fn test_patched_crates_are_annotated_and_not_double_counted() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            r#"
+[package]
+name = "my-app"
+version = "0.1.0"
+
+[patch.crates-io]
+serde = { git = "https://github.com/example/serde", branch = "fix" }
+local-fork = { path = "vendor/local-fork" }
+"#,
+        )
+        .unwrap();
+        let fork = root.join("vendor").join("local-fork");
+        std::fs::create_dir_all(&fork).unwrap();
+        std::fs::write(
+            fork.join("Cargo.toml"),
+            "[package]\nname = \"local-fork\"\nversion = \"0.3.0\"\nlicense = \"MPL-2.0\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("Cargo.lock"),
+            r#"
+version = 3
+
+[[package]]
+name = "my-app"
+version = "0.1.0"
+
+[[package]]
+name = "serde"
+version = "1.0.150"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "serde"
+version = "1.0.150"
+source = "git+https://github.com/example/serde?branch=fix#abc123"
+
+[[package]]
+name = "local-fork"
+version = "0.3.0"
+
+[[package]]
+name = "tokio"
+version = "1.25.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[patch.unused]]
+name = "unused-crate"
+version = "0.1.0"
+source = "git+https://github.com/example/unused#def456"
+"#,
+        )
+        .unwrap();
+
+        let deps = RustAnalyzer::new().analyze(root).unwrap();
+        let names: Vec<&str> = deps.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["serde", "local-fork", "tokio"]);
+
+        let serde = &deps[0];
+        assert!(serde.notes[0].contains("git source"));
+
+        let fork = &deps[1];
+        assert_eq!(fork.license_raw.as_deref(), Some("MPL-2.0"));
+        assert!(matches!(fork.source, LicenseSource::Manifest));
+        assert!(fork.notes[0].contains("path source"));
+
+        assert!(deps[2].notes.is_empty());
+    }
+
+    #[test]
+    fn test_cached_manifest_with_only_license_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let crate_dir = tmp
+            .path()
+            .join("index.crates.io-6f17d22bba15001f")
+            .join("ring-0.16.20");
+        std::fs::create_dir_all(&crate_dir).unwrap();
+        std::fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"ring\"\nversion = \"0.16.20\"\nlicense-file = \"LICENSE\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            crate_dir.join("LICENSE"),
+            "Permission to use, copy, modify, and/or distribute this software for any\n\
+             purpose with or without fee is hereby granted, provided that the above\n\
+             copyright notice and this permission notice appear in all copies.\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            license_from_registry_src(tmp.path(), "ring", "0.16.20").as_deref(),
+            Some("ISC")
+        );
+        assert_eq!(
+            license_from_registry_src(tmp.path(), "ring", "0.17.0"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_cached_manifest_without_license_reads_license_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let crate_dir = tmp
+            .path()
+            .join("index.crates.io-6f17d22bba15001f")
+            .join("tiny-0.1.0");
+        std::fs::create_dir_all(&crate_dir).unwrap();
+        std::fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"tiny\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            crate_dir.join("LICENSE.md"),
+            "# MIT License\n\nPermission is hereby granted, free of charge, to any person\n\
+             obtaining a copy of this software and associated documentation files.\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            license_from_registry_src(tmp.path(), "tiny", "0.1.0").as_deref(),
+            Some("MIT")
+        );
+    }
+
+    #[test]
+    fn test_license_from_crate_archive() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let registry = tmp.path().join("index.crates.io-6f17d22bba15001f");
+        std::fs::create_dir_all(&registry).unwrap();
+
+        let file = std::fs::File::create(registry.join("itoa-1.0.11.crate")).unwrap();
+        let mut tar = tar::Builder::new(flate2::write::GzEncoder::new(
+            file,
+            flate2::Compression::default(),
+        ));
+        let manifest =
+            b"[package]\nname = \"itoa\"\nversion = \"1.0.11\"\nlicense = \"MIT OR Apache-2.0\"\n";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(manifest.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        tar.append_data(&mut header, "itoa-1.0.11/Cargo.toml", &manifest[..])
+            .unwrap();
+        tar.into_inner().unwrap().finish().unwrap();
+
+        assert_eq!(
+            license_from_crate_archives(tmp.path(), "itoa", "1.0.11").as_deref(),
+            Some("MIT OR Apache-2.0")
+        );
+        assert_eq!(
+            license_from_crate_archives(tmp.path(), "itoa", "1.0.10"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_yanked_version_from_index_cache() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = tmp
+            .path()
+            .join("index.crates.io-6f17d22bba15001f")
+            .join(".cache")
+            .join("se")
+            .join("rd");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut data = vec![3, 2, 0, 0, 0];
+        for field in [
+            "Unknown",
+            "1.0.0",
+            r#"{"name":"serde","vers":"1.0.0","deps":[],"cksum":"00","features":{},"yanked":false}"#,
+            "1.0.1",
+            r#"{"name":"serde","vers":"1.0.1","deps":[],"cksum":"00","features":{},"yanked":true}"#,
+        ] {
+            data.extend_from_slice(field.as_bytes());
+            data.push(0);
+        }
+        std::fs::write(dir.join("serde"), data).unwrap();
+
+        assert!(is_yanked(tmp.path(), "serde", "1.0.1"));
+        assert!(!is_yanked(tmp.path(), "serde", "1.0.0"));
+        assert!(!is_yanked(tmp.path(), "serde", "2.0.0"));
+        assert!(!is_yanked(tmp.path(), "tokio", "1.0.0"));
+    }
 }
@@ -0,0 +1,196 @@
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use tempfile::TempDir;
+
+/// Supported archive formats for scanning third-party deliverables.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ArchiveKind {
+    Zip,
+    TarGz,
+}
+
+/// A scan target extracted from an archive into a temporary directory.
+///
+/// The temporary directory is removed when this value is dropped, so callers
+/// must keep it alive for the duration of the scan and drop it before exiting.
+pub struct ExtractedArchive {
+    _dir: TempDir,
+    /// Directory to scan — named after the archive so project labels stay meaningful.
+    pub root: PathBuf,
+}
+
+fn archive_kind(path: &Path) -> Option<ArchiveKind> {
+    if !path.is_file() {
+        return None;
+    }
+    let name = path.file_name()?.to_str()?.to_lowercase();
+    if name.ends_with(".zip") {
+        Some(ArchiveKind::Zip)
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Some(ArchiveKind::TarGz)
+    } else {
+        None
+    }
+}
+
+/// Whether `path` is a `.zip`, `.tar.gz`, or `.tgz` file that [`extract`] unpacks.
+pub fn is_archive(path: &Path) -> bool {
+    archive_kind(path).is_some()
+}
+
+/// Extract `path` into a fresh temporary directory.
+///
+/// Returns `Ok(None)` when `path` is not a `.zip`, `.tar.gz`, or `.tgz` file.
+/// Entries that would escape the extraction directory (`../` or absolute paths)
+/// are rejected by the underlying `zip` / `tar` unpackers.
+pub fn extract(path: &Path) -> Result<Option<ExtractedArchive>> {
+    let Some(kind) = archive_kind(path) else {
+        return Ok(None);
+    };
+
+    let dir = tempfile::Builder::new()
+        .prefix("license-checkr-")
+        .tempdir()
+        .context("Failed to create temporary directory for archive extraction")?;
+    let root = dir.path().join(archive_stem(path));
+    std::fs::create_dir_all(&root)?;
+
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    match kind {
+        ArchiveKind::Zip => {
+            let mut zip = zip::ZipArchive::new(file)
+                .with_context(|| format!("Failed to read zip archive {}", path.display()))?;
+            zip.extract(&root)
+                .with_context(|| format!("Failed to extract {}", path.display()))?;
+        }
+        ArchiveKind::TarGz => {
+            let gz = flate2::read::GzDecoder::new(file);
+            tar::Archive::new(gz)
+                .unpack(&root)
+                .with_context(|| format!("Failed to extract {}", path.display()))?;
+        }
+    }
+
+    Ok(Some(ExtractedArchive { _dir: dir, root }))
+}
+
+/// Archive file name without its archive extension (`vendor-1.2.tar.gz` → `vendor-1.2`).
+fn archive_stem(path: &Path) -> String {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("archive");
+    let lower = name.to_lowercase();
+    for ext in [".tar.gz", ".tgz", ".zip"] {
+        if lower.ends_with(ext) {
+            return name[..name.len() - ext.len()].to_string();
+        }
+    }
+    name.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    use crate::analyzer::node::NodeAnalyzer;
+    use crate::analyzer::Analyzer;
+    use crate::detector::find_workspace_projects;
+
+    const LOCK: &str = r#"{
+  "lockfileVersion": 3,
+  "packages": {
+    "": { "name": "vendor-app", "version": "1.0.0" },
+    "node_modules/express": { "version": "4.18.2", "license": "MIT" },
+    "node_modules/left-pad": { "version": "1.3.0", "license": "WTFPL" }
+  }
+}"#;
+
+    #[test]
+    fn test_archive_stem() {
+        assert_eq!(
+            archive_stem(Path::new("/x/vendor-1.2.tar.gz")),
+            "vendor-1.2"
+        );
+        assert_eq!(archive_stem(Path::new("drop.ZIP")), "drop");
+        assert_eq!(archive_stem(Path::new("a.tgz")), "a");
+    }
+
+    #[test]
+    fn test_non_archive_is_ignored() {
+        let tmp = TempDir::new().unwrap();
+        assert!(extract(tmp.path()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_zip_scan_matches_extracted_directory() {
+        let tmp = TempDir::new().unwrap();
+
+        // Reference: the plain directory
+        let plain = tmp.path().join("plain").join("app");
+        std::fs::create_dir_all(&plain).unwrap();
+        std::fs::write(plain.join("package-lock.json"), LOCK).unwrap();
+
+        // Same project, zipped under a nested folder
+        let zip_path = tmp.path().join("deliverable.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        let opts = zip::write::SimpleFileOptions::default();
+        zip.add_directory("app/", opts).unwrap();
+        zip.start_file("app/package-lock.json", opts).unwrap();
+        zip.write_all(LOCK.as_bytes()).unwrap();
+        zip.finish().unwrap();
+
+        let extracted = extract(&zip_path).unwrap().unwrap();
+        let projects = find_workspace_projects(&extracted.root);
+        assert_eq!(projects.len(), 1);
+
+        let expected = NodeAnalyzer::new().analyze(&plain).unwrap();
+        let actual = NodeAnalyzer::new().analyze(&projects[0]).unwrap();
+        let key = |d: &crate::models::Dependency| {
+            (d.name.clone(), d.version.clone(), d.license_raw.clone())
+        };
+        assert_eq!(
+            actual.iter().map(key).collect::<Vec<_>>(),
+            expected.iter().map(key).collect::<Vec<_>>()
+        );
+
+        let root = extracted.root.clone();
+        drop(extracted);
+        assert!(
+            !root.exists(),
+            "temporary directory should be removed on drop"
+        );
+    }
+
+    #[test]
+    fn test_tar_gz_extracts() {
+        let tmp = TempDir::new().unwrap();
+        let tgz_path = tmp.path().join("drop.tar.gz");
+        {
+            let gz = flate2::write::GzEncoder::new(
+                File::create(&tgz_path).unwrap(),
+                flate2::Compression::default(),
+            );
+            let mut builder = tar::Builder::new(gz);
+            let mut header = tar::Header::new_gnu();
+            header.set_size(LOCK.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, "app/package-lock.json", LOCK.as_bytes())
+                .unwrap();
+            builder.into_inner().unwrap().finish().unwrap();
+        }
+
+        let extracted = extract(&tgz_path).unwrap().unwrap();
+        assert!(extracted.root.ends_with("drop"));
+        assert!(extracted
+            .root
+            .join("app")
+            .join("package-lock.json")
+            .exists());
+    }
+}
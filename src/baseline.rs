@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::models::{Dependency, PolicyVerdict};
+
+/// Differences between a stored baseline scan and the current scan.
+#[derive(Debug, Default)]
+pub struct BaselineDiff {
+    /// Dependencies present now but not in the baseline.
+    pub added: Vec<Dependency>,
+    /// Dependencies present in the baseline but no longer found.
+    pub removed: Vec<Dependency>,
+    /// `(baseline, current)` pairs whose version, license, or verdict changed.
+    pub changed: Vec<(Dependency, Dependency)>,
+    /// `(baseline, current)` pairs whose license string changed, even if the verdict
+    /// did not — a subset of `changed`, surfaced separately to catch relicensing.
+    pub relicensed: Vec<(Dependency, Dependency)>,
+}
+
+impl BaselineDiff {
+    /// Returns `true` when the current scan matches the baseline exactly.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Identity of a dependency across scans: ecosystem + package name.
+pub fn identity(dep: &Dependency) -> String {
+    format!("{}:{}", dep.ecosystem, dep.name)
+}
+
+fn license_of(dep: &Dependency) -> Option<&str> {
+    dep.license_spdx.as_deref().or(dep.license_raw.as_deref())
+}
+
+/// Write the scan result as a JSON baseline (same shape as `--report json`).
+pub fn write(path: &Path, deps: &[&Dependency]) -> Result<()> {
+    let json = serde_json::to_string_pretty(deps)?;
+    std::fs::write(path, json)
+        .with_context(|| format!("Failed to write baseline to {}", path.display()))
+}
+
+/// Read a baseline previously written by [`write`] (or captured from `--report json`).
+pub fn read(path: &Path) -> Result<Vec<Dependency>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read baseline {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("{} is not a valid baseline JSON file", path.display()))
+}
+
+/// Compare the current scan against a baseline, matching entries by ecosystem and name.
+///
+/// When a package appears several times (e.g. two versions in one lockfile), entries
+/// are paired in order of appearance.
+pub fn diff(baseline: &[Dependency], current: &[&Dependency]) -> BaselineDiff {
+    let mut remaining: HashMap<String, Vec<&Dependency>> = HashMap::new();
+    for dep in baseline {
+        remaining.entry(identity(dep)).or_default().push(dep);
+    }
+
+    let mut result = BaselineDiff::default();
+    for dep in current {
+        let previous = remaining.get_mut(&identity(dep)).and_then(|v| {
+            // Prefer an exact version match so unrelated duplicates don't look "changed"
+            let idx = v.iter().position(|b| b.version == dep.version).unwrap_or(0);
+            (!v.is_empty()).then(|| v.remove(idx))
+        });
+        match previous {
+            None => result.added.push((*dep).clone()),
+            Some(prev) => {
+                if license_of(prev) != license_of(dep) {
+                    result.relicensed.push((prev.clone(), (*dep).clone()));
+                }
+                if prev.version != dep.version
+                    || license_of(prev) != license_of(dep)
+                    || prev.verdict != dep.verdict
+                {
+                    result.changed.push((prev.clone(), (*dep).clone()));
+                }
+            }
+        }
+    }
+
+    for deps in remaining.into_values() {
+        result.removed.extend(deps.into_iter().cloned());
+    }
+    result.removed.sort_by_key(identity);
+
+    result
+}
+
+/// Returns `true` if `dep` already failed policy in the baseline with the same
+/// version, license and verdict (anything but pass, since `--exit-code-warn`
+/// can fail on warnings) — i.e. it is an accepted, pre-existing violation.
+pub fn is_known_violation(baseline: &[Dependency], dep: &Dependency) -> bool {
+    baseline.iter().any(|b| {
+        b.verdict != PolicyVerdict::Pass
+            && b.verdict == dep.verdict
+            && identity(b) == identity(dep)
+            && b.version == dep.version
+            && license_of(b) == license_of(dep)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::dep;
+
+    #[test]
+    fn test_write_read_diff_round_trip_has_no_changes() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("baseline.json");
+        let deps = [
+            dep("express", Some("MIT"), PolicyVerdict::Pass).with_version("4.18.2"),
+            dep("gpl-thing", Some("GPL-3.0"), PolicyVerdict::Error),
+        ];
+        let refs: Vec<&Dependency> = deps.iter().collect();
+
+        write(&path, &refs).unwrap();
+        let loaded = read(&path).unwrap();
+        let d = diff(&loaded, &refs);
+
+        assert!(d.is_empty(), "unexpected diff: {:?}", d);
+    }
+
+    #[test]
+    fn test_diff_detects_added_removed_and_changed() {
+        let baseline = vec![
+            dep("a", Some("MIT"), PolicyVerdict::Pass),
+            dep("b", Some("MIT"), PolicyVerdict::Pass),
+        ];
+        let current = [
+            dep("a", Some("MIT"), PolicyVerdict::Pass).with_version("1.1.0"),
+            dep("c", Some("GPL-3.0"), PolicyVerdict::Error).with_version("2.0.0"),
+        ];
+        let refs: Vec<&Dependency> = current.iter().collect();
+        let d = diff(&baseline, &refs);
+
+        assert_eq!(d.added.len(), 1);
+        assert_eq!(d.added[0].name, "c");
+        assert_eq!(d.removed.len(), 1);
+        assert_eq!(d.removed[0].name, "b");
+        assert_eq!(d.changed.len(), 1);
+        assert_eq!(d.changed[0].1.version, "1.1.0");
+    }
+
+    #[test]
+    fn test_diff_flags_relicense_even_without_verdict_change() {
+        let baseline = vec![
+            dep("mycrate", Some("MIT"), PolicyVerdict::Warn),
+            dep("stable", Some("MIT"), PolicyVerdict::Pass),
+        ];
+        let current = [
+            dep("mycrate", Some("AGPL-3.0"), PolicyVerdict::Warn).with_version("2.0.0"),
+            dep("stable", Some("MIT"), PolicyVerdict::Pass).with_version("1.1.0"),
+        ];
+        let refs: Vec<&Dependency> = current.iter().collect();
+        let d = diff(&baseline, &refs);
+
+        assert_eq!(d.relicensed.len(), 1);
+        let (prev, cur) = &d.relicensed[0];
+        assert_eq!(prev.license_spdx.as_deref(), Some("MIT"));
+        assert_eq!(cur.license_spdx.as_deref(), Some("AGPL-3.0"));
+        assert_eq!(d.changed.len(), 2);
+    }
+
+    #[test]
+    fn test_known_violation_requires_same_version_and_license() {
+        let baseline = vec![dep("x", Some("GPL-3.0"), PolicyVerdict::Error)];
+        assert!(is_known_violation(
+            &baseline,
+            &dep("x", Some("GPL-3.0"), PolicyVerdict::Error)
+        ));
+        assert!(!is_known_violation(
+            &baseline,
+            &dep("x", Some("GPL-3.0"), PolicyVerdict::Error).with_version("2.0.0")
+        ));
+        assert!(!is_known_violation(
+            &baseline,
+            &dep("y", Some("GPL-3.0"), PolicyVerdict::Error)
+        ));
+    }
+}
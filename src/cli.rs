@@ -2,43 +2,227 @@ use std::path::PathBuf;
 
 use clap::Parser;
 
-use crate::models::Ecosystem;
+use crate::models::{Ecosystem, LicenseRisk, PolicyVerdict};
+use crate::report::summary::SummaryFormat;
 
 #[derive(Parser, Debug)]
 #[command(
     name = "license-checkr",
     about = "Scan project dependencies and check license compliance",
-    version
+    version,
+    subcommand_precedence_over_arg = true
 )]
 pub struct Cli {
-    /// Project path to scan
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Project path(s) to scan, or a `.zip` / `.tar.gz` archive to extract and scan.
+    /// Several paths are scanned as independent projects and reported like a workspace
     #[arg(default_value = ".")]
-    pub path: PathBuf,
+    pub path: Vec<PathBuf>,
 
     /// Fetch license data from package registries
     #[arg(long)]
     pub online: bool,
 
+    /// Maximum concurrent `--online` requests per registry host (crates.io, npm, PyPI, Maven)
+    #[arg(long, value_name = "N", default_value_t = crate::registry::DEFAULT_CONCURRENCY_PER_HOST)]
+    pub registry_concurrency_per_host: usize,
+
+    /// Maximum concurrent `--online` requests in total, across all registries
+    #[arg(short = 'j', long, value_name = "N", default_value_t = crate::registry::DEFAULT_JOBS)]
+    pub jobs: usize,
+
+    /// Query the registries for every `--online` lookup instead of reusing cached answers
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Give up on an `--online` request after this many seconds
+    #[arg(long, value_name = "SECONDS", default_value_t = crate::registry::DEFAULT_TIMEOUT_SECS)]
+    pub registry_timeout: u64,
+
+    /// `User-Agent` header sent with `--online` requests
+    #[arg(long, value_name = "UA", default_value = crate::registry::DEFAULT_USER_AGENT)]
+    pub user_agent: String,
+
+    /// Do not ask deps.dev for licenses the package's own registry does not provide
+    #[arg(long)]
+    pub no_depsdev: bool,
+
+    /// Directory for the `--online` lookup cache [default: ~/.cache/license-checkr]
+    #[arg(long, value_name = "DIR")]
+    pub cache_dir: Option<PathBuf>,
+
     /// Recursively scan subdirectories for sub-projects (workspace mode)
     #[arg(short = 'r', long)]
     pub recursive: bool,
 
-    /// Policy config file [default: ./.license-checkr/config.toml, fallback ~/.config/license-checkr/config.toml]
+    /// Policy config file [default: nearest .license-checkr/config.toml up to the repo root, fallback ~/.config/license-checkr/config.toml]
     #[arg(long)]
     pub config: Option<PathBuf>,
 
-    /// Report format
+    /// Merge the config's `[profiles.<NAME>.policy]` over the base policy (e.g. `release`)
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Report format (repeatable, e.g. `--report json --report pdf`)
     #[arg(long, default_value = "terminal", value_name = "FORMAT")]
-    pub report: ReportFormat,
+    pub report: Vec<ReportFormat>,
+
+    /// Write every file-based report format into DIR (created if needed) as `license-report.<ext>`
+    #[arg(long, value_name = "DIR")]
+    pub output_dir: Option<PathBuf>,
+
+    /// Write the one file-based --report format to FILE instead of stdout (the terminal report stays on stdout)
+    #[arg(short, long, value_name = "FILE", conflicts_with_all = ["output_dir", "pdf"])]
+    pub output: Option<PathBuf>,
 
     /// PDF output path; use without value to default to license-report.pdf
     #[arg(long, value_name = "FILE", num_args = 0..=1, default_missing_value = "license-report.pdf")]
     pub pdf: Option<PathBuf>,
 
+    /// PNG logo shown in the top-right of the PDF cover
+    #[arg(long, value_name = "PNG")]
+    pub pdf_logo: Option<PathBuf>,
+
+    /// Heading of the PDF cover instead of "License Compliance Report"
+    #[arg(long, value_name = "TEXT")]
+    pub pdf_title: Option<String>,
+
+    /// Skip dependencies the manifest marks as optional (npm `optional` / `devOptional`, Maven `<optional>`)
+    #[arg(long)]
+    pub exclude_optional: bool,
+
+    /// Skip development-only dependencies (npm `devDependencies`, Pipfile `develop`, PDM / Hatch dev groups, Maven `test` / `provided` scope, Gradle `testImplementation`)
+    #[arg(long, overrides_with = "include_dev")]
+    pub exclude_dev: bool,
+
+    /// Scan development-only dependencies (the default; cancels an earlier --exclude-dev, e.g. from an alias)
+    #[arg(long, overrides_with = "exclude_dev")]
+    pub include_dev: bool,
+
+    /// Leave a package out of the reports and the exit code, as `name` (case-insensitive) or `name@version` (repeatable)
+    #[arg(long, value_name = "PACKAGE")]
+    pub ignore: Vec<String>,
+
+    /// Ignore a package and the dependencies only it pulls in, per Cargo.lock / package-lock.json (repeatable)
+    #[arg(long, value_name = "NAME")]
+    pub ignore_tree: Vec<String>,
+
+    /// Only report dependencies carrying this `[labels]` label (repeatable; any match keeps a dependency)
+    #[arg(long, value_name = "LABEL")]
+    pub filter_label: Vec<String>,
+
+    /// Workspace scans: only scan projects whose manifests or lockfiles changed since this git ref
+    #[arg(long, value_name = "REF")]
+    pub since: Option<String>,
+
     /// Exclude an ecosystem from scanning (repeatable)
     #[arg(long = "exclude-lang", value_name = "LANG")]
     pub exclude_lang: Vec<EcosystemArg>,
 
+    /// Warn when fewer than N dependencies are found (overrides `[limits] min_dependencies`)
+    #[arg(long = "min-deps", value_name = "N")]
+    pub min_deps: Option<usize>,
+
+    /// Abort when more than N dependencies are found in total, before `--online` lookups
+    #[arg(long = "max-deps", value_name = "N")]
+    pub max_deps: Option<usize>,
+
+    /// With `--max-deps`, keep the first N dependencies and warn instead of aborting
+    #[arg(long, requires = "max_deps")]
+    pub truncate: bool,
+
+    /// Treat sanity-limit warnings (e.g. `--min-deps`) as errors
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Print every SPDX id with a known risk level, grouped by risk, and exit
+    /// (as JSON with `--report json`)
+    #[arg(long)]
+    pub list_licenses: bool,
+
+    /// On a non-zero exit, print a final stderr line naming the offending dependencies
+    #[arg(long)]
+    pub print_exit_reason: bool,
+
+    /// Only display dependencies at or above this risk level (terminal report; counts and exit code are unaffected)
+    #[arg(long = "min-risk", value_name = "LEVEL")]
+    pub min_risk: Option<RiskArg>,
+
+    /// Sort the terminal tables by this column (`name` when given without a value; `risk` and `verdict` list the most severe first)
+    #[arg(long, value_name = "COLUMN", num_args = 0..=1, default_missing_value = "name")]
+    pub sort: Option<SortKey>,
+
+    /// Terminal table columns to show, in this order (comma-separated, e.g. `name,license,verdict`)
+    #[arg(long, value_name = "LIST", value_delimiter = ',')]
+    pub columns: Vec<Column>,
+
+    /// Split the terminal tables into one section per group (e.g. `ecosystem`); the summary box is unchanged
+    #[arg(long, value_name = "KEY")]
+    pub group_by: Option<GroupBy>,
+
+    /// Compare the scan against a baseline JSON file and print what changed
+    #[arg(long, value_name = "FILE")]
+    pub baseline: Option<PathBuf>,
+
+    /// Lowest verdict that makes the run exit non-zero
+    #[arg(long, value_name = "VERDICT", default_value = "error")]
+    pub fail_on: FailOn,
+
+    /// Exit code when policy errors are found (default 1; 0 never fails on them)
+    #[arg(long, value_name = "CODE")]
+    pub exit_code_error: Option<u8>,
+
+    /// Exit code when reviews are pending (default 1 with `--fail-on review`, else 0)
+    #[arg(long, value_name = "CODE")]
+    pub exit_code_review: Option<u8>,
+
+    /// Exit code when warnings are found (default 0)
+    #[arg(long, value_name = "CODE")]
+    pub exit_code_warn: Option<u8>,
+
+    /// Also write the `--baseline` changes as Markdown to FILE (e.g. for a PR comment)
+    #[arg(long, value_name = "FILE", requires = "baseline")]
+    pub baseline_markdown: Option<PathBuf>,
+
+    /// Only fail on policy errors that are not already in the `--baseline`
+    #[arg(long, requires = "baseline")]
+    pub fail_on_new: bool,
+
+    /// Write the current scan to FILE as the new baseline (never fails the run)
+    #[arg(long, value_name = "FILE", alias = "write-baseline")]
+    pub baseline_update: Option<PathBuf>,
+
+    /// Remember each run locally and tag dependencies that are new since the last one
+    #[arg(long)]
+    pub track_history: bool,
+
+    /// Reuse the parsed dependencies of projects whose manifests are unchanged since the last run
+    #[arg(long)]
+    pub analysis_cache: bool,
+
+    /// Show scan timestamps in local time instead of UTC
+    #[arg(long)]
+    pub local_time: bool,
+
+    /// POST a JSON summary of failing dependencies to URL after the scan (best effort)
+    #[arg(
+        long,
+        value_name = "URL",
+        env = "LICENSE_CHECKR_WEBHOOK",
+        hide_env_values = true
+    )]
+    pub webhook: Option<String>,
+
+    /// Send the `--webhook` notification even when nothing fails
+    #[arg(long, requires = "webhook")]
+    pub webhook_always: bool,
+
+    /// Disable colored output (the `NO_COLOR` environment variable is honoured too)
+    #[arg(long)]
+    pub no_color: bool,
+
     /// Show all dependencies (not just warnings/errors)
     #[arg(short, long)]
     pub verbose: bool,
@@ -46,13 +230,81 @@ pub struct Cli {
     /// Only print summary line
     #[arg(short, long)]
     pub quiet: bool,
+
+    /// Show `--online` lookup progress even with `--quiet` (a line every 50 lookups when stderr is not a terminal)
+    #[arg(long, conflicts_with = "no_progress")]
+    pub progress: bool,
+
+    /// Never show the `--online` progress bar
+    #[arg(long)]
+    pub no_progress: bool,
+
+    /// Template for the `--quiet` summary line, e.g. `total={total} error={error}`.
+    /// Placeholders: {total} {pass} {warn} {review} {error} {projects}
+    #[arg(long, value_name = "TEMPLATE", requires = "quiet")]
+    pub summary_format: Option<SummaryFormat>,
+
+    /// Write warnings, notes and progress messages to stderr as JSON lines (`{"level", "message", "file"?, "package"?}`)
+    #[arg(long)]
+    pub json_diagnostics: bool,
 }
 
-#[derive(Debug, Clone, clap::ValueEnum)]
+#[derive(Debug, Clone, clap::Subcommand)]
+pub enum Command {
+    /// Check config discovery, the Cargo cache and registry connectivity, then exit 0
+    Doctor,
+    /// Write a commented `.license-checkr/config.toml` (or the `--config` file)
+    /// with the built-in default policy
+    Init {
+        /// Overwrite an existing config file
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, clap::ValueEnum)]
 pub enum ReportFormat {
     Terminal,
     Json,
+    /// Header-less tab-separated lines: name, version, ecosystem, license, verdict
+    Tsv,
     Pdf,
+    /// CycloneDX 1.5 JSON SBOM
+    #[value(name = "cyclonedx")]
+    CycloneDx,
+    /// SPDX 2.3 JSON SBOM
+    Spdx,
+    /// SARIF 2.1.0 log of failing and flagged dependencies, for code scanning
+    Sarif,
+    /// GitHub-flavored Markdown summary for PR comments
+    Markdown,
+    /// JUnit XML: a test case per dependency, failing on `error` verdicts
+    #[value(name = "junit")]
+    JUnit,
+}
+
+#[derive(Debug, Clone, clap::ValueEnum)]
+pub enum FailOn {
+    /// Fail only on `error` verdicts
+    Error,
+    /// Also fail on `review` verdicts
+    Review,
+    /// Also fail on `warn` (and `review`) verdicts
+    Warn,
+    /// Never fail on verdicts (report only)
+    Never,
+}
+
+impl FailOn {
+    /// Lowest verdict that fails the run; `None` for `never`.
+    pub fn threshold(&self) -> Option<PolicyVerdict> {
+        match self {
+            FailOn::Error => Some(PolicyVerdict::Error),
+            FailOn::Review => Some(PolicyVerdict::Review),
+            FailOn::Warn => Some(PolicyVerdict::Warn),
+            FailOn::Never => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, clap::ValueEnum)]
@@ -62,6 +314,11 @@ pub enum EcosystemArg {
     Java,
     Node,
     Dotnet,
+    Clojure,
+    Ocaml,
+    Go,
+    Ruby,
+    Php,
 }
 
 impl From<&EcosystemArg> for Ecosystem {
@@ -72,6 +329,60 @@ impl From<&EcosystemArg> for Ecosystem {
             EcosystemArg::Java => Ecosystem::Java,
             EcosystemArg::Node => Ecosystem::Node,
             EcosystemArg::Dotnet => Ecosystem::DotNet,
+            EcosystemArg::Clojure => Ecosystem::Clojure,
+            EcosystemArg::Ocaml => Ecosystem::OCaml,
+            EcosystemArg::Go => Ecosystem::Go,
+            EcosystemArg::Ruby => Ecosystem::Ruby,
+            EcosystemArg::Php => Ecosystem::Php,
+        }
+    }
+}
+
+/// `--sort` key for the terminal tables.
+#[derive(Debug, Clone, PartialEq, clap::ValueEnum)]
+pub enum SortKey {
+    Name,
+    Version,
+    License,
+    /// Most severe risk first, unknown last
+    Risk,
+    /// Most severe verdict first
+    Verdict,
+}
+
+/// `--group-by` key for the terminal tables.
+#[derive(Debug, Clone, PartialEq, clap::ValueEnum)]
+pub enum GroupBy {
+    /// One section per ecosystem, each with its own tables and counts
+    Ecosystem,
+}
+
+/// A `--columns` entry of the terminal tables.
+#[derive(Debug, Clone, PartialEq, clap::ValueEnum)]
+pub enum Column {
+    Name,
+    Version,
+    Ecosystem,
+    License,
+    Risk,
+    Verdict,
+}
+
+#[derive(Debug, Clone, clap::ValueEnum)]
+pub enum RiskArg {
+    Permissive,
+    WeakCopyleft,
+    StrongCopyleft,
+    Proprietary,
+}
+
+impl From<&RiskArg> for LicenseRisk {
+    fn from(arg: &RiskArg) -> Self {
+        match arg {
+            RiskArg::Permissive => LicenseRisk::Permissive,
+            RiskArg::WeakCopyleft => LicenseRisk::WeakCopyleft,
+            RiskArg::StrongCopyleft => LicenseRisk::StrongCopyleft,
+            RiskArg::Proprietary => LicenseRisk::Proprietary,
         }
     }
 }
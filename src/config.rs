@@ -1,16 +1,146 @@
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use globset::{Glob, GlobMatcher};
 use serde::Deserialize;
+use toml::Spanned;
 
-use crate::models::PolicyVerdict;
+use crate::diagnostics::Diagnostic;
+use crate::license::classifier::classify;
+use crate::license::expression::{tokenize_spdx, Token};
+use crate::license::spdx::{fuzzy, id_variants};
+use crate::models::{LicenseRisk, PolicyVerdict};
 
 /// Root configuration structure, deserialized from `.license-checkr/config.toml`.
 #[derive(Debug, Deserialize)]
 pub struct Config {
     /// License policy rules.
     pub policy: PolicyConfig,
+    /// Sanity limits on scan results.
+    #[serde(default)]
+    pub limits: LimitsConfig,
+    /// Named policy variants selected with `--profile`, from `[profiles.<name>.policy]`.
+    #[serde(default)]
+    pub profiles: HashMap<String, ProfileConfig>,
+    /// Report labels per package pattern, from `[labels]` (see [`Config::labels_for`]).
+    #[serde(default)]
+    pub labels: HashMap<PackagePattern, LabelList>,
+    /// License strings mapped to SPDX ids, from `[normalize]`; overrides the
+    /// built-in [`fuzzy`] descriptors (see [`Config::normalized_license`]).
+    #[serde(default)]
+    pub normalize: HashMap<String, String>,
+}
+
+/// The labels of one `[labels]` entry: a single string or a list.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum LabelList {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// One `[profiles.<name>]` table.
+#[derive(Debug, Default, Deserialize)]
+pub struct ProfileConfig {
+    /// Policy overrides merged over the base `[policy]`.
+    #[serde(default)]
+    pub policy: ProfilePolicy,
+}
+
+/// `[profiles.<name>.policy]`: every field is optional and replaces the base value;
+/// `licenses`, `packages` and `risk` entries are merged per key.
+#[derive(Debug, Default, Deserialize)]
+pub struct ProfilePolicy {
+    pub default: Option<PolicyAction>,
+    #[serde(default)]
+    pub licenses: HashMap<String, PolicyAction>,
+    #[serde(default)]
+    pub packages: HashMap<PackagePattern, PolicyAction>,
+    #[serde(default)]
+    pub risk: RiskPolicy,
+    pub mode: Option<PolicyMode>,
+    pub allowed_packages: Option<Vec<PackagePattern>>,
+    pub combine: Option<CombineStrategy>,
+    pub or_requires_allowed: Option<bool>,
+    pub license_ref_risk: Option<RiskLevel>,
+}
+
+impl Config {
+    /// Merge the policy of profile `name` over the base `[policy]`.
+    ///
+    /// Returns `false` (leaving the base policy untouched) when the config has no
+    /// such profile.
+    pub fn apply_profile(&mut self, name: &str) -> bool {
+        let Some(profile) = self.profiles.remove(name) else {
+            return false;
+        };
+        let overrides = profile.policy;
+        if let Some(default) = overrides.default {
+            self.policy.default = default;
+        }
+        self.policy.licenses.extend(overrides.licenses);
+        self.policy.packages.extend(overrides.packages);
+        self.policy.risk.extend(overrides.risk);
+        if let Some(mode) = overrides.mode {
+            self.policy.mode = mode;
+        }
+        if let Some(allowed) = overrides.allowed_packages {
+            self.policy.allowed_packages = allowed;
+        }
+        if let Some(combine) = overrides.combine {
+            self.policy.combine = combine;
+        }
+        if let Some(or_requires_allowed) = overrides.or_requires_allowed {
+            self.policy.or_requires_allowed = or_requires_allowed;
+        }
+        if let Some(risk) = overrides.license_ref_risk {
+            self.policy.license_ref_risk = risk;
+        }
+        true
+    }
+
+    /// Labels of every `[labels]` pattern matching the package, sorted and
+    /// deduplicated. Patterns are `name` or `name@version` globs, as in
+    /// `[policy.packages]` (e.g. `"@acme/*"`, `"openssl@1.*"`).
+    pub fn labels_for(&self, name: &str, version: &str) -> Vec<String> {
+        let mut labels: Vec<String> = self
+            .labels
+            .iter()
+            .filter(|(pattern, _)| pattern.matches(name, version))
+            .flat_map(|(_, labels)| match labels {
+                LabelList::One(label) => std::slice::from_ref(label),
+                LabelList::Many(labels) => labels.as_slice(),
+            })
+            .cloned()
+            .collect();
+        labels.sort();
+        labels.dedup();
+        labels
+    }
+
+    /// The SPDX id a fuzzy license string resolves to: the `[normalize]` entry
+    /// (keys match case-insensitively), else the built-in [`fuzzy`] mapping.
+    /// `None` leaves the license as it is.
+    pub fn normalized_license(&self, license: &str) -> Option<String> {
+        self.custom_license(license)
+            .or_else(|| fuzzy(license.trim()).map(str::to_string))
+    }
+
+    /// Risk level of `license`, with `LicenseRef-` ids at `[policy]
+    /// license_ref_risk`.
+    pub fn classify(&self, license: &str) -> LicenseRisk {
+        classify(license, &self.policy.license_ref_risk.into())
+    }
+
+    /// The `[normalize]` entry for `license` alone (keys match case-insensitively).
+    pub fn custom_license(&self, license: &str) -> Option<String> {
+        let license = license.trim();
+        self.normalize
+            .iter()
+            .find(|(raw, _)| raw.trim().eq_ignore_ascii_case(license))
+            .map(|(_, id)| id.clone())
+    }
 }
 
 /// Defines how licenses are evaluated.
@@ -23,6 +153,159 @@ pub struct PolicyConfig {
     /// Per-license overrides keyed by SPDX identifier (e.g. `"MIT"`, `"GPL-3.0"`).
     #[serde(default)]
     pub licenses: HashMap<String, PolicyAction>,
+    /// Per-package exceptions keyed by `name` or `name@version`, from
+    /// `[policy.packages]`; both parts may be glob patterns (e.g. `"@acme/*"`,
+    /// `"{aws,azure}-sdk-*"`).
+    /// They override the license rules and the allowlist.
+    #[serde(default)]
+    pub packages: HashMap<PackagePattern, PolicyAction>,
+    /// Verdicts per risk level for licenses without a `licenses` entry, from
+    /// `[policy.risk]`; levels left out fall back to `default`.
+    #[serde(default)]
+    pub risk: RiskPolicy,
+    /// How dependencies are gated: by license only (default) or by an explicit
+    /// package allowlist.
+    #[serde(default)]
+    pub mode: PolicyMode,
+    /// Packages permitted under `mode = "package-allowlist"`, as `name` or
+    /// `name@version` glob patterns like the `packages` keys (e.g. `"tokio@1.*"`).
+    #[serde(default)]
+    pub allowed_packages: Vec<PackagePattern>,
+    /// How `OR` / `AND` combine component verdicts in compound expressions.
+    #[serde(default)]
+    pub combine: CombineStrategy,
+    /// An `OR` passes only when an operand has an explicit `pass` rule in
+    /// `licenses`; one that would pass through `default` alone is a warning.
+    #[serde(default)]
+    pub or_requires_allowed: bool,
+    /// Risk level of custom `LicenseRef-…` ids; defaults to `proprietary`.
+    #[serde(default = "default_license_ref_risk")]
+    pub license_ref_risk: RiskLevel,
+}
+
+/// A [`LicenseRisk`] as written in the config (`weak-copyleft`, …).
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RiskLevel {
+    Permissive,
+    WeakCopyleft,
+    StrongCopyleft,
+    Proprietary,
+    Unknown,
+}
+
+impl From<RiskLevel> for LicenseRisk {
+    fn from(level: RiskLevel) -> Self {
+        match level {
+            RiskLevel::Permissive => LicenseRisk::Permissive,
+            RiskLevel::WeakCopyleft => LicenseRisk::WeakCopyleft,
+            RiskLevel::StrongCopyleft => LicenseRisk::StrongCopyleft,
+            RiskLevel::Proprietary => LicenseRisk::Proprietary,
+            RiskLevel::Unknown => LicenseRisk::Unknown,
+        }
+    }
+}
+
+fn default_license_ref_risk() -> RiskLevel {
+    RiskLevel::Proprietary
+}
+
+/// `[policy.risk]`: an optional action per [`LicenseRisk`] level, keyed like
+/// `--min-risk` values (`weak-copyleft`, …) plus `unknown`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct RiskPolicy {
+    pub permissive: Option<PolicyAction>,
+    pub weak_copyleft: Option<PolicyAction>,
+    pub strong_copyleft: Option<PolicyAction>,
+    pub proprietary: Option<PolicyAction>,
+    pub unknown: Option<PolicyAction>,
+}
+
+impl RiskPolicy {
+    /// The action configured for `risk`, if any.
+    pub fn action(&self, risk: &LicenseRisk) -> Option<&PolicyAction> {
+        match risk {
+            LicenseRisk::Permissive => self.permissive.as_ref(),
+            LicenseRisk::WeakCopyleft => self.weak_copyleft.as_ref(),
+            LicenseRisk::StrongCopyleft => self.strong_copyleft.as_ref(),
+            LicenseRisk::Proprietary => self.proprietary.as_ref(),
+            LicenseRisk::Unknown => self.unknown.as_ref(),
+        }
+    }
+
+    /// Replace the levels `overrides` sets, keeping the others.
+    fn extend(&mut self, overrides: RiskPolicy) {
+        self.permissive = overrides.permissive.or(self.permissive.take());
+        self.weak_copyleft = overrides.weak_copyleft.or(self.weak_copyleft.take());
+        self.strong_copyleft = overrides.strong_copyleft.or(self.strong_copyleft.take());
+        self.proprietary = overrides.proprietary.or(self.proprietary.take());
+        self.unknown = overrides.unknown.or(self.unknown.take());
+    }
+}
+
+/// Policy enforcement mode, deserialized from `[policy] mode`: `license`,
+/// `package-allowlist` or `license-allowlist`. A bare `allowlist` could mean
+/// either of the last two and is rejected by [`validate_config`].
+#[derive(Debug, Default, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PolicyMode {
+    /// Verdicts come from the license rules alone.
+    #[default]
+    License,
+    /// Any dependency not matching `allowed_packages` is an error, regardless of
+    /// its license. Allowed packages are still evaluated against the license rules.
+    PackageAllowlist,
+    /// `licenses` entries set to `pass` are the only accepted licenses: every
+    /// other id (including `unknown` and ids listed as `warn`) is an error, and
+    /// `default` and `[policy.risk]` are ignored.
+    LicenseAllowlist,
+}
+
+/// Verdict combination for compound SPDX expressions, deserialized from
+/// `[policy] combine`.
+#[derive(Debug, Default, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CombineStrategy {
+    /// `OR` is a choice (least severe operand wins); `AND` needs every operand
+    /// (most severe wins).
+    #[default]
+    Standard,
+    /// Every component must be acceptable: `OR` resolves like `AND`, so
+    /// `MIT OR GPL-3.0` is as severe as `GPL-3.0`.
+    Conservative,
+}
+
+impl CombineStrategy {
+    /// Combine the operands of an `OR`.
+    fn or(self, a: Eval, b: Eval) -> Eval {
+        match self {
+            CombineStrategy::Standard => Eval {
+                allowed: a.allowed || b.allowed,
+                verdict: verdict_or(a.verdict, b.verdict),
+            },
+            CombineStrategy::Conservative => self.and(a, b),
+        }
+    }
+
+    /// Combine the operands of an `AND`.
+    fn and(self, a: Eval, b: Eval) -> Eval {
+        Eval {
+            allowed: a.allowed && b.allowed,
+            verdict: verdict_and(a.verdict, b.verdict),
+        }
+    }
+}
+
+/// Sanity checks applied to the scan result as a whole, deserialized from `[limits]`.
+#[derive(Debug, Default, Deserialize)]
+pub struct LimitsConfig {
+    /// Minimum number of dependencies a scan is expected to find.
+    ///
+    /// A count below this threshold usually means a manifest failed to parse
+    /// (e.g. after a lockfile format change) rather than a genuinely tiny project.
+    #[serde(default)]
+    pub min_dependencies: Option<usize>,
 }
 
 fn default_policy_action() -> PolicyAction {
@@ -37,6 +320,8 @@ pub enum PolicyAction {
     Pass,
     /// Dependency warrants review but does not fail the scan.
     Warn,
+    /// Dependency needs explicit sign-off; fails the scan only with `--fail-on review`.
+    Review,
     /// Dependency violates policy; the CLI exits with code 1.
     Error,
 }
@@ -47,6 +332,7 @@ impl PolicyAction {
         match self {
             PolicyAction::Pass => PolicyVerdict::Pass,
             PolicyAction::Warn => PolicyVerdict::Warn,
+            PolicyAction::Review => PolicyVerdict::Review,
             PolicyAction::Error => PolicyVerdict::Error,
         }
     }
@@ -74,7 +360,18 @@ impl Default for Config {
             policy: PolicyConfig {
                 default: PolicyAction::Warn,
                 licenses,
+                packages: HashMap::new(),
+                risk: RiskPolicy::default(),
+                mode: PolicyMode::License,
+                allowed_packages: Vec::new(),
+                combine: CombineStrategy::Standard,
+                or_requires_allowed: false,
+                license_ref_risk: default_license_ref_risk(),
             },
+            limits: LimitsConfig::default(),
+            profiles: HashMap::new(),
+            labels: HashMap::new(),
+            normalize: HashMap::new(),
         }
     }
 }
@@ -82,33 +379,222 @@ impl Default for Config {
 /// Load the policy configuration, searching in order:
 ///
 /// 1. `config_override` — path passed via `--config`
-/// 2. `<project_path>/.license-checkr/config.toml`
+/// 2. `.license-checkr/config.toml` in `project_path` or the nearest ancestor
+///    (see [`find_project_config`])
 /// 3. `~/.config/license-checkr/config.toml`
 /// 4. Built-in [`Config::default`]
 pub fn load_config(project_path: &Path, config_override: Option<&Path>) -> Result<Config> {
+    match config_path(project_path, config_override) {
+        Some(path) => read_config(&path),
+        None => Ok(Config::default()),
+    }
+}
+
+/// The config file [`load_config`] reads for `project_path`, or `None` when the
+/// built-in default policy applies.
+pub fn config_path(project_path: &Path, config_override: Option<&Path>) -> Option<PathBuf> {
     if let Some(path) = config_override {
-        let content = std::fs::read_to_string(path)?;
-        return Ok(toml::from_str(&content)?);
+        return Some(path.to_path_buf());
+    }
+
+    if let Some(project_config) = find_project_config(project_path) {
+        return Some(project_config);
+    }
+
+    let home_config = dirs::home_dir()?
+        .join(".config")
+        .join("license-checkr")
+        .join("config.toml");
+    home_config.exists().then_some(home_config)
+}
+
+/// Walk upward from `start` looking for `.license-checkr/config.toml`.
+///
+/// The walk stops at the first directory containing a `.git` entry (the
+/// repository root is still checked) or at the filesystem root, so a
+/// sub-project in a monorepo inherits the nearest ancestor policy without
+/// leaking into unrelated parent directories.
+pub fn find_project_config(start: &Path) -> Option<PathBuf> {
+    for dir in start.ancestors() {
+        let candidate = dir.join(".license-checkr").join("config.toml");
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+    None
+}
+
+fn read_config(path: &Path) -> Result<Config> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config {}", path.display()))?;
+    let warnings =
+        validate_config(&content).with_context(|| format!("Invalid config {}", path.display()))?;
+    for warning in warnings {
+        Diagnostic::warning(format!("{}: {}", path.display(), warning))
+            .file(path)
+            .emit();
     }
+    toml::from_str(&content).with_context(|| format!("Invalid config {}", path.display()))
+}
+
+/// Top-level tables of [`Config`].
+const CONFIG_KEYS: &[&str] = &["policy", "limits", "profiles", "labels", "normalize"];
+
+/// Policy action fields, read with their positions by [`validate_config`].
+#[derive(Default, Deserialize)]
+struct RawConfig {
+    #[serde(default)]
+    policy: RawPolicy,
+    #[serde(default)]
+    profiles: BTreeMap<String, RawProfile>,
+    #[serde(default)]
+    labels: BTreeMap<String, Spanned<toml::Value>>,
+}
 
-    let project_config = project_path.join(".license-checkr").join("config.toml");
-    if project_config.exists() {
-        let content = std::fs::read_to_string(&project_config)?;
-        return Ok(toml::from_str(&content)?);
+#[derive(Default, Deserialize)]
+struct RawProfile {
+    #[serde(default)]
+    policy: RawPolicy,
+}
+
+#[derive(Default, Deserialize)]
+struct RawPolicy {
+    default: Option<Spanned<String>>,
+    mode: Option<Spanned<String>>,
+    #[serde(default)]
+    licenses: BTreeMap<String, Spanned<String>>,
+    #[serde(default)]
+    packages: BTreeMap<String, Spanned<String>>,
+    #[serde(default)]
+    risk: BTreeMap<String, Spanned<String>>,
+    #[serde(default)]
+    allowed_packages: Vec<Spanned<String>>,
+}
+
+/// Check the config text before it is deserialized: a misspelled policy action
+/// or mode is an error naming its line and the accepted values (where `toml`
+/// would only report an unknown variant), and unknown top-level keys are
+/// returned as warnings so configs written for newer versions still load.
+///
+/// Text that is not valid TOML, or whose tables have the wrong shape, passes
+/// through; deserializing it reports the error.
+pub fn validate_config(content: &str) -> Result<Vec<String>> {
+    let Ok(table) = content.parse::<toml::Table>() else {
+        return Ok(Vec::new());
+    };
+    let Ok(raw) = toml::from_str::<RawConfig>(content) else {
+        return Ok(Vec::new());
+    };
+
+    let mut policies = vec![(String::new(), &raw.policy)];
+    for (name, profile) in &raw.profiles {
+        policies.push((format!("profile '{}': ", name), &profile.policy));
+    }
+    for (prefix, policy) in policies {
+        if let Some(mode) = &policy.mode {
+            let line = line_of(content, mode.span().start);
+            match mode.get_ref().as_str() {
+                "license" | "package-allowlist" | "license-allowlist" => {}
+                "allowlist" => anyhow::bail!(
+                    "line {}: {}ambiguous policy mode 'allowlist'; use 'package-allowlist' \
+                     to permit only `allowed_packages` or 'license-allowlist' to permit only \
+                     `pass` licenses",
+                    line,
+                    prefix
+                ),
+                other => anyhow::bail!(
+                    "line {}: {}unknown policy mode '{}'; expected \
+                     license|package-allowlist|license-allowlist",
+                    line,
+                    prefix,
+                    other
+                ),
+            }
+        }
+        let actions = policy
+            .default
+            .iter()
+            .map(|action| ("`default`".to_string(), action))
+            .chain(
+                policy
+                    .licenses
+                    .iter()
+                    .map(|(id, action)| (format!("license '{}'", id), action)),
+            )
+            .chain(
+                policy
+                    .packages
+                    .iter()
+                    .map(|(name, action)| (format!("package '{}'", name), action)),
+            )
+            .chain(
+                policy
+                    .risk
+                    .iter()
+                    .map(|(level, action)| (format!("risk level '{}'", level), action)),
+            );
+        for (key, action) in &policy.packages {
+            if let Err(e) = PackagePattern::new(key) {
+                anyhow::bail!(
+                    "line {}: {}invalid package pattern '{}': {}",
+                    line_of(content, action.span().start),
+                    prefix,
+                    key,
+                    e.kind()
+                );
+            }
+        }
+        for entry in &policy.allowed_packages {
+            if let Err(e) = PackagePattern::new(entry.get_ref()) {
+                anyhow::bail!(
+                    "line {}: {}invalid allowed package pattern '{}': {}",
+                    line_of(content, entry.span().start),
+                    prefix,
+                    entry.get_ref(),
+                    e.kind()
+                );
+            }
+        }
+        for (subject, action) in actions {
+            if !matches!(
+                action.get_ref().as_str(),
+                "pass" | "warn" | "review" | "error"
+            ) {
+                anyhow::bail!(
+                    "line {}: {}unknown policy action '{}' for {}; expected pass|warn|review|error",
+                    line_of(content, action.span().start),
+                    prefix,
+                    action.get_ref(),
+                    subject
+                );
+            }
+        }
     }
 
-    if let Some(home) = dirs::home_dir() {
-        let home_config = home
-            .join(".config")
-            .join("license-checkr")
-            .join("config.toml");
-        if home_config.exists() {
-            let content = std::fs::read_to_string(&home_config)?;
-            return Ok(toml::from_str(&content)?);
+    for (pattern, labels) in &raw.labels {
+        if let Err(e) = PackagePattern::new(pattern) {
+            anyhow::bail!(
+                "line {}: invalid label pattern '{}': {}",
+                line_of(content, labels.span().start),
+                pattern,
+                e.kind()
+            );
         }
     }
 
-    Ok(Config::default())
+    Ok(table
+        .keys()
+        .filter(|key| !CONFIG_KEYS.contains(&key.as_str()))
+        .map(|key| format!("unknown top-level key '{}' is ignored", key))
+        .collect())
+}
+
+/// 1-based line number of byte `offset` in `content`.
+fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count() + 1
 }
 
 /// Determine the policy verdict for a given SPDX license identifier or expression.
@@ -123,8 +609,8 @@ pub fn apply_policy(config: &Config, license_spdx: Option<&str>) -> PolicyVerdic
     let license = license_spdx.unwrap_or("unknown");
 
     // Exact match first (covers simple identifiers and the literal "unknown")
-    if let Some(action) = config.policy.licenses.get(license) {
-        return action.to_verdict();
+    if config.policy.licenses.contains_key(license) {
+        return apply_policy_single(config, license).verdict;
     }
 
     // Normalize "/" separator (some ecosystems use it as an OR shorthand)
@@ -133,57 +619,144 @@ pub fn apply_policy(config: &Config, license_spdx: Option<&str>) -> PolicyVerdic
     eval_spdx_expr(config, &normalized)
 }
 
-// ---------------------------------------------------------------------------
-// SPDX expression parser
-// ---------------------------------------------------------------------------
+/// Evaluate a dependency's package identity: its `[policy.packages]` exception
+/// (see [`package_action`]), else the allowlist.
+///
+/// Returns the exception's verdict when there is one, `Some(PolicyVerdict::Error)`
+/// when `[policy] mode = "package-allowlist"` and the package matches no
+/// `allowed_packages` entry, `None` otherwise — in which case the license verdict
+/// from [`apply_policy`] applies.
+pub fn apply_package_policy(config: &Config, name: &str, version: &str) -> Option<PolicyVerdict> {
+    if let Some(action) = package_action(config, name, version) {
+        return Some(action.to_verdict());
+    }
+    if config.policy.mode != PolicyMode::PackageAllowlist {
+        return None;
+    }
+    let allowed = config
+        .policy
+        .allowed_packages
+        .iter()
+        .any(|entry| entry.matches(name, version));
+    (!allowed).then_some(PolicyVerdict::Error)
+}
 
-/// Tokens produced by [`tokenize_spdx`].
-#[derive(Debug, PartialEq, Clone)]
-enum Token {
-    Id(String),
-    And,
-    Or,
-    With,
-    LParen,
-    RParen,
-}
-
-/// Tokenize an SPDX license expression into a flat [`Vec<Token>`].
-fn tokenize_spdx(expr: &str) -> Vec<Token> {
-    let mut tokens = Vec::new();
-    let mut chars = expr.chars().peekable();
-    while let Some(&c) = chars.peek() {
-        if c.is_whitespace() {
-            chars.next();
-            continue;
-        }
-        if c == '(' {
-            tokens.push(Token::LParen);
-            chars.next();
-        } else if c == ')' {
-            tokens.push(Token::RParen);
-            chars.next();
-        } else {
-            let mut s = String::new();
-            while let Some(&c) = chars.peek() {
-                if c.is_whitespace() || c == '(' || c == ')' {
-                    break;
-                }
-                s.push(c);
-                chars.next();
-            }
-            let token = match s.as_str() {
-                "AND" => Token::And,
-                "OR" => Token::Or,
-                "WITH" => Token::With,
-                _ => Token::Id(s),
-            };
-            tokens.push(token);
-        }
+/// The `[policy.packages]` action for a package: an exact `name@version` entry,
+/// else a bare `name`, else the longest matching glob pattern (ties go to the
+/// alphabetically first, so the choice never depends on table order).
+fn package_action<'a>(config: &'a Config, name: &str, version: &str) -> Option<&'a PolicyAction> {
+    let packages = &config.policy.packages;
+    if let Some(action) = packages
+        .get(format!("{}@{}", name, version).as_str())
+        .or_else(|| packages.get(name))
+    {
+        return Some(action);
     }
-    tokens
+    packages
+        .iter()
+        .filter(|(pattern, _)| pattern.is_glob() && pattern.matches(name, version))
+        .max_by(|(a, _), (b, _)| {
+            let (a, b) = (a.as_str(), b.as_str());
+            a.len().cmp(&b.len()).then_with(|| b.cmp(a))
+        })
+        .map(|(_, action)| action)
 }
 
+/// A `[policy.packages]` or `[labels]` key or an `allowed_packages` entry, `name`
+/// or `name@version`, with a glob matcher for each part compiled once when the
+/// config is read. Keys compare and hash as their text, so exact entries are
+/// still found with a `&str` lookup.
+#[derive(Debug, Clone)]
+pub struct PackagePattern {
+    pattern: String,
+    name: GlobMatcher,
+    version: Option<GlobMatcher>,
+}
+
+impl PackagePattern {
+    /// Compile `pattern`, split on the last `@` after the first character (so
+    /// scoped npm names such as `@types/node@20.*` keep their leading `@`). Both
+    /// parts accept `*`, `?`, `[…]` and `{a,b}`, where `*` also matches `/`.
+    pub fn new(pattern: &str) -> Result<Self, globset::Error> {
+        let (name, version) = match pattern.rfind('@').filter(|&i| i > 0) {
+            Some(i) => (&pattern[..i], Some(&pattern[i + 1..])),
+            None => (pattern, None),
+        };
+        let version = match version {
+            Some(version) => Some(Glob::new(version)?.compile_matcher()),
+            None => None,
+        };
+        Ok(PackagePattern {
+            pattern: pattern.to_string(),
+            name: Glob::new(name)?.compile_matcher(),
+            version,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Whether the key uses glob syntax rather than naming a package.
+    pub fn is_glob(&self) -> bool {
+        self.pattern.contains(['*', '?', '[', '{'])
+    }
+
+    pub fn matches(&self, name: &str, version: &str) -> bool {
+        self.name.is_match(name) && self.version.as_ref().is_none_or(|v| v.is_match(version))
+    }
+}
+
+impl PartialEq for PackagePattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern
+    }
+}
+
+impl Eq for PackagePattern {}
+
+impl std::hash::Hash for PackagePattern {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.pattern.hash(state);
+    }
+}
+
+impl std::borrow::Borrow<str> for PackagePattern {
+    fn borrow(&self) -> &str {
+        &self.pattern
+    }
+}
+
+/// Invalid patterns are rejected with their line by [`validate_config`] before
+/// the config is deserialized.
+impl<'de> Deserialize<'de> for PackagePattern {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        PackagePattern::new(&pattern).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Check the total dependency count against a `min_dependencies` threshold.
+///
+/// Returns a human-readable message when `total` is below `min`, `None` otherwise
+/// (including when no threshold is configured).
+pub fn check_min_dependencies(min: Option<usize>, total: usize) -> Option<String> {
+    let min = min?;
+    if total >= min {
+        return None;
+    }
+    Some(format!(
+        "only {} dependenc{} found (expected at least {}) — a manifest may have failed to parse",
+        total,
+        if total == 1 { "y" } else { "ies" },
+        min
+    ))
+}
+
+// ---------------------------------------------------------------------------
+// SPDX expression parser
+// ---------------------------------------------------------------------------
+
 /// Recursive descent parser that evaluates an SPDX expression against `config`.
 ///
 /// Grammar (AND binds tighter than OR):
@@ -213,29 +786,39 @@ impl<'a> ExprParser<'a> {
     }
 
     /// Parse an OR-level expression (lowest precedence).
-    fn parse_or(&mut self) -> PolicyVerdict {
+    fn parse_or(&mut self) -> Eval {
         let mut result = self.parse_and();
+        let mut is_or = false;
         while matches!(self.peek(), Some(Token::Or)) {
             self.consume();
             let rhs = self.parse_and();
-            result = verdict_or(result, rhs);
+            result = self.config.policy.combine.or(result, rhs);
+            is_or = true;
+        }
+        // `or_requires_allowed`: a pass through the default policy alone is not enough
+        if is_or
+            && self.config.policy.or_requires_allowed
+            && result.verdict == PolicyVerdict::Pass
+            && !result.allowed
+        {
+            result.verdict = PolicyVerdict::Warn;
         }
         result
     }
 
     /// Parse an AND-level expression (higher precedence than OR).
-    fn parse_and(&mut self) -> PolicyVerdict {
+    fn parse_and(&mut self) -> Eval {
         let mut result = self.parse_atom();
         while matches!(self.peek(), Some(Token::And)) {
             self.consume();
             let rhs = self.parse_atom();
-            result = verdict_and(result, rhs);
+            result = self.config.policy.combine.and(result, rhs);
         }
         result
     }
 
     /// Parse an atom: a parenthesised sub-expression or a single license id.
-    fn parse_atom(&mut self) -> PolicyVerdict {
+    fn parse_atom(&mut self) -> Eval {
         match self.peek() {
             Some(Token::LParen) => {
                 self.consume(); // consume '('
@@ -258,40 +841,88 @@ impl<'a> ExprParser<'a> {
                 }
                 apply_policy_single(self.config, &id)
             }
-            _ => self.config.policy.default.to_verdict(),
+            _ => Eval {
+                verdict: unlisted_verdict(self.config, None),
+                allowed: false,
+            },
         }
     }
 }
 
+/// Verdict of a (sub-)expression, and whether it is backed by explicit `pass`
+/// rules rather than `[policy] default` (see `or_requires_allowed`).
+#[derive(Debug)]
+struct Eval {
+    verdict: PolicyVerdict,
+    allowed: bool,
+}
+
 /// Evaluate a full SPDX expression string against the policy.
 fn eval_spdx_expr(config: &Config, expr: &str) -> PolicyVerdict {
     let tokens = tokenize_spdx(expr);
-    ExprParser { tokens, pos: 0, config }.parse_or()
+    ExprParser { tokens, pos: 0, config }.parse_or().verdict
+}
+
+/// Look up a single (non-compound) SPDX identifier in the policy map, else see
+/// [`unlisted_verdict`]. The most specific of the id's [`id_variants`] with an
+/// entry applies, so a `GPL-3.0` entry also covers `GPL-3.0-only`,
+/// `GPL-3.0-or-later` and `GPL-3.0+` unless those have entries of their own.
+/// Ids and keys match case-insensitively.
+fn apply_policy_single(config: &Config, id: &str) -> Eval {
+    let licenses = &config.policy.licenses;
+    let action = id_variants(id).iter().find_map(|variant| {
+        licenses.get(variant).or_else(|| {
+            licenses
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(variant))
+                .map(|(_, action)| action)
+        })
+    });
+    if let Some(action) = action {
+        let allowed = matches!(action, PolicyAction::Pass);
+        let verdict = if config.policy.mode == PolicyMode::LicenseAllowlist && !allowed {
+            PolicyVerdict::Error
+        } else {
+            action.to_verdict()
+        };
+        return Eval { verdict, allowed };
+    }
+    Eval {
+        verdict: unlisted_verdict(config, Some(id)),
+        allowed: false,
+    }
 }
 
-/// Look up a single (non-compound) SPDX identifier in the policy map.
-fn apply_policy_single(config: &Config, id: &str) -> PolicyVerdict {
-    if let Some(action) = config.policy.licenses.get(id) {
-        return action.to_verdict();
+/// Verdict of a license without a `licenses` entry: an error under
+/// `mode = "license-allowlist"`, else its `[policy.risk]` rule (when `id` is
+/// known), else `default`.
+fn unlisted_verdict(config: &Config, id: Option<&str>) -> PolicyVerdict {
+    if config.policy.mode == PolicyMode::LicenseAllowlist {
+        return PolicyVerdict::Error;
+    }
+    match id.and_then(|id| config.policy.risk.action(&config.classify(id))) {
+        Some(action) => action.to_verdict(),
+        None => config.policy.default.to_verdict(),
     }
-    config.policy.default.to_verdict()
 }
 
 /// Most permissive (least severe) of two verdicts — used for OR semantics.
-/// Pass < Warn < Error
+/// Pass < Warn < Review < Error
 fn verdict_or(a: PolicyVerdict, b: PolicyVerdict) -> PolicyVerdict {
     match (a, b) {
         (PolicyVerdict::Pass, _) | (_, PolicyVerdict::Pass) => PolicyVerdict::Pass,
         (PolicyVerdict::Warn, _) | (_, PolicyVerdict::Warn) => PolicyVerdict::Warn,
+        (PolicyVerdict::Review, _) | (_, PolicyVerdict::Review) => PolicyVerdict::Review,
         _ => PolicyVerdict::Error,
     }
 }
 
 /// Most restrictive (most severe) of two verdicts — used for AND semantics.
-/// Error > Warn > Pass
+/// Error > Review > Warn > Pass
 fn verdict_and(a: PolicyVerdict, b: PolicyVerdict) -> PolicyVerdict {
     match (a, b) {
         (PolicyVerdict::Error, _) | (_, PolicyVerdict::Error) => PolicyVerdict::Error,
+        (PolicyVerdict::Review, _) | (_, PolicyVerdict::Review) => PolicyVerdict::Review,
         (PolicyVerdict::Warn, _) | (_, PolicyVerdict::Warn) => PolicyVerdict::Warn,
         _ => PolicyVerdict::Pass,
     }
@@ -341,6 +972,86 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_review_ordering_in_verdict_or_and() {
+        use PolicyVerdict::*;
+        assert_eq!(verdict_or(Warn, Review), Warn);
+        assert_eq!(verdict_or(Review, Error), Review);
+        assert_eq!(verdict_or(Review, Pass), Pass);
+        assert_eq!(verdict_and(Warn, Review), Review);
+        assert_eq!(verdict_and(Review, Error), Error);
+        assert_eq!(verdict_and(Pass, Review), Review);
+    }
+
+    #[test]
+    fn test_combine_strategies_over_same_expression() {
+        let expr = Some("MIT OR GPL-3.0");
+        let nested = Some("(MIT OR LGPL-2.1) AND Apache-2.0");
+
+        let mut cfg = default_config();
+        assert_eq!(cfg.policy.combine, CombineStrategy::Standard);
+        assert_eq!(apply_policy(&cfg, expr), PolicyVerdict::Pass);
+        assert_eq!(apply_policy(&cfg, nested), PolicyVerdict::Pass);
+
+        cfg.policy.combine = CombineStrategy::Conservative;
+        assert_eq!(apply_policy(&cfg, expr), PolicyVerdict::Error);
+        assert_eq!(apply_policy(&cfg, nested), PolicyVerdict::Warn);
+        // AND is unchanged
+        assert_eq!(
+            apply_policy(&cfg, Some("MIT AND Apache-2.0")),
+            PolicyVerdict::Pass
+        );
+
+        let parsed: Config = toml::from_str("[policy]\ncombine = \"conservative\"\n").unwrap();
+        assert_eq!(parsed.policy.combine, CombineStrategy::Conservative);
+    }
+
+    #[test]
+    fn test_or_requires_allowed_operand() {
+        let mut cfg = default_config();
+        cfg.policy.default = PolicyAction::Pass;
+        let defaulted = Some("Zlib OR SomethingUnknown");
+        let explicit = Some("MIT OR SomethingUnknown");
+
+        // Without the setting the default-derived pass is enough
+        assert_eq!(apply_policy(&cfg, defaulted), PolicyVerdict::Pass);
+        assert_eq!(apply_policy(&cfg, explicit), PolicyVerdict::Pass);
+
+        cfg.policy.or_requires_allowed = true;
+        assert_eq!(apply_policy(&cfg, defaulted), PolicyVerdict::Warn);
+        assert_eq!(apply_policy(&cfg, explicit), PolicyVerdict::Pass);
+        assert_eq!(
+            apply_policy(&cfg, Some("(Zlib OR MIT) AND Apache-2.0")),
+            PolicyVerdict::Pass
+        );
+        // Single identifiers and AND are unaffected
+        assert_eq!(apply_policy(&cfg, Some("Zlib")), PolicyVerdict::Pass);
+        assert_eq!(
+            apply_policy(&cfg, Some("Zlib AND MIT")),
+            PolicyVerdict::Pass
+        );
+
+        let parsed: Config = toml::from_str("[policy]\nor_requires_allowed = true\n").unwrap();
+        assert!(parsed.policy.or_requires_allowed);
+    }
+
+    #[test]
+    fn test_review_action_in_compound_expression() {
+        let mut cfg = default_config();
+        cfg.policy
+            .licenses
+            .insert("MPL-2.0".to_string(), PolicyAction::Review);
+        assert_eq!(apply_policy(&cfg, Some("MPL-2.0")), PolicyVerdict::Review);
+        assert_eq!(
+            apply_policy(&cfg, Some("MPL-2.0 OR LGPL-2.1")),
+            PolicyVerdict::Warn
+        );
+        assert_eq!(
+            apply_policy(&cfg, Some("MPL-2.0 AND LGPL-2.1")),
+            PolicyVerdict::Review
+        );
+    }
+
     #[test]
     fn test_slash_separator() {
         let cfg = default_config();
@@ -412,4 +1123,637 @@ mod tests {
             PolicyVerdict::Error
         );
     }
+
+    #[test]
+    fn test_min_dependencies_below_threshold_warns() {
+        let msg = check_min_dependencies(Some(10), 3).unwrap();
+        assert!(msg.contains("only 3 dependencies"));
+        assert!(msg.contains("at least 10"));
+    }
+
+    #[test]
+    fn test_min_dependencies_at_or_above_threshold_silent() {
+        assert_eq!(check_min_dependencies(Some(10), 10), None);
+        assert_eq!(check_min_dependencies(Some(10), 42), None);
+        assert_eq!(check_min_dependencies(None, 0), None);
+    }
+
+    #[test]
+    fn test_config_found_in_ancestor() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(tmp.path().join(".git")).unwrap();
+        let cfg_dir = tmp.path().join(".license-checkr");
+        std::fs::create_dir(&cfg_dir).unwrap();
+        std::fs::write(
+            cfg_dir.join("config.toml"),
+            "[policy]\ndefault = \"error\"\n",
+        )
+        .unwrap();
+        let project = tmp.path().join("services").join("api");
+        std::fs::create_dir_all(&project).unwrap();
+
+        assert_eq!(
+            find_project_config(&project),
+            Some(cfg_dir.join("config.toml"))
+        );
+        let cfg = load_config(&project, None).unwrap();
+        assert_eq!(apply_policy(&cfg, Some("MIT")), PolicyVerdict::Error);
+    }
+
+    #[test]
+    fn test_config_walk_stops_at_git_boundary() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let cfg_dir = tmp.path().join(".license-checkr");
+        std::fs::create_dir(&cfg_dir).unwrap();
+        std::fs::write(cfg_dir.join("config.toml"), "[policy]\n").unwrap();
+        let repo = tmp.path().join("repo");
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        let project = repo.join("app");
+        std::fs::create_dir_all(&project).unwrap();
+
+        assert_eq!(find_project_config(&project), None);
+    }
+
+    #[test]
+    fn test_limits_section_parsed() {
+        let cfg: Config = toml::from_str(
+            r#"
+[policy]
+default = "warn"
+
+[limits]
+min_dependencies = 25
+"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.limits.min_dependencies, Some(25));
+    }
+
+    fn profiles_config() -> Config {
+        toml::from_str(
+            r#"
+[policy]
+default = "warn"
+
+[policy.licenses]
+"MIT" = "pass"
+"LGPL-2.1" = "warn"
+
+[profiles.release.policy]
+default = "error"
+
+[profiles.release.policy.licenses]
+"LGPL-2.1" = "error"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_profile_overrides_license_and_default() {
+        let mut cfg = profiles_config();
+        assert!(cfg.apply_profile("release"));
+        assert_eq!(apply_policy(&cfg, Some("LGPL-2.1")), PolicyVerdict::Error);
+        assert_eq!(apply_policy(&cfg, Some("EUPL-1.2")), PolicyVerdict::Error);
+        // Base entries the profile does not mention still apply
+        assert_eq!(apply_policy(&cfg, Some("MIT")), PolicyVerdict::Pass);
+    }
+
+    #[test]
+    fn test_base_policy_without_or_with_unknown_profile() {
+        let cfg = profiles_config();
+        assert_eq!(apply_policy(&cfg, Some("LGPL-2.1")), PolicyVerdict::Warn);
+
+        let mut cfg = profiles_config();
+        assert!(!cfg.apply_profile("staging"));
+        assert_eq!(apply_policy(&cfg, Some("LGPL-2.1")), PolicyVerdict::Warn);
+        assert_eq!(apply_policy(&cfg, Some("EUPL-1.2")), PolicyVerdict::Warn);
+    }
+
+    fn allowlist_config() -> Config {
+        toml::from_str(
+            r#"
+[policy]
+mode = "package-allowlist"
+allowed_packages = ["serde", "tokio@1.*", "@types/node@20.*"]
+
+[policy.licenses]
+"MIT" = "pass"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_allowlist_permits_listed_package() {
+        let cfg = allowlist_config();
+        assert_eq!(apply_package_policy(&cfg, "serde", "1.0.200"), None);
+        assert_eq!(apply_package_policy(&cfg, "tokio", "1.38.0"), None);
+        assert_eq!(apply_package_policy(&cfg, "@types/node", "20.11.5"), None);
+    }
+
+    #[test]
+    fn test_allowlist_rejects_unlisted_package_regardless_of_license() {
+        let cfg = allowlist_config();
+        assert_eq!(apply_policy(&cfg, Some("MIT")), PolicyVerdict::Pass);
+        assert_eq!(
+            apply_package_policy(&cfg, "left-pad", "1.3.0"),
+            Some(PolicyVerdict::Error)
+        );
+        // Version pattern must match too
+        assert_eq!(
+            apply_package_policy(&cfg, "tokio", "0.2.25"),
+            Some(PolicyVerdict::Error)
+        );
+    }
+
+    #[test]
+    fn test_allowlist_entries_are_package_globs() {
+        let cfg: Config = toml::from_str(
+            r#"
+[policy]
+mode = "package-allowlist"
+allowed_packages = ["@acme/*", "{aws,azure}-sdk-*@2.*"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(apply_package_policy(&cfg, "@acme/ui", "0.1.0"), None);
+        assert_eq!(apply_package_policy(&cfg, "aws-sdk-s3", "2.4.0"), None);
+        assert_eq!(
+            apply_package_policy(&cfg, "gcp-sdk-storage", "2.4.0"),
+            Some(PolicyVerdict::Error)
+        );
+
+        let content = "[policy]\nmode = \"package-allowlist\"\nallowed_packages = [\n  \"serde\",\n  \"tokio@[1\",\n]\n";
+        let err = validate_config(content).unwrap_err().to_string();
+        assert!(err.starts_with("line 5: invalid allowed package pattern 'tokio@[1'"));
+        assert!(toml::from_str::<Config>(content).is_err());
+    }
+
+    #[test]
+    fn test_license_mode_ignores_allowed_packages() {
+        let cfg = default_config();
+        assert_eq!(cfg.policy.mode, PolicyMode::License);
+        assert_eq!(apply_package_policy(&cfg, "anything", "1.0.0"), None);
+    }
+
+    fn risk_config() -> Config {
+        toml::from_str(
+            r#"
+[policy]
+default = "review"
+
+[policy.licenses]
+"LGPL-2.1" = "pass"
+
+[policy.risk]
+strong-copyleft = "error"
+weak-copyleft = "warn"
+permissive = "pass"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_risk_rule_errors_strong_copyleft() {
+        let cfg = risk_config();
+        assert!(!cfg.policy.licenses.contains_key("GPL-3.0"));
+        assert_eq!(apply_policy(&cfg, Some("GPL-3.0")), PolicyVerdict::Error);
+        assert_eq!(apply_policy(&cfg, Some("AGPL-3.0")), PolicyVerdict::Error);
+        assert_eq!(apply_policy(&cfg, Some("MPL-2.0")), PolicyVerdict::Warn);
+        assert_eq!(apply_policy(&cfg, Some("ISC")), PolicyVerdict::Pass);
+        // Operands of compound expressions are classified one by one
+        assert_eq!(
+            apply_policy(&cfg, Some("MIT OR GPL-3.0")),
+            PolicyVerdict::Pass
+        );
+        assert_eq!(
+            apply_policy(&cfg, Some("MIT AND GPL-3.0")),
+            PolicyVerdict::Error
+        );
+    }
+
+    #[test]
+    fn test_version_suffixes_match_base_policy_entry() {
+        let mut cfg = Config::default();
+        for id in ["GPL-3.0", "GPL-3.0-only", "GPL-3.0-or-later", "GPL-3.0+"] {
+            assert_eq!(apply_policy(&cfg, Some(id)), PolicyVerdict::Error, "{}", id);
+        }
+        assert_eq!(
+            apply_policy(&cfg, Some("MIT OR LGPL-2.1+")),
+            PolicyVerdict::Pass
+        );
+
+        // A more specific entry wins over the base one
+        cfg.policy
+            .licenses
+            .insert("GPL-3.0-or-later".to_string(), PolicyAction::Review);
+        assert_eq!(apply_policy(&cfg, Some("GPL-3.0+")), PolicyVerdict::Review);
+        assert_eq!(
+            apply_policy(&cfg, Some("GPL-3.0-only")),
+            PolicyVerdict::Error
+        );
+
+        // The deprecated bare id means `-only`
+        let mut cfg = Config::default();
+        cfg.policy.licenses.remove("GPL-2.0");
+        cfg.policy
+            .licenses
+            .insert("GPL-2.0-only".to_string(), PolicyAction::Review);
+        assert_eq!(apply_policy(&cfg, Some("GPL-2.0")), PolicyVerdict::Review);
+    }
+
+    #[test]
+    fn test_policy_lookup_ignores_id_case() {
+        let cfg = Config::default();
+        assert_eq!(apply_policy(&cfg, Some("mit")), PolicyVerdict::Pass);
+        assert_eq!(apply_policy(&cfg, Some("apache-2.0")), PolicyVerdict::Pass);
+        assert_eq!(
+            apply_policy(&cfg, Some("bsd-3-clause")),
+            PolicyVerdict::Pass
+        );
+        assert_eq!(apply_policy(&cfg, Some("gpl-3.0")), PolicyVerdict::Error);
+
+        let cfg: Config =
+            toml::from_str("[policy]\n[policy.licenses]\n\"licenseref-acme\" = \"pass\"\n")
+                .unwrap();
+        assert_eq!(
+            apply_policy(&cfg, Some("LicenseRef-Acme")),
+            PolicyVerdict::Pass
+        );
+    }
+
+    #[test]
+    fn test_license_ref_ids_in_policy() {
+        let mut cfg = Config::default();
+        assert_eq!(
+            cfg.classify("LicenseRef-Acme-Internal"),
+            LicenseRisk::Proprietary
+        );
+        cfg.policy.risk.proprietary = Some(PolicyAction::Error);
+        assert_eq!(
+            apply_policy(&cfg, Some("LicenseRef-Acme-Internal")),
+            PolicyVerdict::Error
+        );
+        cfg.policy
+            .licenses
+            .insert("LicenseRef-Acme-Internal".to_string(), PolicyAction::Pass);
+        assert_eq!(
+            apply_policy(&cfg, Some("LicenseRef-Acme-Internal")),
+            PolicyVerdict::Pass
+        );
+        assert_eq!(
+            apply_policy(&cfg, Some("MIT AND LicenseRef-Acme-Internal")),
+            PolicyVerdict::Pass
+        );
+
+        let cfg: Config =
+            toml::from_str("[policy]\nlicense_ref_risk = \"weak-copyleft\"\n").unwrap();
+        assert_eq!(cfg.classify("LicenseRef-Vendor"), LicenseRisk::WeakCopyleft);
+    }
+
+    #[test]
+    fn test_risk_rule_precedence() {
+        let cfg = risk_config();
+        // An explicit license rule beats the risk rule...
+        assert_eq!(apply_policy(&cfg, Some("LGPL-2.1")), PolicyVerdict::Pass);
+        // ...and levels without a risk rule use the default
+        assert_eq!(
+            apply_policy(&cfg, Some("Acme-Custom")),
+            PolicyVerdict::Review
+        );
+        assert_eq!(apply_policy(&cfg, None), PolicyVerdict::Review);
+    }
+
+    #[test]
+    fn test_risk_rule_rejects_unknown_level() {
+        let cfg: Result<Config, _> =
+            toml::from_str("[policy]\n[policy.risk]\nstrong_copyleft = \"error\"\n");
+        assert!(cfg.is_err());
+    }
+
+    #[test]
+    fn test_validate_config_names_misspelled_action() {
+        let content = "[policy]\ndefault = \"warn\"\n\n[policy.licenses]\n\"MIT\" = \"pas\"\n";
+        let err = validate_config(content).unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "line 5: unknown policy action 'pas' for license 'MIT'; expected pass|warn|review|error"
+        );
+
+        let content = "[policy]\n[profiles.ci.policy]\ndefault = \"fail\"\n";
+        let err = validate_config(content).unwrap_err().to_string();
+        assert!(err.starts_with("line 3: profile 'ci': unknown policy action 'fail' for `default`"));
+    }
+
+    #[test]
+    fn test_validate_config_rejects_ambiguous_allowlist_mode() {
+        let content = "[policy]\nmode = \"allowlist\"\n";
+        let err = validate_config(content).unwrap_err().to_string();
+        assert!(err.starts_with("line 2: ambiguous policy mode 'allowlist'"));
+        assert!(err.contains("'package-allowlist'") && err.contains("'license-allowlist'"));
+
+        let content = "[policy]\n[profiles.ci.policy]\nmode = \"licence\"\n";
+        let err = validate_config(content).unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "line 3: profile 'ci': unknown policy mode 'licence'; expected \
+             license|package-allowlist|license-allowlist"
+        );
+
+        for mode in ["license", "package-allowlist", "license-allowlist"] {
+            let content = format!("[policy]\nmode = \"{}\"\n", mode);
+            assert!(validate_config(&content).is_ok());
+            assert!(toml::from_str::<Config>(&content).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_validate_config_warns_on_unknown_top_level_key() {
+        let content = "[policy]\n\n[reporting]\nformat = \"json\"\n";
+        assert_eq!(
+            validate_config(content).unwrap(),
+            ["unknown top-level key 'reporting' is ignored"]
+        );
+        // The key does not stop the config from loading
+        assert!(toml::from_str::<Config>(content).is_ok());
+        assert!(validate_config("[policy]\ndefault = \"pass\"\n")
+            .unwrap()
+            .is_empty());
+    }
+
+    fn license_allowlist_config() -> Config {
+        toml::from_str(
+            r#"
+[policy]
+mode = "license-allowlist"
+default = "pass"
+
+[policy.licenses]
+"MIT" = "pass"
+"Apache-2.0" = "pass"
+"LGPL-2.1" = "warn"
+
+[policy.risk]
+permissive = "pass"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_license_allowlist_errors_on_everything_else() {
+        let cfg = license_allowlist_config();
+        assert_eq!(apply_policy(&cfg, Some("MIT")), PolicyVerdict::Pass);
+        // `default`, `[policy.risk]` and non-pass entries no longer apply
+        assert_eq!(apply_policy(&cfg, Some("ISC")), PolicyVerdict::Error);
+        assert_eq!(apply_policy(&cfg, Some("LGPL-2.1")), PolicyVerdict::Error);
+        assert_eq!(apply_policy(&cfg, None), PolicyVerdict::Error);
+        // Packages are not restricted, unlike `mode = "package-allowlist"`
+        assert_eq!(apply_package_policy(&cfg, "anything", "1.0.0"), None);
+    }
+
+    #[test]
+    fn test_license_allowlist_expressions() {
+        let cfg = license_allowlist_config();
+        // A choice passes when one option is allowed; a conjunction needs all
+        assert_eq!(
+            apply_policy(&cfg, Some("MIT OR GPL-3.0")),
+            PolicyVerdict::Pass
+        );
+        assert_eq!(
+            apply_policy(&cfg, Some("Apache-2.0 AND MIT")),
+            PolicyVerdict::Pass
+        );
+        assert_eq!(
+            apply_policy(&cfg, Some("MIT AND LGPL-2.1")),
+            PolicyVerdict::Error
+        );
+        assert_eq!(
+            apply_policy(&cfg, Some("Apache-2.0 WITH LLVM-exception")),
+            PolicyVerdict::Pass
+        );
+    }
+
+    fn package_exceptions_config() -> Config {
+        toml::from_str(
+            r#"
+[policy]
+default = "warn"
+
+[policy.licenses]
+"GPL-3.0" = "error"
+
+[policy.packages]
+"internal-gpl" = "pass"
+"internal-gpl@0.9.0" = "error"
+"@acme/gpl-widget" = "review"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_package_exception_by_name() {
+        let cfg = package_exceptions_config();
+        assert_eq!(apply_policy(&cfg, Some("GPL-3.0")), PolicyVerdict::Error);
+        assert_eq!(
+            apply_package_policy(&cfg, "internal-gpl", "1.2.0"),
+            Some(PolicyVerdict::Pass)
+        );
+        assert_eq!(
+            apply_package_policy(&cfg, "@acme/gpl-widget", "3.0.0"),
+            Some(PolicyVerdict::Review)
+        );
+        // Other packages fall through to the license rules
+        assert_eq!(apply_package_policy(&cfg, "gpl-lib", "1.2.0"), None);
+    }
+
+    #[test]
+    fn test_package_exception_prefers_exact_version() {
+        let cfg = package_exceptions_config();
+        assert_eq!(
+            apply_package_policy(&cfg, "internal-gpl", "0.9.0"),
+            Some(PolicyVerdict::Error)
+        );
+        assert_eq!(
+            apply_package_policy(&cfg, "internal-gpl", "0.9.1"),
+            Some(PolicyVerdict::Pass)
+        );
+    }
+
+    #[test]
+    fn test_package_patterns_most_specific_first() {
+        let cfg: Config = toml::from_str(
+            r#"
+[policy]
+default = "error"
+
+[policy.packages]
+"@mycompany/*" = "pass"
+"@mycompany/legacy-*" = "review"
+"@mycompany/legacy-ui@1.*" = "warn"
+"com.internal.*" = "pass"
+"com.internal.vendored.*" = "error"
+"aws-*" = "pass"
+"#,
+        )
+        .unwrap();
+        let verdict = |name: &str, version: &str| apply_package_policy(&cfg, name, version);
+
+        // Scoped npm packages
+        assert_eq!(
+            verdict("@mycompany/api", "2.0.0"),
+            Some(PolicyVerdict::Pass)
+        );
+        assert_eq!(
+            verdict("@mycompany/legacy-auth", "2.0.0"),
+            Some(PolicyVerdict::Review)
+        );
+        assert_eq!(
+            verdict("@mycompany/legacy-ui", "1.4.0"),
+            Some(PolicyVerdict::Warn)
+        );
+        assert_eq!(verdict("@othercompany/api", "2.0.0"), None);
+
+        // Java groups (`group:artifact`)
+        assert_eq!(
+            verdict("com.internal.auth:client", "1.0"),
+            Some(PolicyVerdict::Pass)
+        );
+        assert_eq!(
+            verdict("com.internal.vendored.gpl:lib", "1.0"),
+            Some(PolicyVerdict::Error)
+        );
+        assert_eq!(verdict("com.external:lib", "1.0"), None);
+
+        assert_eq!(verdict("aws-sdk-s3", "1.0.0"), Some(PolicyVerdict::Pass));
+        assert_eq!(verdict("tokio", "1.0.0"), None);
+    }
+
+    #[test]
+    fn test_package_patterns_use_glob_syntax() {
+        let cfg: Config = toml::from_str(
+            r#"
+[policy.packages]
+"{serde,tokio}-*" = "pass"
+"lib?" = "review"
+"openssl@1.[01].*" = "error"
+"#,
+        )
+        .unwrap();
+        let verdict = |name: &str, version: &str| apply_package_policy(&cfg, name, version);
+
+        assert_eq!(verdict("serde-json", "1.0.0"), Some(PolicyVerdict::Pass));
+        assert_eq!(verdict("tokio-util", "0.7.0"), Some(PolicyVerdict::Pass));
+        assert_eq!(verdict("rand-core", "0.6.0"), None);
+        assert_eq!(verdict("libz", "1.0.0"), Some(PolicyVerdict::Review));
+        assert_eq!(verdict("libzz", "1.0.0"), None);
+        assert_eq!(verdict("openssl", "1.1.1"), Some(PolicyVerdict::Error));
+        assert_eq!(verdict("openssl", "3.0.0"), None);
+    }
+
+    #[test]
+    fn test_validate_config_reports_invalid_package_pattern() {
+        let content = r#"[policy]
+default = "warn"
+
+[policy.packages]
+"@acme/*" = "pass"
+"lib[abc" = "review"
+"#;
+        let err = validate_config(content).unwrap_err().to_string();
+        assert!(
+            err.starts_with("line 6: invalid package pattern 'lib[abc':"),
+            "{}",
+            err
+        );
+        // Patterns are compiled while deserializing, so they cannot slip through
+        assert!(toml::from_str::<Config>(content).is_err());
+    }
+
+    #[test]
+    fn test_package_exception_overrides_allowlist() {
+        let mut cfg = allowlist_config();
+        cfg.policy
+            .packages
+            .insert(PackagePattern::new("left-pad").unwrap(), PolicyAction::Warn);
+        assert_eq!(
+            apply_package_policy(&cfg, "left-pad", "1.3.0"),
+            Some(PolicyVerdict::Warn)
+        );
+    }
+
+    #[test]
+    fn test_labels_by_package_pattern() {
+        let cfg: Config = toml::from_str(
+            r#"
+[policy]
+
+[labels]
+"@acme/*" = "customer-facing"
+"eslint*" = ["internal-tooling", "dev"]
+"openssl@1.*" = ["security-review", "customer-facing"]
+"{aws,azure}-sdk-*" = "cloud"
+"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.labels_for("@acme/ui", "2.0.0"), ["customer-facing"]);
+        assert_eq!(
+            cfg.labels_for("eslint-plugin-react", "7.0.0"),
+            ["dev", "internal-tooling"]
+        );
+        assert_eq!(
+            cfg.labels_for("openssl", "1.1.1"),
+            ["customer-facing", "security-review"]
+        );
+        assert!(cfg.labels_for("openssl", "3.0.0").is_empty());
+        assert_eq!(cfg.labels_for("azure-sdk-core", "1.0.0"), ["cloud"]);
+        assert!(cfg.labels_for("left-pad", "1.3.0").is_empty());
+    }
+
+    #[test]
+    fn test_validate_config_reports_invalid_label_pattern() {
+        let content = r#"[policy]
+
+[labels]
+"lib[abc" = "internal"
+"#;
+        let err = validate_config(content).unwrap_err().to_string();
+        assert!(
+            err.starts_with("line 4: invalid label pattern 'lib[abc':"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_normalize_table_overrides_fuzzy_descriptors() {
+        let cfg: Config = toml::from_str(
+            r#"
+[policy]
+
+[normalize]
+"BSD-like" = "BSD-2-Clause"
+"Acme Corp License" = "LicenseRef-Acme"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            cfg.normalized_license("bsd-like").as_deref(),
+            Some("BSD-2-Clause")
+        );
+        assert_eq!(
+            cfg.normalized_license("Acme Corp License").as_deref(),
+            Some("LicenseRef-Acme")
+        );
+        // Built-in descriptors still apply
+        assert_eq!(cfg.normalized_license("MIT-like").as_deref(), Some("MIT"));
+        assert_eq!(
+            default_config().normalized_license("BSD-like").as_deref(),
+            Some("BSD-3-Clause")
+        );
+        assert_eq!(cfg.normalized_license("MIT"), None);
+    }
 }
@@ -1,4 +1,5 @@
-use std::path::Path;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
 use crate::models::Ecosystem;
 
@@ -16,6 +17,8 @@ pub fn detect_ecosystems(path: &Path) -> Vec<Ecosystem> {
     if path.join("requirements.txt").exists()
         || path.join("pyproject.toml").exists()
         || path.join("Pipfile.lock").exists()
+        || path.join("poetry.lock").exists()
+        || path.join("conda-lock.yml").exists()
     {
         ecosystems.push(Ecosystem::Python);
     }
@@ -30,6 +33,9 @@ pub fn detect_ecosystems(path: &Path) -> Vec<Ecosystem> {
     if path.join("package.json").exists()
         || path.join("package-lock.json").exists()
         || path.join("yarn.lock").exists()
+        || path.join("pnpm-lock.yaml").exists()
+        || path.join("deno.json").exists()
+        || path.join("deno.lock").exists()
     {
         ecosystems.push(Ecosystem::Node);
     }
@@ -41,6 +47,26 @@ pub fn detect_ecosystems(path: &Path) -> Vec<Ecosystem> {
         ecosystems.push(Ecosystem::DotNet);
     }
 
+    if path.join("deps.edn").exists() || path.join("project.clj").exists() {
+        ecosystems.push(Ecosystem::Clojure);
+    }
+
+    if path.join("dune-project").exists() || has_opam_file(path) {
+        ecosystems.push(Ecosystem::OCaml);
+    }
+
+    if path.join("go.mod").exists() {
+        ecosystems.push(Ecosystem::Go);
+    }
+
+    if path.join("Gemfile.lock").exists() || path.join("Gemfile").exists() {
+        ecosystems.push(Ecosystem::Ruby);
+    }
+
+    if path.join("composer.json").exists() || path.join("composer.lock").exists() {
+        ecosystems.push(Ecosystem::Php);
+    }
+
     ecosystems
 }
 
@@ -58,6 +84,16 @@ fn has_dotnet_project_file(path: &Path) -> bool {
     })
 }
 
+/// Returns `true` if any `*.opam` file exists directly under `path`.
+fn has_opam_file(path: &Path) -> bool {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return false;
+    };
+    entries
+        .flatten()
+        .any(|e| e.path().extension().and_then(|s| s.to_str()) == Some("opam"))
+}
+
 /// Well-known manifest filenames used to identify a project root.
 const MANIFEST_FILES: &[&str] = &[
     "Cargo.toml",
@@ -65,16 +101,69 @@ const MANIFEST_FILES: &[&str] = &[
     "requirements.txt",
     "pyproject.toml",
     "Pipfile.lock",
+    "poetry.lock",
+    "conda-lock.yml",
     "pom.xml",
     "build.gradle",
     "build.gradle.kts",
     "package.json",
     "package-lock.json",
     "yarn.lock",
+    "pnpm-lock.yaml",
+    "deno.json",
+    "deno.lock",
     "packages.config",
     "paket.dependencies",
+    "deps.edn",
+    "project.clj",
+    "dune-project",
+    "go.mod",
+    "Gemfile",
+    "Gemfile.lock",
+    "composer.json",
+    "composer.lock",
 ];
 
+/// Whether a file named `name` is a manifest or lockfile an analyzer reads —
+/// besides [`MANIFEST_FILES`], .NET / opam project files, Gradle scripts, version
+/// catalogs and lockfiles, `paket.lock`, `go.sum` and `requirements*.txt` files.
+pub fn is_manifest_file(name: &str) -> bool {
+    let extension = Path::new(name).extension().and_then(|s| s.to_str());
+    MANIFEST_FILES.contains(&name)
+        || matches!(
+            name,
+            "gradle.lockfile" | "libs.versions.toml" | "paket.lock" | "go.sum"
+        )
+        || matches!(extension, Some("csproj" | "fsproj" | "opam" | "gradle"))
+        || name.ends_with(".gradle.kts")
+        || (name.starts_with("requirements") && extension == Some("txt"))
+}
+
+/// The `projects` that contain a changed manifest or lockfile from `changed`
+/// (`--since`), in their original order. A file belongs to the deepest project
+/// directory containing it.
+pub fn touched_projects(projects: &[PathBuf], changed: &[PathBuf]) -> Vec<PathBuf> {
+    let mut touched: HashSet<&PathBuf> = HashSet::new();
+    for file in changed {
+        let Some(name) = file.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if !is_manifest_file(name) {
+            continue;
+        }
+        let owner = projects
+            .iter()
+            .filter(|project| file.starts_with(project))
+            .max_by_key(|project| project.components().count());
+        touched.extend(owner);
+    }
+    projects
+        .iter()
+        .filter(|project| touched.contains(project))
+        .cloned()
+        .collect()
+}
+
 /// Directories that should never be descended into during workspace discovery.
 const SKIP_DIRS: &[&str] = &[
     "node_modules",
@@ -91,24 +180,40 @@ const SKIP_DIRS: &[&str] = &[
     ".nuxt",
     "bin",
     "obj",
+    "_build",
+    "_opam",
 ];
 
 /// Walk `root` recursively and return one path per discovered sub-project.
 ///
 /// A directory is considered a project if it contains at least one known
-/// manifest file or a `.csproj`/`.fsproj` file. Descending stops once a
-/// project is found (nested manifests are not double-counted). Results are
-/// returned in sorted order.
+/// manifest file, a `.csproj`/`.fsproj` file, or a `*.opam` file. Descending
+/// stops once a project is found (nested manifests are not double-counted).
+/// Results are returned in sorted order.
 pub fn find_workspace_projects(root: &Path) -> Vec<std::path::PathBuf> {
     let mut results = Vec::new();
     let mut visited = std::collections::HashSet::new();
-    walk_for_projects(root, &mut results, &mut visited);
+    walk_for_projects(root, None, &mut results, &mut visited);
     results.sort();
     results
 }
 
+/// Like [`find_workspace_projects`], but only looks `max_depth` directory levels
+/// below `root` — used when a single-project scan finds no manifest at the root
+/// (e.g. the `pom.xml` lives in `app/`).
+pub fn find_nested_projects(root: &Path, max_depth: usize) -> Vec<std::path::PathBuf> {
+    let mut results = Vec::new();
+    let mut visited = std::collections::HashSet::new();
+    walk_for_projects(root, Some(max_depth), &mut results, &mut visited);
+    results.sort();
+    results
+}
+
+/// Collect projects at or below `dir`, descending at most `depth_left` more
+/// levels (unlimited when `None`).
 fn walk_for_projects(
     dir: &Path,
+    depth_left: Option<usize>,
     out: &mut Vec<std::path::PathBuf>,
     visited: &mut std::collections::HashSet<std::path::PathBuf>,
 ) {
@@ -123,13 +228,18 @@ fn walk_for_projects(
 
     // Is this directory itself a project?
     let is_project = MANIFEST_FILES.iter().any(|f| dir.join(f).exists())
-        || has_dotnet_project_file(dir);
+        || has_dotnet_project_file(dir)
+        || has_opam_file(dir);
 
     if is_project {
         out.push(dir.to_path_buf());
         return; // stop descending — nested manifests not double-counted
     }
 
+    if depth_left == Some(0) {
+        return;
+    }
+
     // Recurse into sorted subdirectories, skipping noise dirs
     let Ok(entries) = std::fs::read_dir(dir) else {
         return;
@@ -153,7 +263,7 @@ fn walk_for_projects(
     subdirs.sort();
 
     for sub in subdirs {
-        walk_for_projects(&sub, out, visited);
+        walk_for_projects(&sub, depth_left.map(|d| d - 1), out, visited);
     }
 }
 
@@ -163,6 +273,38 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_changed_manifest_maps_to_its_project() {
+        let projects = vec![
+            PathBuf::from("/repo"),
+            PathBuf::from("/repo/services/api"),
+            PathBuf::from("/repo/services/web"),
+        ];
+        let touched = |changed: &[&str]| {
+            let changed: Vec<PathBuf> = changed.iter().map(PathBuf::from).collect();
+            touched_projects(&projects, &changed)
+        };
+
+        assert_eq!(
+            touched(&["/repo/services/api/package-lock.json"]),
+            [PathBuf::from("/repo/services/api")]
+        );
+        // Source files do not count; a root manifest belongs to the root project only
+        assert!(touched(&["/repo/services/web/src/index.ts"]).is_empty());
+        assert_eq!(touched(&["/repo/Cargo.lock"]), [PathBuf::from("/repo")]);
+        assert_eq!(
+            touched(&[
+                "/repo/services/web/gradle/deps.gradle",
+                "/repo/services/api/app.csproj",
+                "/elsewhere/package.json",
+            ]),
+            [
+                PathBuf::from("/repo/services/api"),
+                PathBuf::from("/repo/services/web"),
+            ]
+        );
+    }
+
     fn touch(dir: &std::path::Path, name: &str) {
         fs::write(dir.join(name), "").unwrap();
     }
@@ -194,6 +336,25 @@ mod tests {
         assert_eq!(projects.len(), 2);
     }
 
+    #[test]
+    fn test_nested_projects_found_within_depth() {
+        let tmp = TempDir::new().unwrap();
+        let app = tmp.path().join("app");
+        let deep = tmp.path().join("a").join("b").join("c");
+        fs::create_dir_all(&app).unwrap();
+        fs::create_dir_all(&deep).unwrap();
+        touch(&app, "pom.xml");
+        touch(&deep, "package.json"); // three levels down: out of reach
+
+        let projects = find_nested_projects(tmp.path(), 2);
+        assert_eq!(projects.len(), 1);
+        assert_eq!(
+            projects[0].canonicalize().unwrap(),
+            app.canonicalize().unwrap()
+        );
+        assert_eq!(detect_ecosystems(&projects[0]), vec![Ecosystem::Java]);
+    }
+
     #[test]
     fn test_does_not_recurse_into_sub_project() {
         let tmp = TempDir::new().unwrap();
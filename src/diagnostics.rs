@@ -0,0 +1,162 @@
+//! Advisory messages on stderr (warnings, notes, progress counts).
+//!
+//! By default they are colored text lines; `--json-diagnostics` switches them to
+//! newline-delimited JSON objects (`{"level", "message", "file"?, "package"?}`)
+//! for log pipelines. Reports on stdout are never affected.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use colored::Colorize;
+use serde::Serialize;
+
+static JSON: AtomicBool = AtomicBool::new(false);
+
+#[cfg(test)]
+thread_local! {
+    /// Lines emitted on this thread while [`capture`] runs, kept off stderr.
+    static CAPTURED: std::cell::RefCell<Option<Vec<String>>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Run `f`, returning its result and the diagnostic lines it emitted on this thread.
+#[cfg(test)]
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    let outer = CAPTURED.replace(Some(Vec::new()));
+    let result = f();
+    let lines = CAPTURED.replace(outer).unwrap_or_default();
+    (result, lines)
+}
+
+/// Emit every later diagnostic as a JSON line (`--json-diagnostics`).
+pub fn set_json(enabled: bool) {
+    JSON.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether diagnostics are JSON lines; interactive stderr output such as
+/// progress bars is suppressed then.
+pub fn is_json() -> bool {
+    JSON.load(Ordering::Relaxed)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Info,
+    Note,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Diagnostic {
+    level: Level,
+    message: String,
+    /// File the message is about; JSON only, the text form carries it in `message`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+    /// Package the message is about; JSON only, like `file`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    package: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(level: Level, message: impl Into<String>) -> Self {
+        Diagnostic {
+            level,
+            message: message.into(),
+            file: None,
+            package: None,
+        }
+    }
+
+    pub fn info(message: impl Into<String>) -> Self {
+        Self::new(Level::Info, message)
+    }
+
+    pub fn note(message: impl Into<String>) -> Self {
+        Self::new(Level::Note, message)
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(Level::Warning, message)
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(Level::Error, message)
+    }
+
+    pub fn file(mut self, path: &Path) -> Self {
+        self.file = Some(path.display().to_string());
+        self
+    }
+
+    pub fn package(mut self, name: &str) -> Self {
+        self.package = Some(name.to_string());
+        self
+    }
+
+    /// Print the diagnostic to stderr in the selected format.
+    pub fn emit(&self) {
+        let line = if is_json() {
+            self.to_json()
+        } else {
+            self.to_text()
+        };
+        #[cfg(test)]
+        if CAPTURED
+            .with_borrow_mut(|captured| captured.as_mut().map(|lines| lines.push(line.clone())))
+            .is_some()
+        {
+            return;
+        }
+        eprintln!("{}", line);
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("diagnostic serializes to JSON")
+    }
+
+    fn to_text(&self) -> String {
+        let prefix = match self.level {
+            Level::Info => return self.message.clone(),
+            Level::Note => "note:".cyan().bold(),
+            Level::Warning => "warning:".yellow().bold(),
+            Level::Error => "error:".red().bold(),
+        };
+        format!("{} {}", prefix, self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    use crate::analyzer::node::NodeAnalyzer;
+    use crate::analyzer::Analyzer;
+    use crate::cli::Cli;
+
+    #[test]
+    fn test_broken_lockfile_is_one_json_line() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let lock = tmp.path().join("package-lock.json");
+        std::fs::write(&lock, "{\n  \"packages\": {").unwrap();
+
+        let cli = Cli::parse_from(["license-checkr", "--json-diagnostics"]);
+        set_json(cli.json_diagnostics);
+        let (deps, lines) = capture(|| NodeAnalyzer::new().analyze(tmp.path()).unwrap());
+        set_json(false);
+
+        assert!(deps.is_empty());
+        assert_eq!(lines.len(), 1);
+        assert!(!lines[0].contains('\n'));
+        let json: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(json["level"], "warning");
+        assert_eq!(json["file"], lock.display().to_string());
+        assert!(json["message"]
+            .as_str()
+            .unwrap()
+            .contains("package-lock.json: failed to parse"));
+        assert!(json.get("package").is_none());
+    }
+}
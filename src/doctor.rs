@@ -0,0 +1,173 @@
+//! `license-checkr doctor`: check the environment a scan depends on and print a
+//! ✓/✗ checklist with remediation hints.
+//!
+//! Diagnostics never fail the command — it always exits 0 so it can be pasted
+//! into a support ticket as-is.
+
+use std::path::Path;
+use std::time::Duration;
+
+use colored::Colorize;
+
+use crate::analyzer::rust::cargo_registry_src;
+use crate::config::{config_path, load_config};
+use crate::registry;
+
+/// Outcome of one diagnostic.
+#[derive(Debug)]
+struct Check {
+    ok: bool,
+    label: String,
+    /// How to fix a failed check.
+    hint: Option<String>,
+}
+
+impl Check {
+    fn pass(label: impl Into<String>) -> Self {
+        Check {
+            ok: true,
+            label: label.into(),
+            hint: None,
+        }
+    }
+
+    fn fail(label: impl Into<String>, hint: impl Into<String>) -> Self {
+        Check {
+            ok: false,
+            label: label.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// Run every check for a scan of `project_path` and print the checklist.
+pub async fn run(project_path: &Path, config_override: Option<&Path>) {
+    let mut checks = vec![
+        check_config(project_path, config_override),
+        check_cargo_cache(cargo_registry_src().as_deref()),
+    ];
+    checks.extend(check_registries().await);
+
+    println!(
+        "\n {} v{} doctor\n",
+        "license-checkr".bold(),
+        env!("CARGO_PKG_VERSION")
+    );
+    for check in &checks {
+        let mark = if check.ok { "✓".green() } else { "✗".red() };
+        println!(" {} {}", mark, check.label);
+        if let Some(hint) = &check.hint {
+            println!("     {} {}", "→".dimmed(), hint);
+        }
+    }
+    println!(
+        " {} SPDX identifiers: built-in table of license-checkr v{} (no separate list version)",
+        "·".dimmed(),
+        env!("CARGO_PKG_VERSION")
+    );
+
+    let failed = checks.iter().filter(|c| !c.ok).count();
+    if failed == 0 {
+        println!("\n All checks passed.\n");
+    } else {
+        println!(
+            "\n {} check{} failed.\n",
+            failed,
+            if failed == 1 { "" } else { "s" }
+        );
+    }
+}
+
+/// Which config applies to `project_path`, and whether it parses.
+fn check_config(project_path: &Path, config_override: Option<&Path>) -> Check {
+    let Some(path) = config_path(project_path, config_override) else {
+        return Check::pass("Config: none found, using the built-in default policy");
+    };
+    match load_config(project_path, config_override) {
+        Ok(_) => Check::pass(format!("Config: {}", path.display())),
+        Err(e) => Check::fail(
+            format!("Config: {} ({:#})", path.display(), e),
+            "Fix the TOML error above; `policy.default` and license actions must be pass, warn, review or error",
+        ),
+    }
+}
+
+/// Whether the Cargo registry cache used for offline Rust license lookups exists.
+fn check_cargo_cache(registry_src: Option<&Path>) -> Check {
+    match registry_src {
+        Some(dir) if dir.is_dir() => Check::pass(format!("Cargo cache: {}", dir.display())),
+        Some(dir) => Check::fail(
+            format!("Cargo cache: {} not found", dir.display()),
+            "Run `cargo fetch` in the project (or set CARGO_HOME) so Rust licenses resolve offline",
+        ),
+        None => Check::fail(
+            "Cargo cache: CARGO_HOME not set and no home directory",
+            "Set CARGO_HOME to your Cargo installation so Rust licenses resolve offline",
+        ),
+    }
+}
+
+/// A quick `HEAD` request to every `--online` registry host.
+async fn check_registries() -> Vec<Check> {
+    let client = match reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            return vec![Check::fail(
+                format!("Network: cannot create HTTP client ({})", e),
+                "Check the TLS setup of this machine",
+            )]
+        }
+    };
+
+    let requests = registry::hosts().into_iter().map(|host| {
+        let client = client.clone();
+        async move {
+            let result = client
+                .head(format!("https://{}/", host))
+                .header("User-Agent", "license-checkr/0.1.0")
+                .send()
+                .await;
+            match result {
+                // Any HTTP answer means the host is reachable
+                Ok(_) => Check::pass(format!("Network: {} reachable", host)),
+                Err(e) => Check::fail(
+                    format!("Network: {} unreachable ({})", host, e),
+                    "--online lookups against this registry will fail; check connectivity, proxy (HTTPS_PROXY) or firewall rules",
+                ),
+            }
+        }
+    });
+    futures::future::join_all(requests).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_check_reports_invalid_config() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = tmp.path().join("config.toml");
+
+        std::fs::write(&config, "[policy]\ndefault = \"warn\"\n").unwrap();
+        let check = check_config(tmp.path(), Some(&config));
+        assert!(check.ok, "{:?}", check);
+
+        std::fs::write(&config, "[policy]\ndefault = \"allow\"\n").unwrap();
+        let check = check_config(tmp.path(), Some(&config));
+        assert!(!check.ok);
+        assert!(check.label.contains("config.toml"));
+        assert!(check.hint.is_some());
+    }
+
+    #[test]
+    fn test_cargo_cache_check() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        assert!(check_cargo_cache(Some(tmp.path())).ok);
+        assert!(!check_cargo_cache(Some(&tmp.path().join("missing"))).ok);
+        assert!(!check_cargo_cache(None).ok);
+    }
+}
@@ -0,0 +1,56 @@
+//! `--since <ref>`: the files changed in git since a ref, used to skip workspace
+//! projects whose manifests were not touched.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+/// The repository containing `path`: the nearest ancestor with a `.git` entry
+/// (a directory, or a file for worktrees and submodules).
+fn repo_root(path: &Path) -> Option<&Path> {
+    path.ancestors().find(|dir| dir.join(".git").exists())
+}
+
+/// Absolute paths of the files that differ between `since` and the working
+/// tree (`git diff --name-only <since>`), across every repository containing
+/// one of `paths`.
+///
+/// Fails when a path is not inside a git repository, `git` cannot be run, or
+/// the diff fails (e.g. `since` is not a valid ref).
+pub fn changed_files(paths: &[PathBuf], since: &str) -> Result<Vec<PathBuf>> {
+    let mut roots: Vec<&Path> = Vec::new();
+    for path in paths {
+        let root = repo_root(path)
+            .with_context(|| format!("{} is not in a git repository", path.display()))?;
+        if !roots.contains(&root) {
+            roots.push(root);
+        }
+    }
+
+    let mut changed = Vec::new();
+    for root in roots {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(["diff", "--name-only", "-z", since, "--"])
+            .output()
+            .context("Failed to run git")?;
+        if !output.status.success() {
+            bail!(
+                "git diff {} failed in {}: {}",
+                since,
+                root.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        // Paths are relative to the repository root, NUL-separated under `-z`
+        changed.extend(
+            String::from_utf8_lossy(&output.stdout)
+                .split('\0')
+                .filter(|name| !name.is_empty())
+                .map(|name| root.join(name)),
+        );
+    }
+    Ok(changed)
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::baseline;
+use crate::models::{Dependency, PolicyVerdict};
+
+/// Location of the local run history for `project`:
+/// `~/.cache/license-checkr/history/{project-hash}.json`.
+///
+/// The hash is derived from the project's absolute path so each checkout keeps
+/// its own history.
+pub fn history_path(project: &Path) -> Result<PathBuf> {
+    let cache = dirs::cache_dir().context("Could not determine the user cache directory")?;
+    Ok(cache
+        .join("license-checkr")
+        .join("history")
+        .join(format!("{:016x}.json", project_hash(project))))
+}
+
+/// Stable 64-bit FNV-1a hash of the project path.
+pub fn project_hash(project: &Path) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in project.to_string_lossy().bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+/// Compare `deps` with the previous run stored at `file`, mark what is new, then
+/// overwrite the history with the current scan.
+///
+/// A dependency is new when it was absent from the previous run, or when it now
+/// errors but did not before. On the first run (no history yet) or when the
+/// history file is unreadable, nothing is marked.
+pub fn track(file: &Path, deps: &mut [Dependency]) -> Result<()> {
+    if file.exists() {
+        if let Ok(previous) = baseline::read(file) {
+            mark_new(&previous, deps);
+        }
+    }
+
+    if let Some(dir) = file.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let refs: Vec<&Dependency> = deps.iter().collect();
+    baseline::write(file, &refs)
+}
+
+fn mark_new(previous: &[Dependency], deps: &mut [Dependency]) {
+    let mut errored: HashMap<String, bool> = HashMap::new();
+    for dep in previous {
+        *errored.entry(baseline::identity(dep)).or_default() |= dep.verdict == PolicyVerdict::Error;
+    }
+
+    for dep in deps {
+        dep.is_new = match errored.get(&baseline::identity(dep)) {
+            None => true,
+            Some(was_error) => dep.verdict == PolicyVerdict::Error && !was_error,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::dep;
+
+    #[test]
+    fn test_two_runs_mark_added_and_newly_erroring() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let file = tmp.path().join("history").join("project.json");
+
+        // First run: no history yet, nothing is new
+        let mut first = vec![
+            dep("requests", Some("Apache-2.0"), PolicyVerdict::Pass),
+            dep("chardet", Some("LGPL-2.1"), PolicyVerdict::Warn),
+        ];
+        track(&file, &mut first).unwrap();
+        assert!(first.iter().all(|d| !d.is_new));
+        assert!(file.exists());
+
+        // Second run: one dependency added, one now errors
+        let mut second = vec![
+            dep("requests", Some("Apache-2.0"), PolicyVerdict::Pass),
+            dep("chardet", Some("GPL-3.0"), PolicyVerdict::Error),
+            dep("urllib3", Some("MIT"), PolicyVerdict::Pass),
+        ];
+        track(&file, &mut second).unwrap();
+        let new: Vec<&str> = second
+            .iter()
+            .filter(|d| d.is_new)
+            .map(|d| d.name.as_str())
+            .collect();
+        assert_eq!(new, ["chardet", "urllib3"]);
+
+        // Third run with the same set: nothing new, history was updated
+        let mut third = second.clone();
+        track(&file, &mut third).unwrap();
+        assert!(third.iter().all(|d| !d.is_new));
+    }
+
+    #[test]
+    fn test_history_path_is_per_project() {
+        let a = history_path(Path::new("/work/a")).unwrap();
+        let b = history_path(Path::new("/work/b")).unwrap();
+        assert_ne!(a, b);
+        assert!(a.ends_with(format!("{:016x}.json", project_hash(Path::new("/work/a")))));
+    }
+}
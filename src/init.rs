@@ -0,0 +1,119 @@
+//! `license-checkr init`: scaffold a commented `.license-checkr/config.toml`
+//! holding the built-in default policy.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::config::Config;
+
+/// Write the starter config to `config_override`, or to
+/// `.license-checkr/config.toml` under `project_path`. An existing file is only
+/// replaced with `force`.
+pub fn run(project_path: &Path, config_override: Option<&Path>, force: bool) -> Result<PathBuf> {
+    let path = match config_override {
+        Some(path) => path.to_path_buf(),
+        None => project_path.join(".license-checkr").join("config.toml"),
+    };
+    if path.exists() && !force {
+        anyhow::bail!(
+            "{} already exists; pass --force to overwrite it",
+            path.display()
+        );
+    }
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    std::fs::write(&path, starter_config())
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// [`Config::default`] as commented TOML, followed by commented-out examples of
+/// the optional tables.
+fn starter_config() -> String {
+    let defaults = Config::default();
+    let mut licenses: Vec<_> = defaults.policy.licenses.iter().collect();
+    // Alphabetical, with the `unknown` catch-all last
+    licenses.sort_by_key(|(id, _)| (id.as_str() == "unknown", id.as_str()));
+
+    let mut out = String::from(
+        "# license-checkr policy. Actions: pass | warn | review | error.\n\
+         # Every option is described under \"Policy Configuration\" in the README.\n\
+         \n\
+         [policy]\n\
+         # Verdict for licenses without an entry below\n",
+    );
+    writeln!(
+        out,
+        "default = \"{}\"",
+        defaults.policy.default.to_verdict()
+    )
+    .unwrap();
+    out.push_str("\n# Per-license verdicts, keyed by SPDX id\n[policy.licenses]\n");
+    for (id, action) in licenses {
+        writeln!(out, "\"{}\" = \"{}\"", id, action.to_verdict()).unwrap();
+    }
+    out.push_str(
+        "\n\
+         # Verdicts per risk level for licenses not listed above\n\
+         # [policy.risk]\n\
+         # strong-copyleft = \"error\"\n\
+         # weak-copyleft = \"warn\"\n\
+         \n\
+         # Per-package exceptions (`name`, `name@version`, `*` wildcards); they\n\
+         # override the license rules\n\
+         # [policy.packages]\n\
+         # \"internal-sdk\" = \"pass\"\n\
+         # \"left-pad@1.0.0\" = \"error\"\n\
+         \n\
+         # Map license strings that are not SPDX ids\n\
+         # [normalize]\n\
+         # \"BSD-like\" = \"BSD-2-Clause\"\n\
+         \n\
+         # Warn when a scan finds fewer dependencies than expected\n\
+         # [limits]\n\
+         # min_dependencies = 50\n",
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::apply_policy;
+    use crate::models::PolicyVerdict;
+
+    #[test]
+    fn test_starter_config_parses_to_the_defaults() {
+        let content = starter_config();
+        assert!(crate::config::validate_config(&content).unwrap().is_empty());
+        let config: Config = toml::from_str(&content).unwrap();
+        let defaults = Config::default();
+        assert_eq!(config.policy.licenses.len(), defaults.policy.licenses.len());
+        for id in ["MIT", "LGPL-2.1", "GPL-3.0", "unknown", "Zlib"] {
+            assert_eq!(
+                apply_policy(&config, Some(id)),
+                apply_policy(&defaults, Some(id))
+            );
+        }
+        assert_eq!(apply_policy(&config, Some("GPL-3.0")), PolicyVerdict::Error);
+    }
+
+    #[test]
+    fn test_init_refuses_to_overwrite_without_force() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = run(tmp.path(), None, false).unwrap();
+        assert_eq!(path, tmp.path().join(".license-checkr/config.toml"));
+
+        std::fs::write(&path, "[policy]\n").unwrap();
+        let err = run(tmp.path(), None, false).unwrap_err();
+        assert!(err.to_string().contains("--force"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[policy]\n");
+
+        run(tmp.path(), None, true).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), starter_config());
+    }
+}
@@ -8,13 +8,17 @@ use crate::models::LicenseRisk;
 /// - SPDX OR expressions (MIT OR Apache-2.0)  → most permissive wins
 /// - SPDX AND expressions (MIT AND GPL-3.0)  → most restrictive wins
 /// - Proprietary/commercial strings
+/// - `LicenseRef-` custom ids → `license_ref_risk` (`[policy] license_ref_risk`)
 /// - Empty / unknown
-pub fn classify(license: &str) -> LicenseRisk {
+pub fn classify(license: &str, license_ref_risk: &LicenseRisk) -> LicenseRisk {
     let trimmed = license.trim();
 
     if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("unknown") {
         return LicenseRisk::Unknown;
     }
+    if is_license_ref(trimmed) {
+        return license_ref_risk.clone();
+    }
 
     let lower = trimmed.to_lowercase();
     if lower.contains("proprietary") || lower.contains("commercial") {
@@ -29,7 +33,7 @@ pub fn classify(license: &str) -> LicenseRisk {
     if normalized.contains(" OR ") {
         let risks: Vec<LicenseRisk> = normalized
             .split(" OR ")
-            .map(|p| classify_single(p.trim()))
+            .map(|p| classify_single(p.trim(), license_ref_risk))
             .collect();
         return most_permissive(risks);
     }
@@ -38,20 +42,42 @@ pub fn classify(license: &str) -> LicenseRisk {
     if normalized.contains(" AND ") {
         let risks: Vec<LicenseRisk> = normalized
             .split(" AND ")
-            .map(|p| classify_single(p.trim()))
+            .map(|p| classify_single(p.trim(), license_ref_risk))
             .collect();
         return most_restrictive(risks);
     }
 
-    classify_single(&normalized)
+    classify_single(&normalized, license_ref_risk)
 }
 
-fn classify_single(id: &str) -> LicenseRisk {
+fn classify_single(id: &str, license_ref_risk: &LicenseRisk) -> LicenseRisk {
     // Strip WITH exception clauses (e.g. "GPL-2.0 WITH Classpath-exception-2.0")
     let base = id.split(" WITH ").next().unwrap_or(id).trim();
+    if is_license_ref(base) {
+        return license_ref_risk.clone();
+    }
     classify_spdx_id(base)
 }
 
+/// Whether `id` is a custom SPDX id: `LicenseRef-…`, optionally prefixed by
+/// `DocumentRef-…:`.
+pub fn is_license_ref(id: &str) -> bool {
+    // A compound expression is not a single id, even if an operand is one
+    if id.contains(char::is_whitespace) {
+        return false;
+    }
+    let id = match id.split_once(':') {
+        Some((document, rest)) if has_prefix(document, "DocumentRef-") => rest,
+        _ => id,
+    };
+    has_prefix(id, "LicenseRef-")
+}
+
+fn has_prefix(s: &str, prefix: &str) -> bool {
+    s.get(..prefix.len())
+        .is_some_and(|p| p.eq_ignore_ascii_case(prefix))
+}
+
 fn most_permissive(risks: Vec<LicenseRisk>) -> LicenseRisk {
     if risks.contains(&LicenseRisk::Permissive) {
         return LicenseRisk::Permissive;
@@ -88,40 +114,113 @@ fn most_restrictive(risks: Vec<LicenseRisk>) -> LicenseRisk {
 mod tests {
     use super::*;
 
+    /// [`classify`] with the default `license_ref_risk`.
+    fn classify_default(license: &str) -> LicenseRisk {
+        classify(license, &LicenseRisk::Proprietary)
+    }
+
     #[test]
     fn test_or_expression() {
-        assert_eq!(classify("MIT OR GPL-3.0"), LicenseRisk::Permissive);
+        assert_eq!(classify_default("MIT OR GPL-3.0"), LicenseRisk::Permissive);
     }
 
     #[test]
     fn test_slash_separator() {
-        assert_eq!(classify("MIT/Apache-2.0"), LicenseRisk::Permissive);
-        assert_eq!(classify("MIT/GPL-3.0"), LicenseRisk::Permissive);
-        assert_eq!(classify("GPL-3.0/LGPL-3.0"), LicenseRisk::WeakCopyleft);
+        assert_eq!(classify_default("MIT/Apache-2.0"), LicenseRisk::Permissive);
+        assert_eq!(classify_default("MIT/GPL-3.0"), LicenseRisk::Permissive);
+        assert_eq!(
+            classify_default("GPL-3.0/LGPL-3.0"),
+            LicenseRisk::WeakCopyleft
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_descriptors_are_classified() {
+        // Without normalization the slash would split into "zlib OR libpng"
+        assert_eq!(classify_default("zlib/libpng"), LicenseRisk::Permissive);
+        assert_eq!(classify_default("BSD-like"), LicenseRisk::Permissive);
+        assert_eq!(classify_default("MIT-like"), LicenseRisk::Permissive);
+        assert_eq!(classify_default("Apache-style"), LicenseRisk::Permissive);
+    }
+
+    #[test]
+    fn test_license_ref_ids() {
+        assert_eq!(
+            classify_default("LicenseRef-Acme"),
+            LicenseRisk::Proprietary
+        );
+        assert_eq!(
+            classify_default("DocumentRef-spdx-tool:LicenseRef-Acme"),
+            LicenseRisk::Proprietary
+        );
+        assert_eq!(
+            classify("LicenseRef-Acme", &LicenseRisk::Unknown),
+            LicenseRisk::Unknown
+        );
+        assert_eq!(
+            classify("MIT AND LicenseRef-Acme", &LicenseRisk::WeakCopyleft),
+            LicenseRisk::WeakCopyleft
+        );
+        assert!(!is_license_ref("LicenseRefinery"));
+    }
+
+    #[test]
+    fn test_compound_expression_with_document_ref_is_classified_per_operand() {
+        let expr = "MIT OR DocumentRef-spdx-tool:LicenseRef-Acme";
+        assert!(!is_license_ref(expr));
+        assert_eq!(classify_default(expr), LicenseRisk::Permissive);
+        assert_eq!(
+            classify(
+                "GPL-3.0 AND DocumentRef-x:LicenseRef-y",
+                &LicenseRisk::Unknown
+            ),
+            LicenseRisk::StrongCopyleft
+        );
+        // Only a leading `DocumentRef-…:` prefix is stripped
+        assert!(!is_license_ref("Other:LicenseRef-Acme"));
+        assert!(is_license_ref("documentref-x:licenseref-y"));
     }
 
     #[test]
     fn test_and_expression() {
-        assert_eq!(classify("MIT AND GPL-3.0"), LicenseRisk::StrongCopyleft);
+        assert_eq!(
+            classify_default("MIT AND GPL-3.0"),
+            LicenseRisk::StrongCopyleft
+        );
     }
 
     #[test]
     fn test_proprietary() {
-        assert_eq!(classify("Proprietary"), LicenseRisk::Proprietary);
-        assert_eq!(classify("commercial license"), LicenseRisk::Proprietary);
+        assert_eq!(classify_default("Proprietary"), LicenseRisk::Proprietary);
+        assert_eq!(
+            classify_default("commercial license"),
+            LicenseRisk::Proprietary
+        );
     }
 
     #[test]
     fn test_unknown() {
-        assert_eq!(classify(""), LicenseRisk::Unknown);
-        assert_eq!(classify("unknown"), LicenseRisk::Unknown);
-        assert_eq!(classify("CUSTOM-LICENSE-42"), LicenseRisk::Unknown);
+        assert_eq!(classify_default(""), LicenseRisk::Unknown);
+        assert_eq!(classify_default("unknown"), LicenseRisk::Unknown);
+        assert_eq!(classify_default("CUSTOM-LICENSE-42"), LicenseRisk::Unknown);
+    }
+
+    #[test]
+    fn test_ocaml_linking_exception() {
+        assert_eq!(
+            classify_default("LGPL-2.1-with-OCaml-exception"),
+            LicenseRisk::WeakCopyleft
+        );
+        assert_eq!(
+            classify_default("LGPL-2.1-or-later WITH OCaml-LGPL-linking-exception"),
+            LicenseRisk::WeakCopyleft
+        );
     }
 
     #[test]
     fn test_with_exception() {
         assert_eq!(
-            classify("GPL-2.0 WITH Classpath-exception-2.0"),
+            classify_default("GPL-2.0 WITH Classpath-exception-2.0"),
             LicenseRisk::StrongCopyleft
         );
     }
@@ -0,0 +1,81 @@
+//! Last-resort license detection from the license files shipped with a package,
+//! for packages whose metadata declares no license.
+
+use std::path::Path;
+
+use super::text::detect_from_text;
+
+/// License file names checked, in order.
+const LICENSE_FILES: &[&str] = &["LICENSE", "LICENSE.txt", "LICENSE.md", "COPYING"];
+
+/// Infer an SPDX id from the first recognizable license file directly in `dir`
+/// (`LICENSE`, `LICENSE.txt`, `LICENSE.md`, `COPYING`). Files whose text matches
+/// no known license are skipped; `None` when nothing matches.
+pub fn license_from_dir(dir: &Path) -> Option<String> {
+    LICENSE_FILES
+        .iter()
+        .filter_map(|name| std::fs::read_to_string(dir.join(name)).ok())
+        .find_map(|text| detect_from_text(&text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const APACHE: &str = "
+                                 Apache License
+                           Version 2.0, January 2004
+                        http://www.apache.org/licenses/
+
+   TERMS AND CONDITIONS FOR USE, REPRODUCTION, AND DISTRIBUTION
+";
+
+    const GPL: &str = "
+                    GNU GENERAL PUBLIC LICENSE
+                       Version 3, 29 June 2007
+
+ Copyright (C) 2007 Free Software Foundation, Inc. <https://fsf.org/>
+ Everyone is permitted to copy and distribute verbatim copies
+ of this license document, but changing it is not allowed.
+";
+
+    const BSD: &str = "Copyright (c) 2024, Example
+All rights reserved.
+
+Redistribution and use in source and binary forms, with or without
+modification, are permitted provided that the following conditions are met:
+
+3. Neither the name of the copyright holder nor the names of its
+   contributors may be used to endorse or promote products derived from
+   this software without specific prior written permission.
+";
+
+    #[test]
+    fn test_license_from_dir() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = |name: &str, files: &[(&str, &str)]| {
+            let dir = tmp.path().join(name);
+            std::fs::create_dir_all(&dir).unwrap();
+            for (file, text) in files {
+                std::fs::write(dir.join(file), text).unwrap();
+            }
+            dir
+        };
+
+        let apache = dir("apache", &[("LICENSE.txt", APACHE)]);
+        assert_eq!(license_from_dir(&apache).as_deref(), Some("Apache-2.0"));
+
+        let gpl = dir("gpl", &[("COPYING", GPL)]);
+        assert_eq!(license_from_dir(&gpl).as_deref(), Some("GPL-3.0"));
+
+        // An unrecognized LICENSE does not hide a known COPYING
+        let both = dir(
+            "both",
+            &[("LICENSE.md", "See the website."), ("COPYING", BSD)],
+        );
+        assert_eq!(license_from_dir(&both).as_deref(), Some("BSD-3-Clause"));
+
+        let none = dir("none", &[("README.md", GPL)]);
+        assert_eq!(license_from_dir(&none), None);
+    }
+}
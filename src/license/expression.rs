@@ -0,0 +1,279 @@
+use std::collections::BTreeSet;
+
+use crate::license::spdx::canonical_case;
+
+/// Tokens produced by [`tokenize_spdx`].
+#[derive(Debug, PartialEq, Clone)]
+pub enum Token {
+    /// A license or exception identifier (e.g. `MIT`, `Classpath-exception-2.0`).
+    Id(String),
+    And,
+    Or,
+    With,
+    LParen,
+    RParen,
+}
+
+/// Tokenize an SPDX license expression into a flat [`Vec<Token>`].
+pub fn tokenize_spdx(expr: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = expr.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        if c == '(' {
+            tokens.push(Token::LParen);
+            chars.next();
+        } else if c == ')' {
+            tokens.push(Token::RParen);
+            chars.next();
+        } else {
+            let mut s = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '(' || c == ')' {
+                    break;
+                }
+                s.push(c);
+                chars.next();
+            }
+            let token = match s.as_str() {
+                "AND" => Token::And,
+                "OR" => Token::Or,
+                "WITH" => Token::With,
+                _ => Token::Id(s),
+            };
+            tokens.push(token);
+        }
+    }
+    tokens
+}
+
+/// Minimal SPDX expression AST used for canonicalization.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Expr {
+    /// A single license id with an optional `WITH` exception.
+    License {
+        id: String,
+        exception: Option<String>,
+    },
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+}
+
+/// Recursive descent parser producing an [`Expr`] (same grammar as the policy evaluator).
+struct AstParser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl AstParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn consume(&mut self) -> Option<Token> {
+        let t = self.tokens.get(self.pos).cloned();
+        if t.is_some() {
+            self.pos += 1;
+        }
+        t
+    }
+
+    fn parse_or(&mut self) -> Option<Expr> {
+        let mut terms = vec![self.parse_and()?];
+        while matches!(self.peek(), Some(Token::Or)) {
+            self.consume();
+            terms.push(self.parse_and()?);
+        }
+        Some(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            Expr::Or(terms)
+        })
+    }
+
+    fn parse_and(&mut self) -> Option<Expr> {
+        let mut terms = vec![self.parse_atom()?];
+        while matches!(self.peek(), Some(Token::And)) {
+            self.consume();
+            terms.push(self.parse_atom()?);
+        }
+        Some(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            Expr::And(terms)
+        })
+    }
+
+    fn parse_atom(&mut self) -> Option<Expr> {
+        match self.consume()? {
+            Token::LParen => {
+                let inner = self.parse_or()?;
+                match self.consume()? {
+                    Token::RParen => Some(inner),
+                    _ => None,
+                }
+            }
+            Token::Id(id) => {
+                let exception = if matches!(self.peek(), Some(Token::With)) {
+                    self.consume();
+                    match self.consume()? {
+                        Token::Id(exc) => Some(exc),
+                        _ => return None,
+                    }
+                } else {
+                    None
+                };
+                // License ids are case-insensitive; exception ids are kept as written
+                Some(Expr::License {
+                    id: canonical_case(&id),
+                    exception,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Flatten nested same-operator nodes, dedupe operands, and sort `OR` operands.
+fn simplify(expr: Expr) -> Expr {
+    match expr {
+        Expr::License { .. } => expr,
+        Expr::And(terms) => {
+            let mut out: Vec<Expr> = Vec::new();
+            for term in terms.into_iter().map(simplify) {
+                let flat = match term {
+                    Expr::And(inner) => inner,
+                    other => vec![other],
+                };
+                for t in flat {
+                    if !out.contains(&t) {
+                        out.push(t);
+                    }
+                }
+            }
+            if out.len() == 1 {
+                out.remove(0)
+            } else {
+                Expr::And(out)
+            }
+        }
+        Expr::Or(terms) => {
+            let mut out: BTreeSet<Expr> = BTreeSet::new();
+            for term in terms.into_iter().map(simplify) {
+                match term {
+                    Expr::Or(inner) => out.extend(inner),
+                    other => {
+                        out.insert(other);
+                    }
+                }
+            }
+            let mut out: Vec<Expr> = out.into_iter().collect();
+            if out.len() == 1 {
+                out.remove(0)
+            } else {
+                Expr::Or(out)
+            }
+        }
+    }
+}
+
+fn render(expr: &Expr) -> String {
+    match expr {
+        Expr::License {
+            id,
+            exception: Some(exc),
+        } => format!("{} WITH {}", id, exc),
+        Expr::License {
+            id,
+            exception: None,
+        } => id.clone(),
+        // AND binds tighter than OR, so OR operands never need parentheses
+        Expr::Or(terms) => terms.iter().map(render).collect::<Vec<_>>().join(" OR "),
+        Expr::And(terms) => terms
+            .iter()
+            .map(|t| match t {
+                Expr::Or(_) => format!("({})", render(t)),
+                _ => render(t),
+            })
+            .collect::<Vec<_>>()
+            .join(" AND "),
+    }
+}
+
+/// Canonicalize an SPDX license expression for stable, minimal output.
+///
+/// Identical operands are deduplicated, redundant parentheses are dropped and
+/// `OR` operands are sorted (e.g. `(MIT OR Apache-2.0 OR MIT)` → `Apache-2.0 OR MIT`).
+/// Strings that are not well-formed SPDX expressions (such as free-text license
+/// names) are returned trimmed but otherwise unchanged.
+pub fn canonicalize(expr: &str) -> String {
+    let tokens = tokenize_spdx(expr);
+    let mut parser = AstParser { tokens, pos: 0 };
+    match parser.parse_or() {
+        Some(ast) if parser.pos == parser.tokens.len() => render(&simplify(ast)),
+        _ => expr.trim().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dedupe_identical_terms() {
+        assert_eq!(canonicalize("MIT OR MIT"), "MIT");
+        assert_eq!(canonicalize("MIT AND MIT"), "MIT");
+    }
+
+    #[test]
+    fn test_license_ids_get_canonical_case() {
+        assert_eq!(canonicalize("mit OR apache-2.0"), "Apache-2.0 OR MIT");
+        assert_eq!(
+            canonicalize("gpl-2.0 WITH Classpath-exception-2.0"),
+            "GPL-2.0 WITH Classpath-exception-2.0"
+        );
+    }
+
+    #[test]
+    fn test_drop_redundant_parens() {
+        assert_eq!(canonicalize("(MIT)"), "MIT");
+        assert_eq!(canonicalize("((MIT OR Apache-2.0))"), "Apache-2.0 OR MIT");
+        assert_eq!(
+            canonicalize("(MIT AND BSD-3-Clause) OR ISC"),
+            "ISC OR MIT AND BSD-3-Clause"
+        );
+    }
+
+    #[test]
+    fn test_sort_or_operands() {
+        assert_eq!(canonicalize("B OR A"), "A OR B");
+        assert_eq!(
+            canonicalize("MIT OR (Apache-2.0 OR MIT)"),
+            "Apache-2.0 OR MIT"
+        );
+    }
+
+    #[test]
+    fn test_keeps_required_parens_and_exceptions() {
+        assert_eq!(
+            canonicalize("(MIT OR Apache-2.0) AND BSD-3-Clause"),
+            "(Apache-2.0 OR MIT) AND BSD-3-Clause"
+        );
+        assert_eq!(
+            canonicalize("GPL-2.0 WITH Classpath-exception-2.0"),
+            "GPL-2.0 WITH Classpath-exception-2.0"
+        );
+    }
+
+    #[test]
+    fn test_free_text_unchanged() {
+        assert_eq!(
+            canonicalize(" Apache License, Version 2.0 "),
+            "Apache License, Version 2.0"
+        );
+        assert_eq!(canonicalize("MIT OR"), "MIT OR");
+    }
+}
@@ -4,6 +4,16 @@
 //!   and normalizes common non-SPDX strings.
 //! - [`classifier`] — entry point that handles raw license strings including
 //!   SPDX OR/AND expressions and proprietary keywords.
+//! - [`expression`] — SPDX expression tokenizer and canonicalizer.
+//! - [`obligations`] — obligation tags (attribution, source disclosure, …) per license.
+//! - [`text`] — SPDX detection from full license texts.
+//! - [`detector`] — the same detection for `LICENSE` / `COPYING` files in a package directory.
 
 pub mod classifier;
+pub mod detector;
+pub mod expression;
+pub mod obligations;
 pub mod spdx;
+pub mod text;
+
+pub use text::detect_from_text;
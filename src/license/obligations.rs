@@ -0,0 +1,185 @@
+//! What a license obliges a distributor to do, as short tags for reports
+//! ("attribution required", "disclose source on distribution", …).
+//!
+//! This is guidance, not legal advice: the table covers common licenses and
+//! falls back to the typical obligations of the license's risk bucket.
+
+use serde::Serialize;
+
+use crate::license::expression::{tokenize_spdx, Token};
+use crate::license::spdx::{classify_spdx_id, normalize};
+use crate::models::LicenseRisk;
+
+/// One obligation tag, serialized in kebab-case (e.g. `"disclose-source"`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Obligation {
+    /// Keep the copyright and license notices.
+    Attribution,
+    /// Mark files you modified.
+    StateChanges,
+    /// Publish your changes to the licensed files themselves (file / library-level copyleft).
+    DiscloseChanges,
+    /// Provide the complete corresponding source of the combined work when distributing it.
+    DiscloseSource,
+    /// Also provide source to users interacting with it over a network.
+    NetworkDisclosure,
+    /// Commercial use is not permitted.
+    NoCommercialUse,
+    /// Use requires a separate agreement with the licensor.
+    LicenseAgreement,
+}
+
+impl Obligation {
+    pub fn label(self) -> &'static str {
+        match self {
+            Obligation::Attribution => "attribution required",
+            Obligation::StateChanges => "state changes",
+            Obligation::DiscloseChanges => "disclose changes to the library",
+            Obligation::DiscloseSource => "disclose source on distribution",
+            Obligation::NetworkDisclosure => "disclose source for network use",
+            Obligation::NoCommercialUse => "no commercial use",
+            Obligation::LicenseAgreement => "license agreement required",
+        }
+    }
+}
+
+impl std::fmt::Display for Obligation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// Typical obligations of a risk bucket; used for the PDF risk summary and for
+/// licenses without a specific entry.
+pub fn for_risk(risk: &LicenseRisk) -> &'static [Obligation] {
+    use Obligation::*;
+    match risk {
+        LicenseRisk::Permissive => &[Attribution],
+        LicenseRisk::WeakCopyleft => &[Attribution, DiscloseChanges],
+        LicenseRisk::StrongCopyleft => &[Attribution, StateChanges, DiscloseSource],
+        LicenseRisk::Proprietary => &[LicenseAgreement],
+        LicenseRisk::Unknown => &[],
+    }
+}
+
+/// Obligations of a single SPDX identifier, `None` when the license is unknown.
+fn for_id(id: &str) -> Option<Vec<Obligation>> {
+    use Obligation::*;
+    let specific: &[Obligation] = match id {
+        "CC0-1.0" | "Unlicense" | "0BSD" | "MIT-0" | "WTFPL" => &[],
+        "Apache-2.0" => &[Attribution, StateChanges],
+        "AGPL-3.0" | "AGPL-3.0-only" | "AGPL-3.0-or-later" => {
+            &[Attribution, StateChanges, DiscloseSource, NetworkDisclosure]
+        }
+        _ if id.starts_with("CC-BY-NC") => &[Attribution, NoCommercialUse],
+        _ => match classify_spdx_id(id) {
+            LicenseRisk::Unknown => return None,
+            risk => for_risk(&risk),
+        },
+    };
+    Some(specific.to_vec())
+}
+
+/// Obligations of a license string. `AND` combines every operand's obligations;
+/// for `OR` the licensee may pick, so the known alternative with the fewest
+/// obligations applies. `WITH` exceptions are ignored; unknown licenses have none.
+pub fn obligations(license: &str) -> Vec<Obligation> {
+    let tokens = tokenize_spdx(&normalize(license).replace('/', " OR "));
+    let mut all = parse_or(&mut tokens.iter().peekable()).unwrap_or_default();
+    all.sort();
+    all.dedup();
+    all
+}
+
+type Tokens<'a> = std::iter::Peekable<std::slice::Iter<'a, Token>>;
+
+fn parse_or(tokens: &mut Tokens<'_>) -> Option<Vec<Obligation>> {
+    let mut best = parse_and(tokens);
+    while tokens.next_if_eq(&&Token::Or).is_some() {
+        let alternative = parse_and(tokens);
+        best = match (best, alternative) {
+            (Some(a), Some(b)) if distinct(&b) < distinct(&a) => Some(b),
+            (None, b) => b,
+            (a, _) => a,
+        };
+    }
+    best
+}
+
+fn parse_and(tokens: &mut Tokens<'_>) -> Option<Vec<Obligation>> {
+    let mut all = parse_atom(tokens);
+    while tokens.next_if_eq(&&Token::And).is_some() {
+        all = match (all, parse_atom(tokens)) {
+            (Some(mut a), Some(b)) => {
+                a.extend(b);
+                Some(a)
+            }
+            (a, b) => a.or(b),
+        };
+    }
+    all
+}
+
+fn parse_atom(tokens: &mut Tokens<'_>) -> Option<Vec<Obligation>> {
+    match tokens.next() {
+        Some(Token::LParen) => {
+            let inner = parse_or(tokens);
+            tokens.next_if_eq(&&Token::RParen);
+            inner
+        }
+        Some(Token::Id(id)) => {
+            if tokens.next_if_eq(&&Token::With).is_some() {
+                tokens.next();
+            }
+            for_id(id)
+        }
+        _ => None,
+    }
+}
+
+/// Number of different obligations in `list`.
+fn distinct(list: &[Obligation]) -> usize {
+    let mut list = list.to_vec();
+    list.sort();
+    list.dedup();
+    list.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Obligation::*;
+
+    #[test]
+    fn test_obligations_by_license() {
+        assert_eq!(obligations("MIT"), [Attribution]);
+        assert!(obligations("GPL-3.0").contains(&DiscloseSource));
+        assert!(obligations("GPL-3.0-or-later").contains(&DiscloseSource));
+        assert!(obligations("AGPL-3.0").contains(&NetworkDisclosure));
+        assert_eq!(obligations("LGPL-2.1"), [Attribution, DiscloseChanges]);
+        assert!(obligations("CC0-1.0").is_empty());
+        assert!(obligations("CC-BY-NC-4.0").contains(&NoCommercialUse));
+    }
+
+    #[test]
+    fn test_obligations_of_expressions() {
+        // OR: the least demanding alternative
+        assert_eq!(obligations("MIT OR GPL-3.0"), [Attribution]);
+        // AND: everything applies
+        assert_eq!(
+            obligations("MIT AND GPL-3.0"),
+            [Attribution, StateChanges, DiscloseSource]
+        );
+        assert_eq!(
+            obligations("(MIT OR Apache-2.0) AND LGPL-2.1"),
+            [Attribution, DiscloseChanges]
+        );
+        assert!(obligations("unknown").is_empty());
+        // An unknown alternative is not the least demanding one
+        assert_eq!(
+            obligations("Apache-2.0 OR LicenseRef-Custom"),
+            [Attribution, StateChanges]
+        );
+    }
+}
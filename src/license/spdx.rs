@@ -1,65 +1,210 @@
 use crate::models::LicenseRisk;
 
-/// Classify a single canonical SPDX identifier into a risk level.
+/// Every SPDX identifier with a known risk level, grouped by level (listed by
+/// `--list-licenses`).
+pub const KNOWN_LICENSES: &[(LicenseRisk, &[&str])] = &[
+    (
+        LicenseRisk::Permissive,
+        &[
+            "MIT",
+            "Apache-2.0",
+            "BSD-2-Clause",
+            "BSD-3-Clause",
+            "BSD-4-Clause",
+            "ISC",
+            "0BSD",
+            "Unlicense",
+            "Zlib",
+            "CC0-1.0",
+            "WTFPL",
+            "CC-BY-4.0",
+            "CC-BY-3.0",
+            "PSF-2.0",
+            "Python-2.0",
+            "MIT-0",
+            "BlueOak-1.0.0",
+            "Artistic-2.0",
+            // Font and data licenses bundled by asset packages (attribution required)
+            "OFL-1.0",
+            "OFL-1.1",
+            "OFL-1.1-RFN",
+            "OFL-1.1-no-RFN",
+            "CC-BY-2.0",
+            "CC-BY-2.5",
+        ],
+    ),
+    (
+        LicenseRisk::WeakCopyleft,
+        &[
+            "LGPL-2.0",
+            "LGPL-2.0-only",
+            "LGPL-2.0-or-later",
+            "LGPL-2.1",
+            "LGPL-2.1-only",
+            "LGPL-2.1-or-later",
+            "LGPL-3.0",
+            "LGPL-3.0-only",
+            "LGPL-3.0-or-later",
+            "MPL-2.0",
+            "EUPL-1.2",
+            "CDDL-1.0",
+            "EPL-1.0",
+            "EPL-2.0",
+            "APSL-2.0",
+            "OSL-3.0",
+            // Share-alike applies to the asset itself
+            "CC-BY-SA-2.0",
+            "CC-BY-SA-2.5",
+            "CC-BY-SA-3.0",
+            "CC-BY-SA-4.0",
+        ],
+    ),
+    (
+        LicenseRisk::StrongCopyleft,
+        &[
+            "GPL-2.0",
+            "GPL-2.0-only",
+            "GPL-2.0-or-later",
+            "GPL-3.0",
+            "GPL-3.0-only",
+            "GPL-3.0-or-later",
+            "AGPL-3.0",
+            "AGPL-3.0-only",
+            "AGPL-3.0-or-later",
+            "EUPL-1.1",
+        ],
+    ),
+    (
+        // No-derivatives / non-commercial asset licenses — usage needs explicit permission
+        LicenseRisk::Proprietary,
+        &[
+            "CC-BY-ND-2.0",
+            "CC-BY-ND-2.5",
+            "CC-BY-ND-3.0",
+            "CC-BY-ND-4.0",
+            "CC-BY-NC-2.0",
+            "CC-BY-NC-2.5",
+            "CC-BY-NC-3.0",
+            "CC-BY-NC-4.0",
+            "CC-BY-NC-SA-2.0",
+            "CC-BY-NC-SA-2.5",
+            "CC-BY-NC-SA-3.0",
+            "CC-BY-NC-SA-4.0",
+            "CC-BY-NC-ND-2.0",
+            "CC-BY-NC-ND-2.5",
+            "CC-BY-NC-ND-3.0",
+            "CC-BY-NC-ND-4.0",
+        ],
+    ),
+];
+
+/// Classify a single SPDX identifier into a risk level (see [`KNOWN_LICENSES`]),
+/// trying its [`id_variants`] in turn so `GPL-3.0+` classifies like `GPL-3.0`.
 pub fn classify_spdx_id(id: &str) -> LicenseRisk {
-    match id.trim() {
-        // Permissive
-        "MIT"
-        | "Apache-2.0"
-        | "BSD-2-Clause"
-        | "BSD-3-Clause"
-        | "BSD-4-Clause"
-        | "ISC"
-        | "0BSD"
-        | "Unlicense"
-        | "Zlib"
-        | "CC0-1.0"
-        | "WTFPL"
-        | "CC-BY-4.0"
-        | "CC-BY-3.0"
-        | "PSF-2.0"
-        | "Python-2.0"
-        | "MIT-0"
-        | "BlueOak-1.0.0"
-        | "Artistic-2.0" => LicenseRisk::Permissive,
-
-        // Weak copyleft
-        "LGPL-2.0"
-        | "LGPL-2.0-only"
-        | "LGPL-2.0-or-later"
-        | "LGPL-2.1"
-        | "LGPL-2.1-only"
-        | "LGPL-2.1-or-later"
-        | "LGPL-3.0"
-        | "LGPL-3.0-only"
-        | "LGPL-3.0-or-later"
-        | "MPL-2.0"
-        | "EUPL-1.2"
-        | "CDDL-1.0"
-        | "EPL-1.0"
-        | "EPL-2.0"
-        | "APSL-2.0"
-        | "OSL-3.0" => LicenseRisk::WeakCopyleft,
-
-        // Strong copyleft
-        "GPL-2.0"
-        | "GPL-2.0-only"
-        | "GPL-2.0-or-later"
-        | "GPL-3.0"
-        | "GPL-3.0-only"
-        | "GPL-3.0-or-later"
-        | "AGPL-3.0"
-        | "AGPL-3.0-only"
-        | "AGPL-3.0-or-later"
-        | "EUPL-1.1" => LicenseRisk::StrongCopyleft,
-
-        _ => LicenseRisk::Unknown,
+    id_variants(id)
+        .iter()
+        .find_map(|variant| {
+            KNOWN_LICENSES
+                .iter()
+                .find(|(_, ids)| ids.contains(&variant.as_str()))
+        })
+        .map_or(LicenseRisk::Unknown, |(risk, _)| risk.clone())
+}
+
+/// GNU license versions whose bare id (`GPL-3.0`) is deprecated in favor of
+/// explicit `-only` / `-or-later` forms.
+const GNU_VERSIONED_IDS: &[&str] = &[
+    "GPL-1.0", "GPL-2.0", "GPL-3.0", "LGPL-2.0", "LGPL-2.1", "LGPL-3.0", "AGPL-1.0", "AGPL-3.0",
+    "GFDL-1.1", "GFDL-1.2", "GFDL-1.3",
+];
+
+/// The official casing of a known license id (`apache-2.0` → `Apache-2.0`,
+/// `gpl-3.0+` → `GPL-3.0+`); other ids are returned trimmed but unchanged.
+///
+/// SPDX license ids match case-insensitively. Exception ids after `WITH` are
+/// never passed here.
+pub fn canonical_case(id: &str) -> String {
+    let id = id.trim();
+    let (base, plus) = match id.strip_suffix('+') {
+        Some(base) => (base, "+"),
+        None => (id, ""),
+    };
+    KNOWN_LICENSES
+        .iter()
+        .flat_map(|(_, ids)| ids.iter())
+        .chain(GNU_VERSIONED_IDS)
+        .find(|known| known.eq_ignore_ascii_case(base))
+        .map_or_else(|| id.to_string(), |known| format!("{}{}", known, plus))
+}
+
+/// Spellings of one SPDX id that a policy entry or the risk table may use, most
+/// specific first:
+///
+/// - `GPL-3.0+` → `GPL-3.0+`, `GPL-3.0-or-later`, `GPL-3.0` (and `MPL-1.1+` →
+///   `MPL-1.1+`, `MPL-1.1`)
+/// - `GPL-3.0-or-later` / `GPL-3.0-only` → itself, then the bare `GPL-3.0`
+/// - the deprecated bare `GPL-3.0` → itself, then `GPL-3.0-only`, its meaning
+pub fn id_variants(id: &str) -> Vec<String> {
+    let id = canonical_case(id);
+    let id = id.as_str();
+    let mut variants = vec![id.to_string()];
+    if let Some(base) = id.strip_suffix('+') {
+        if GNU_VERSIONED_IDS.contains(&base) {
+            variants.push(format!("{}-or-later", base));
+        }
+        variants.push(base.to_string());
+    } else if let Some(base) = id
+        .strip_suffix("-or-later")
+        .or_else(|| id.strip_suffix("-only"))
+        .filter(|base| GNU_VERSIONED_IDS.contains(base))
+    {
+        variants.push(base.to_string());
+    } else if GNU_VERSIONED_IDS.contains(&id) {
+        variants.push(format!("{}-only", id));
     }
+    variants
 }
 
-/// Normalize common non-SPDX strings to their SPDX equivalents.
+/// Best-fit SPDX id for a fuzzy license descriptor ("BSD-like", "zlib/libpng"),
+/// matched case-insensitively.
+///
+/// The choices are conservative: "BSD-like" becomes `BSD-3-Clause` (the variant
+/// with the most conditions still in common use) and "Apache-style" becomes
+/// `Apache-2.0` (notice and change-marking obligations, unlike MIT). A config
+/// `[normalize]` table can map the same strings differently.
+pub fn fuzzy(raw: &str) -> Option<&'static str> {
+    let lower = raw.trim().to_lowercase();
+    let id = match lower.trim_end_matches(" license") {
+        "zlib/libpng" | "zlib/png" | "zlib-libpng" | "zlib-like" | "zlib-style" => "Zlib",
+        "bsd-like" | "bsd-style" | "bsd like" | "bsd style" => "BSD-3-Clause",
+        "mit-like" | "mit-style" | "mit like" | "mit style" => "MIT",
+        "apache-like" | "apache-style" | "apache like" | "apache style" => "Apache-2.0",
+        "isc-like" | "isc-style" => "ISC",
+        _ => return None,
+    };
+    Some(id)
+}
+
+/// Normalize common non-SPDX strings (and [`fuzzy`] descriptors) to their SPDX
+/// equivalents.
 pub fn normalize(raw: &str) -> String {
     let trimmed = raw.trim();
+    if let Some(id) = fuzzy(trimmed) {
+        return id.to_string();
+    }
+    if !trimmed.contains(char::is_whitespace) {
+        let id = canonical_case(trimmed);
+        // `GPL-3.0+` is the deprecated spelling of `GPL-3.0-or-later`
+        if let Some(base) = id
+            .strip_suffix('+')
+            .filter(|base| GNU_VERSIONED_IDS.contains(base))
+        {
+            return format!("{}-or-later", base);
+        }
+        if id != trimmed {
+            return id;
+        }
+    }
     match trimmed {
         "Apache 2.0" | "Apache License 2.0" | "Apache License, Version 2.0" => {
             "Apache-2.0".to_string()
@@ -76,10 +221,20 @@ pub fn normalize(raw: &str) -> String {
         }
         "GNU LGPL v2.1" | "LGPL v2.1" | "LGPLv2.1" => "LGPL-2.1".to_string(),
         "GNU LGPL v3" | "LGPL v3" | "LGPLv3" => "LGPL-3.0".to_string(),
+        // Legacy opam spellings of the LGPL with the OCaml static-linking exception
+        "LGPL-2.1-with-OCaml-exception" | "LGPL-2.1 with OCaml linking exception" => {
+            "LGPL-2.1 WITH OCaml-LGPL-linking-exception".to_string()
+        }
+        "LGPL-3.0-with-OCaml-exception" | "LGPL-3.0 with OCaml linking exception" => {
+            "LGPL-3.0 WITH OCaml-LGPL-linking-exception".to_string()
+        }
         "Mozilla Public License 2.0" | "MPL 2.0" | "MPLv2" => "MPL-2.0".to_string(),
         "ISC License" => "ISC".to_string(),
         "CC0" | "Public Domain" => "CC0-1.0".to_string(),
         "AGPL v3" | "AGPLv3" | "GNU AGPL v3" => "AGPL-3.0".to_string(),
+        "SIL OFL 1.1" | "SIL Open Font License 1.1" | "Open Font License 1.1" | "OFL" => {
+            "OFL-1.1".to_string()
+        }
         other => other.to_string(),
     }
 }
@@ -107,9 +262,73 @@ mod tests {
         assert_eq!(classify_spdx_id("MPL-2.0"), LicenseRisk::WeakCopyleft);
     }
 
+    #[test]
+    fn test_classify_font_and_data_licenses() {
+        assert_eq!(classify_spdx_id("OFL-1.1"), LicenseRisk::Permissive);
+        assert_eq!(classify_spdx_id("CC-BY-SA-4.0"), LicenseRisk::WeakCopyleft);
+        assert_eq!(classify_spdx_id("CC-BY-ND-4.0"), LicenseRisk::Proprietary);
+        assert_eq!(
+            classify_spdx_id("CC-BY-NC-SA-4.0"),
+            LicenseRisk::Proprietary
+        );
+        assert_eq!(normalize("SIL Open Font License 1.1"), "OFL-1.1");
+    }
+
+    #[test]
+    fn test_or_later_and_deprecated_spellings() {
+        assert_eq!(normalize("GPL-3.0+"), "GPL-3.0-or-later");
+        assert_eq!(normalize("LGPL-2.1+"), "LGPL-2.1-or-later");
+        assert_eq!(normalize("MPL-1.1+"), "MPL-1.1+");
+        for id in ["GPL-3.0", "GPL-3.0+", "GPL-3.0-only", "GPL-3.0-or-later"] {
+            assert_eq!(classify_spdx_id(id), LicenseRisk::StrongCopyleft, "{}", id);
+        }
+        assert_eq!(classify_spdx_id("LGPL-2.1+"), LicenseRisk::WeakCopyleft);
+        assert_eq!(classify_spdx_id("MPL-2.0+"), LicenseRisk::WeakCopyleft);
+        assert_eq!(
+            id_variants("GPL-2.0+"),
+            ["GPL-2.0+", "GPL-2.0-or-later", "GPL-2.0"]
+        );
+        assert_eq!(id_variants("GPL-2.0"), ["GPL-2.0", "GPL-2.0-only"]);
+        assert_eq!(id_variants("MIT"), ["MIT"]);
+    }
+
+    #[test]
+    fn test_ids_match_case_insensitively() {
+        assert_eq!(normalize("apache-2.0"), "Apache-2.0");
+        assert_eq!(normalize("mit"), "MIT");
+        assert_eq!(normalize("BSD-3-clause"), "BSD-3-Clause");
+        assert_eq!(normalize("gpl-3.0+"), "GPL-3.0-or-later");
+        assert_eq!(classify_spdx_id("apache-2.0"), LicenseRisk::Permissive);
+        assert_eq!(
+            classify_spdx_id("Lgpl-2.1-Or-Later"),
+            LicenseRisk::WeakCopyleft
+        );
+        assert_eq!(canonical_case("LicenseRef-acme"), "LicenseRef-acme");
+    }
+
     #[test]
     fn test_normalize() {
         assert_eq!(normalize("MIT License"), "MIT");
         assert_eq!(normalize("Apache License 2.0"), "Apache-2.0");
+        assert_eq!(
+            normalize("LGPL-2.1-with-OCaml-exception"),
+            "LGPL-2.1 WITH OCaml-LGPL-linking-exception"
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_descriptors() {
+        for (raw, id) in [
+            ("zlib/libpng", "Zlib"),
+            ("zlib/libpng License", "Zlib"),
+            ("BSD-like", "BSD-3-Clause"),
+            ("BSD style", "BSD-3-Clause"),
+            ("MIT-like", "MIT"),
+            ("Apache-style", "Apache-2.0"),
+        ] {
+            assert_eq!(normalize(raw), id, "{}", raw);
+            assert_eq!(classify_spdx_id(id), LicenseRisk::Permissive);
+        }
+        assert_eq!(fuzzy("GPL-like"), None);
     }
 }
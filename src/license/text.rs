@@ -0,0 +1,103 @@
+//! Best-effort SPDX detection from full license texts (e.g. a crate's `license-file`).
+
+/// Distinctive phrases for each license, checked in order. Every phrase of an
+/// entry must appear in the normalized text. More specific licenses come first
+/// (LGPL/AGPL before GPL, BSD-3 before BSD-2, ISC before 0BSD).
+const SIGNATURES: &[(&str, &[&str])] = &[
+    ("AGPL-3.0", &["gnu affero general public license", "version 3"]),
+    ("LGPL-3.0", &["gnu lesser general public license", "version 3"]),
+    ("LGPL-2.1", &["gnu lesser general public license", "version 2.1"]),
+    ("GPL-3.0", &["gnu general public license", "version 3"]),
+    ("GPL-2.0", &["gnu general public license", "version 2"]),
+    ("MPL-2.0", &["mozilla public license", "version 2.0"]),
+    ("Apache-2.0", &["apache license", "version 2.0"]),
+    ("BSL-1.0", &["boost software license - version 1.0"]),
+    (
+        "Unlicense",
+        &["this is free and unencumbered software released into the public domain"],
+    ),
+    ("CC0-1.0", &["cc0 1.0 universal"]),
+    (
+        "Zlib",
+        &["altered source versions must be plainly marked as such"],
+    ),
+    (
+        "BSD-3-Clause",
+        &[
+            "redistribution and use in source and binary forms",
+            "neither the name of",
+        ],
+    ),
+    (
+        "BSD-2-Clause",
+        &["redistribution and use in source and binary forms"],
+    ),
+    (
+        "MIT",
+        &["permission is hereby granted, free of charge, to any person obtaining a copy"],
+    ),
+    (
+        "ISC",
+        &[
+            "permission to use, copy, modify, and/or distribute this software for any purpose",
+            "provided that the above copyright notice and this permission notice appear in all copies",
+        ],
+    ),
+    (
+        "0BSD",
+        &["permission to use, copy, modify, and/or distribute this software for any purpose"],
+    ),
+];
+
+/// Infer an SPDX identifier from the full text of a license file.
+///
+/// Matching is case- and whitespace-insensitive, so hard-wrapped texts are
+/// recognized. Returns `None` when no known license text is found.
+pub fn detect_from_text(text: &str) -> Option<String> {
+    let normalized = text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+
+    SIGNATURES
+        .iter()
+        .find(|(_, phrases)| phrases.iter().all(|p| normalized.contains(p)))
+        .map(|(id, _)| id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIT: &str = "MIT License
+
+Copyright (c) 2024 Example
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the \"Software\"), to deal
+in the Software without restriction.";
+
+    #[test]
+    fn test_detect_wrapped_mit_text() {
+        assert_eq!(detect_from_text(MIT).as_deref(), Some("MIT"));
+    }
+
+    #[test]
+    fn test_detect_prefers_specific_gpl_family() {
+        let lgpl = "GNU LESSER GENERAL PUBLIC LICENSE\n   Version 3, 29 June 2007\n\
+                    This version of the GNU Lesser General Public License incorporates\n\
+                    the terms of version 3 of the GNU General Public License";
+        assert_eq!(detect_from_text(lgpl).as_deref(), Some("LGPL-3.0"));
+
+        let isc = "Permission to use, copy, modify, and/or distribute this software for any\n\
+                   purpose with or without fee is hereby granted, provided that the above\n\
+                   copyright notice and this permission notice appear in all copies.";
+        assert_eq!(detect_from_text(isc).as_deref(), Some("ISC"));
+    }
+
+    #[test]
+    fn test_detect_unknown_text() {
+        assert_eq!(detect_from_text("All rights reserved. Do not copy."), None);
+    }
+}
@@ -3,80 +3,383 @@
 //! # Flow
 //! 1. Parse CLI arguments ([`cli`]).
 //! 2. Load policy config ([`config::load_config`]).
+//!    Archives (`.zip` / `.tar.gz`) are extracted to a temporary directory ([`archive`]).
 //! 3. Auto-detect ecosystems ([`detector::detect_ecosystems`]).
 //! 4. Analyze each ecosystem's manifests ([`analyzer`]).
 //! 5. Optionally enrich from package registries (`--online`, [`registry`]).
 //! 6. Classify licenses and apply policy ([`license`], [`config::apply_policy`]).
 //! 7. Render the requested report ([`report`]).
-//! 8. Exit `0` (clean) or `1` (at least one [`models::PolicyVerdict::Error`]).
+//! 8. Exit `0` (clean) or `1` (at least one [`models::PolicyVerdict::Error`], or
+//!    [`models::PolicyVerdict::Review`] with `--fail-on review`).
 
 mod analyzer;
+mod archive;
+mod baseline;
 mod cli;
 mod config;
 mod detector;
+mod diagnostics;
+mod doctor;
+mod git;
+mod history;
+mod init;
 mod license;
 mod models;
 mod registry;
 mod report;
+mod version;
+mod webhook;
 
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::io::IsTerminal;
 use std::path::Path;
+use std::time::Duration;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
-use serde::Serialize;
 
 use analyzer::Analyzer;
 use cli::{Cli, ReportFormat};
-use config::{apply_policy, load_config};
+use config::{apply_package_policy, apply_policy, check_min_dependencies, load_config};
 use detector::detect_ecosystems;
-use license::classifier::classify;
-use models::{Ecosystem, LicenseSource, PolicyVerdict, ProjectScan};
+use diagnostics::Diagnostic;
+use license::expression::canonicalize;
+use models::{Ecosystem, LicenseRisk, LicenseSource, PolicyVerdict, ProjectScan};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    if cli.no_color {
+        colored::control::set_override(false);
+    }
+    diagnostics::set_json(cli.json_diagnostics);
+
+    let result = run(cli).await;
+    if let Err(e) = &result {
+        if diagnostics::is_json() {
+            Diagnostic::error(format!("{:#}", e)).emit();
+            std::process::exit(1);
+        }
+    }
+    result
+}
 
-    let path = cli
+async fn run(cli: Cli) -> Result<()> {
+    let paths: Vec<std::path::PathBuf> = cli
         .path
-        .canonicalize()
-        .unwrap_or_else(|_| cli.path.clone());
+        .iter()
+        .map(|p| p.canonicalize().unwrap_or_else(|_| p.clone()))
+        .collect();
+
+    if cli.list_licenses {
+        list_licenses(cli.report.contains(&ReportFormat::Json));
+        return Ok(());
+    }
+
+    if let Some(cli::Command::Doctor) = cli.command {
+        doctor::run(&paths[0], cli.config.as_deref()).await;
+        return Ok(());
+    }
+    if let Some(cli::Command::Init { force }) = cli.command {
+        let path = init::run(&paths[0], cli.config.as_deref(), force)?;
+        println!("Wrote {}", path.display());
+        return Ok(());
+    }
 
     let excluded: Vec<Ecosystem> = cli.exclude_lang.iter().map(Into::into).collect();
 
-    let report_format = match &cli.pdf {
-        Some(_) => ReportFormat::Pdf,
-        None => cli.report.clone(),
-    };
+    // `--pdf` implies `--report pdf` in place of the terminal report
+    let mut report_formats = cli.report.clone();
+    if cli.pdf.is_some() {
+        report_formats.retain(|f| *f != ReportFormat::Terminal);
+        if !report_formats.contains(&ReportFormat::Pdf) {
+            report_formats.push(ReportFormat::Pdf);
+        }
+    }
+    report::check_output(&report_formats, cli.output.as_deref())?;
     let pdf_path = cli
         .pdf
         .clone()
         .unwrap_or_else(|| std::path::PathBuf::from("license-report.pdf"));
 
-    let has_errors = if cli.recursive {
-        run_workspace(&cli, &path, &excluded, &report_format, &pdf_path).await?
-    } else {
-        run_single(&cli, &path, &excluded, &report_format, &pdf_path).await?
+    // Several positional paths are scanned as independent projects and rendered
+    // like a workspace (each path is itself discovered under `--recursive`).
+    // Archives are extracted and discovered like the single-path case; the temp
+    // dirs live in `extracted` until after the scan.
+    if paths.len() > 1 {
+        let mut extracted = Vec::new();
+        let mut project_paths = Vec::new();
+        for path in &paths {
+            check_scan_path(cli.recursive, path, &excluded)?;
+            match archive::extract(path)? {
+                Some(archive) => {
+                    project_paths.extend(discover_projects(&archive.root)?);
+                    extracted.push(archive);
+                }
+                None if cli.recursive => {
+                    project_paths.extend(detector::find_workspace_projects(path))
+                }
+                None => project_paths.push(path.clone()),
+            }
+        }
+        let result = run_workspace(
+            &cli,
+            None,
+            project_paths,
+            &excluded,
+            &report_formats,
+            &pdf_path,
+        )
+        .await;
+        drop(extracted);
+        return finish(&cli, result?);
+    }
+    let path = &paths[0];
+
+    // Archives are extracted to a temp dir and always scanned in workspace mode so
+    // nested manifests are discovered. The temp dir is removed when `extracted` drops,
+    // which must happen before `std::process::exit` in `finish`.
+    let extracted = archive::extract(path)?;
+    let root = match &extracted {
+        Some(archive) => Some(archive.root.as_path()),
+        None if cli.recursive => Some(path.as_path()),
+        None => None,
     };
+    let result = match root {
+        Some(root) => match discover_projects(root) {
+            Ok(project_paths) => {
+                run_workspace(
+                    &cli,
+                    Some(root),
+                    project_paths,
+                    &excluded,
+                    &report_formats,
+                    &pdf_path,
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        },
+        None => {
+            let project_paths = single_project_paths(&cli, path, &excluded);
+            if project_paths.len() == 1 {
+                run_single(
+                    &cli,
+                    &project_paths[0],
+                    &excluded,
+                    &report_formats,
+                    &pdf_path,
+                )
+                .await
+            } else {
+                run_workspace(
+                    &cli,
+                    Some(path),
+                    project_paths,
+                    &excluded,
+                    &report_formats,
+                    &pdf_path,
+                )
+                .await
+            }
+        }
+    };
+    drop(extracted);
+    finish(&cli, result?)
+}
+
+/// Reject a positional `PATH` that cannot be scanned: it must exist and be an
+/// archive or contain a (non-excluded) manifest, or sub-projects with `--recursive`.
+fn check_scan_path(recursive: bool, path: &Path, excluded: &[Ecosystem]) -> Result<()> {
+    if !path.exists() {
+        anyhow::bail!("Path does not exist: {}", path.display());
+    }
+    let scannable = archive::is_archive(path)
+        || detect_ecosystems(path)
+            .iter()
+            .any(|e| !excluded.contains(e))
+        || (recursive && !detector::find_workspace_projects(path).is_empty());
+    if !scannable {
+        anyhow::bail!("No supported project manifests found in {}", path.display());
+    }
+    Ok(())
+}
 
-    if has_errors {
-        std::process::exit(1);
+/// Exit non-zero when the scan failed; otherwise return normally.
+fn finish(cli: &Cli, outcome: ScanOutcome) -> Result<()> {
+    // `--baseline-update` is a snapshot operation and never fails the run
+    if outcome.is_failure() && cli.baseline_update.is_none() {
+        if cli.print_exit_reason {
+            Diagnostic::error(outcome.summary()).emit();
+        }
+        std::process::exit(i32::from(outcome.exit_code()));
     }
 
     Ok(())
 }
 
+// ── Exit outcome ──────────────────────────────────────────────────────────────
+
+/// Exit code per verdict (`--exit-code-error` / `--exit-code-review` /
+/// `--exit-code-warn`). A failing run exits with the highest code among the
+/// verdicts present; `pass` is always 0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ExitCodes {
+    error: u8,
+    review: u8,
+    warn: u8,
+}
+
+impl ExitCodes {
+    /// Unset codes default to 1 for verdicts at or above `--fail-on`, else 0
+    /// (always 0 with `--fail-on never`).
+    fn from_cli(cli: &Cli) -> Self {
+        let min = cli.fail_on.threshold().map(|t| t.severity());
+        let default =
+            |verdict: PolicyVerdict| u8::from(min.is_some_and(|m| verdict.severity() >= m));
+        ExitCodes {
+            error: cli
+                .exit_code_error
+                .unwrap_or_else(|| default(PolicyVerdict::Error)),
+            review: cli
+                .exit_code_review
+                .unwrap_or_else(|| default(PolicyVerdict::Review)),
+            warn: cli
+                .exit_code_warn
+                .unwrap_or_else(|| default(PolicyVerdict::Warn)),
+        }
+    }
+
+    fn for_verdict(&self, verdict: &PolicyVerdict) -> u8 {
+        match verdict {
+            PolicyVerdict::Pass => 0,
+            PolicyVerdict::Warn => self.warn,
+            PolicyVerdict::Review => self.review,
+            PolicyVerdict::Error => self.error,
+        }
+    }
+}
+
+/// Everything that makes a scan exit non-zero, collected for `--print-exit-reason`.
+#[derive(Debug, Default)]
+struct ScanOutcome {
+    /// Sanity-limit violations escalated to errors by `--strict`.
+    limit_failures: Vec<String>,
+    /// `name (license)` for every dependency with an error verdict.
+    violations: Vec<String>,
+    /// `name (license)` for every dependency awaiting review, with `--fail-on review`.
+    reviews: Vec<String>,
+    /// `name (license)` for every dependency with a warning, with `--exit-code-warn`.
+    warnings: Vec<String>,
+    /// Highest [`ExitCodes`] code among the recorded dependencies.
+    exit_code: u8,
+}
+
+impl ScanOutcome {
+    /// Record every dependency in `deps` whose verdict maps to a non-zero exit
+    /// code, optionally prefixed by project name.
+    ///
+    /// With `known` (from `--fail-on-new`), violations already present in the baseline are skipped.
+    fn add_violations(
+        &mut self,
+        deps: &[models::Dependency],
+        project: Option<&str>,
+        known: Option<&[models::Dependency]>,
+        exit_codes: &ExitCodes,
+    ) {
+        for dep in deps {
+            let code = exit_codes.for_verdict(&dep.verdict);
+            if code == 0 || known.is_some_and(|b| baseline::is_known_violation(b, dep)) {
+                continue;
+            }
+            self.exit_code = self.exit_code.max(code);
+            let license = dep
+                .license_spdx
+                .as_deref()
+                .or(dep.license_raw.as_deref())
+                .unwrap_or("unknown");
+            let name = match project {
+                Some(p) => format!("{}/{}", p, dep.name),
+                None => dep.name.clone(),
+            };
+            let entry = format!("{} ({})", name, license);
+            match dep.verdict {
+                PolicyVerdict::Error => self.violations.push(entry),
+                PolicyVerdict::Review => self.reviews.push(entry),
+                _ => self.warnings.push(entry),
+            }
+        }
+    }
+
+    fn is_failure(&self) -> bool {
+        self.exit_code() != 0
+    }
+
+    /// The process exit code: the highest recorded verdict code, at least 1 for
+    /// `--strict` limit failures.
+    fn exit_code(&self) -> u8 {
+        if self.limit_failures.is_empty() {
+            self.exit_code
+        } else {
+            self.exit_code.max(1)
+        }
+    }
+
+    /// One-line explanation of the failure, e.g.
+    /// `FAIL: 2 dependencies violate policy (error): foo (GPL-3.0), bar (AGPL-3.0)`.
+    fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.violations.is_empty() {
+            let n = self.violations.len();
+            parts.push(format!(
+                "{} dependenc{} violate{} policy (error): {}",
+                n,
+                if n == 1 { "y" } else { "ies" },
+                if n == 1 { "s" } else { "" },
+                self.violations.join(", ")
+            ));
+        }
+        if !self.reviews.is_empty() {
+            let n = self.reviews.len();
+            parts.push(format!(
+                "{} dependenc{} require{} review: {}",
+                n,
+                if n == 1 { "y" } else { "ies" },
+                if n == 1 { "s" } else { "" },
+                self.reviews.join(", ")
+            ));
+        }
+        if !self.warnings.is_empty() {
+            let n = self.warnings.len();
+            parts.push(format!(
+                "{} dependenc{} ha{} warnings: {}",
+                n,
+                if n == 1 { "y" } else { "ies" },
+                if n == 1 { "s" } else { "ve" },
+                self.warnings.join(", ")
+            ));
+        }
+        for msg in &self.limit_failures {
+            parts.push(format!("--strict: {}", msg));
+        }
+        format!("FAIL: {}", parts.join("; "))
+    }
+}
+
 // ── Single-project mode ───────────────────────────────────────────────────────
 
 async fn run_single(
     cli: &Cli,
     path: &Path,
     excluded: &[Ecosystem],
-    report_format: &ReportFormat,
+    report_formats: &[ReportFormat],
     pdf_path: &Path,
-) -> Result<bool> {
-    let config = load_config(path, cli.config.as_deref())?;
+) -> Result<ScanOutcome> {
+    let scanned_at = report::format_timestamp(chrono::Utc::now(), cli.local_time);
+    let config = load_profiled_config(path, cli.config.as_deref(), cli.profile.as_deref())?;
 
     let ecosystems: Vec<Ecosystem> = detect_ecosystems(path)
         .into_iter()
@@ -84,121 +387,324 @@ async fn run_single(
         .collect();
 
     if ecosystems.is_empty() {
-        eprintln!(
+        anyhow::bail!(
             "No supported project manifests found in {}",
             path.display()
         );
-        std::process::exit(1);
     }
 
-    let mut all_deps = scan_project(path, &config, excluded, cli.online, cli.quiet).await?;
+    let mut all_deps = analyze_project(
+        path,
+        excluded,
+        Exclusions::from_cli(cli),
+        cli.analysis_cache,
+        cli.quiet,
+    )?;
+    DependencyLimit::from_cli(cli).apply([(path, &mut all_deps)])?;
+    let online_stats = match OnlineOptions::from_cli(cli) {
+        Some(options) => Some(
+            enrich_online(
+                &mut all_deps,
+                &options,
+                &registry::HostLimits::new(cli.registry_concurrency_per_host, cli.jobs),
+                open_registry_cache(cli)?.as_ref(),
+                Progress::from_cli(cli),
+            )
+            .await?,
+        ),
+        None => None,
+    };
 
-    // Classify + apply policy
-    for dep in &mut all_deps {
-        let license = dep
-            .license_spdx
-            .as_deref()
-            .or(dep.license_raw.as_deref())
-            .unwrap_or("unknown");
-        dep.risk = classify(license);
-        dep.verdict = apply_policy(&config, Some(license));
-    }
+    let mut outcome = ScanOutcome::default();
 
-    match report_format {
-        ReportFormat::Terminal => {
-            report::terminal::render(&all_deps, path, cli.verbose, cli.quiet)?;
-        }
-        ReportFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(&all_deps)?);
-        }
-        ReportFormat::Pdf => {
-            report::pdf::render(&all_deps, path, pdf_path)?;
+    let min_deps = cli.min_deps.or(config.limits.min_dependencies);
+    if let Some(msg) = check_min_dependencies(min_deps, all_deps.len()) {
+        report_limit_violation(&msg, cli.strict);
+        if cli.strict {
+            outcome.limit_failures.push(msg);
         }
     }
 
-    Ok(all_deps.iter().any(|d| d.verdict == PolicyVerdict::Error))
+    evaluate_deps(&mut all_deps, &config);
+    print_online_stats(cli, online_stats.as_ref());
+
+    if cli.track_history {
+        history::track(&history::history_path(path)?, &mut all_deps)?;
+    }
+
+    let min_risk = cli.min_risk.as_ref().map(LicenseRisk::from);
+    let written = report::render(
+        report_formats,
+        &labeled(&all_deps, &cli.filter_label),
+        path,
+        &render_options(
+            cli,
+            min_risk.as_ref(),
+            pdf_path,
+            &scanned_at,
+            online_stats.as_ref(),
+        ),
+    )?;
+    print_written_reports(&written);
+
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string());
+    notify_webhook(cli, &name, all_deps.iter().map(|d| (None, d)), &scanned_at).await;
+
+    let all_refs: Vec<&models::Dependency> = all_deps.iter().collect();
+    let baseline = process_baseline(cli, &all_refs)?;
+    let known = baseline.as_deref().filter(|_| cli.fail_on_new);
+
+    outcome.add_violations(&all_deps, None, known, &ExitCodes::from_cli(cli));
+    Ok(outcome)
+}
+
+/// How many directory levels below a manifest-less `PATH` are searched.
+const NESTED_SEARCH_DEPTH: usize = 2;
+
+/// What to scan for a single `PATH` without `--recursive`: `path` itself when it
+/// has a manifest, otherwise the projects up to [`NESTED_SEARCH_DEPTH`] levels
+/// below it, each reported on stderr. Several nested projects are scanned like a
+/// workspace.
+fn single_project_paths(cli: &Cli, path: &Path, excluded: &[Ecosystem]) -> Vec<std::path::PathBuf> {
+    let root_detected = detect_ecosystems(path)
+        .iter()
+        .any(|e| !excluded.contains(e));
+    let nested = if root_detected {
+        Vec::new()
+    } else {
+        detector::find_nested_projects(path, NESTED_SEARCH_DEPTH)
+    };
+    // Without nested projects, `run_single` reports the missing manifest
+    if nested.is_empty() {
+        return vec![path.to_path_buf()];
+    }
+
+    if !cli.quiet {
+        for dir in &nested {
+            let ecosystems: Vec<String> = detect_ecosystems(dir)
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            Diagnostic::note(format!(
+                "no manifest in {}; found {} in {}",
+                path.display(),
+                if ecosystems.is_empty() {
+                    "a project".to_string()
+                } else {
+                    ecosystems.join(", ")
+                },
+                dir.strip_prefix(path).unwrap_or(dir).display()
+            ))
+            .file(dir)
+            .emit();
+        }
+    }
+    nested
 }
 
 // ── Workspace mode ────────────────────────────────────────────────────────────
 
-async fn run_workspace(
-    cli: &Cli,
-    root: &Path,
-    excluded: &[Ecosystem],
-    report_format: &ReportFormat,
-    pdf_path: &Path,
-) -> Result<bool> {
+/// Discover sub-projects under `root`, failing when there are none.
+fn discover_projects(root: &Path) -> Result<Vec<std::path::PathBuf>> {
     let project_paths = detector::find_workspace_projects(root);
-
     if project_paths.is_empty() {
-        eprintln!("No sub-projects found under {}", root.display());
-        std::process::exit(1);
+        anyhow::bail!("No sub-projects found under {}", root.display());
     }
+    Ok(project_paths)
+}
 
-    if !cli.quiet {
-        println!(
-            "\n {} v{}  —  workspace mode",
-            "license-checkr".bold(),
-            env!("CARGO_PKG_VERSION")
-        );
-        println!(
-            " Root:  {}\n Found: {} sub-project{}\n",
-            root.display(),
-            project_paths.len(),
-            if project_paths.len() == 1 { "" } else { "s" }
-        );
+/// `--since`: keep only the projects whose manifests or lockfiles changed since
+/// the git ref. Outside a git repository, or when `git diff` fails (e.g. an
+/// unknown ref), every project is scanned after a warning.
+fn changed_projects(cli: &Cli, project_paths: Vec<std::path::PathBuf>) -> Vec<std::path::PathBuf> {
+    let Some(since) = &cli.since else {
+        return project_paths;
+    };
+    match git::changed_files(&project_paths, since) {
+        Ok(changed) => {
+            let touched = detector::touched_projects(&project_paths, &changed);
+            if !cli.quiet {
+                Diagnostic::note(format!(
+                    "--since {}: {} of {} projects have changed manifests",
+                    since,
+                    touched.len(),
+                    project_paths.len()
+                ))
+                .emit();
+            }
+            touched
+        }
+        Err(e) => {
+            Diagnostic::warning(format!(
+                "--since {}: {:#}; scanning every project",
+                since, e
+            ))
+            .emit();
+            project_paths
+        }
     }
+}
 
+/// Scan each project concurrently, each with its own policy config.
+///
+/// All projects are analyzed before `--max-deps` is checked over their combined
+/// dependencies, so the cap never depends on which project finishes first; the
+/// `--online` lookups follow. Returns every project (including empty ones) with
+/// its optional sanity-limit warning, plus the lookup counters summed over all
+/// projects.
+async fn scan_projects(
+    cli: &Cli,
+    project_paths: Vec<std::path::PathBuf>,
+    excluded: &[Ecosystem],
+) -> Result<(
+    Vec<(ProjectScan, Option<String>)>,
+    Option<registry::OnlineStats>,
+)> {
     let tasks: Vec<_> = project_paths
         .into_iter()
         .map(|proj_path| {
             let excluded = excluded.to_vec();
-            let online = cli.online;
+            let exclusions = Exclusions::from_cli(cli);
+            let analysis_cache = cli.analysis_cache;
             let config_override = cli.config.clone();
+            let profile = cli.profile.clone();
 
-            tokio::spawn(async move {
+            tokio::task::spawn_blocking(move || {
                 let name = proj_path
                     .file_name()
                     .and_then(|n| n.to_str())
                     .unwrap_or("unknown")
                     .to_string();
 
-                let proj_config = load_config(&proj_path, config_override.as_deref())?;
+                let proj_config = load_profiled_config(
+                    &proj_path,
+                    config_override.as_deref(),
+                    profile.as_deref(),
+                )?;
                 // Always suppress inline prints — output is flushed in order after join_all.
-                let mut deps =
-                    scan_project(&proj_path, &proj_config, &excluded, online, true).await?;
-
-                for dep in &mut deps {
-                    let license = dep
-                        .license_spdx
-                        .as_deref()
-                        .or(dep.license_raw.as_deref())
-                        .unwrap_or("unknown");
-                    dep.risk = classify(license);
-                    dep.verdict = apply_policy(&proj_config, Some(license));
-                }
+                let deps =
+                    analyze_project(&proj_path, &excluded, exclusions, analysis_cache, true)?;
 
-                Ok::<ProjectScan, anyhow::Error>(ProjectScan {
-                    name,
-                    path: proj_path,
-                    deps,
-                })
+                Ok::<_, anyhow::Error>((
+                    ProjectScan {
+                        name,
+                        path: proj_path,
+                        deps,
+                    },
+                    proj_config,
+                ))
             })
         })
         .collect();
 
-    let mut projects: Vec<ProjectScan> = futures::future::join_all(tasks)
-        .await
+    let mut scanned = Vec::new();
+    for join_result in futures::future::join_all(tasks).await {
+        scanned.push(join_result.expect("project scan task panicked")?);
+    }
+
+    DependencyLimit::from_cli(cli).apply(
+        scanned
+            .iter_mut()
+            .map(|(project, _)| (project.path.as_path(), &mut project.deps)),
+    )?;
+
+    let mut total: Option<registry::OnlineStats> = None;
+    if let Some(online) = OnlineOptions::from_cli(cli) {
+        // Shared across projects so the per-host cap holds for the whole workspace
+        let limits = registry::HostLimits::new(cli.registry_concurrency_per_host, cli.jobs);
+        let registry_cache = open_registry_cache(cli)?;
+        let lookups = scanned
+            .iter_mut()
+            .filter(|(project, _)| !project.deps.is_empty())
+            .map(|(project, _)| {
+                enrich_online(
+                    &mut project.deps,
+                    &online,
+                    &limits,
+                    registry_cache.as_ref(),
+                    Progress::Off,
+                )
+            });
+        for stats in futures::future::try_join_all(lookups).await? {
+            total.get_or_insert_with(Default::default).merge(&stats);
+        }
+    }
+
+    let results = scanned
         .into_iter()
-        .map(|join_result| join_result.expect("project scan task panicked"))
-        .collect::<Result<Vec<_>>>()?;
+        .map(|(mut project, proj_config)| {
+            evaluate_deps(&mut project.deps, &proj_config);
+            let min_deps = cli.min_deps.or(proj_config.limits.min_dependencies);
+            let limit_warning = check_min_dependencies(min_deps, project.deps.len())
+                .map(|msg| format!("{}: {}", project.name, msg));
+            (project, limit_warning)
+        })
+        .collect();
+    Ok((results, total))
+}
+
+/// Scan and render several projects together.
+///
+/// `root` is the directory they were discovered under, or `None` when the
+/// project paths were passed explicitly on the command line.
+async fn run_workspace(
+    cli: &Cli,
+    root: Option<&Path>,
+    project_paths: Vec<std::path::PathBuf>,
+    excluded: &[Ecosystem],
+    report_formats: &[ReportFormat],
+    pdf_path: &Path,
+) -> Result<ScanOutcome> {
+    let scanned_at = report::format_timestamp(chrono::Utc::now(), cli.local_time);
+    let project_paths = changed_projects(cli, project_paths);
+    if !cli.quiet {
+        println!(
+            "\n {} v{}  —  workspace mode",
+            "license-checkr".bold(),
+            env!("CARGO_PKG_VERSION")
+        );
+        let plural = if project_paths.len() == 1 { "" } else { "s" };
+        match root {
+            Some(root) => println!(
+                " Root:  {}\n Found: {} sub-project{}\n",
+                root.display(),
+                project_paths.len(),
+                plural
+            ),
+            None => println!(" Paths: {} project{}\n", project_paths.len(), plural),
+        }
+    }
+
+    let (results, online_stats) = scan_projects(cli, project_paths, excluded).await?;
+    print_online_stats(cli, online_stats.as_ref());
+
+    let mut outcome = ScanOutcome::default();
+    let mut projects: Vec<ProjectScan> = Vec::with_capacity(results.len());
+    for (project, limit_warning) in results {
+        if let Some(msg) = limit_warning {
+            report_limit_violation(&msg, cli.strict);
+            if cli.strict {
+                outcome.limit_failures.push(msg);
+            }
+        }
+        projects.push(project);
+    }
 
     // Drop projects with zero dependencies (empty / unsupported ecosystems)
     projects.retain(|p| !p.deps.is_empty());
 
     if projects.is_empty() {
-        eprintln!("No dependencies found in any sub-project.");
-        return Ok(false);
+        Diagnostic::info("No dependencies found in any sub-project.").emit();
+        return Ok(outcome);
+    }
+
+    if cli.track_history {
+        for project in &mut projects {
+            history::track(&history::history_path(&project.path)?, &mut project.deps)?;
+        }
     }
 
     // Print scan summaries in deterministic order now that all tasks have finished.
@@ -210,62 +716,434 @@ async fn run_workspace(
                 project.name.bold(),
                 project.path.display()
             );
-            // Group dep counts by ecosystem.
-            let mut eco_counts: std::collections::BTreeMap<String, usize> =
-                std::collections::BTreeMap::new();
-            for dep in &project.deps {
-                *eco_counts.entry(dep.ecosystem.to_string()).or_insert(0) += 1;
-            }
-            for (eco, count) in &eco_counts {
-                eprintln!("    {} {} {} dependencies", "·".dimmed(), eco, count);
+            for (eco, count) in &report::ecosystem_counts(&project.deps) {
+                print_dep_count(eco, *count);
             }
         }
         println!();
     }
 
-    match report_format {
-        ReportFormat::Terminal => {
-            report::terminal::render_workspace(&projects, cli.verbose, cli.quiet)?;
-        }
-        ReportFormat::Json => {
-            #[derive(Serialize)]
-            struct ProjectScanJson<'a> {
-                project: &'a str,
-                path: String,
-                dependencies: &'a [models::Dependency],
-            }
-            let out: Vec<ProjectScanJson<'_>> = projects
+    // `--filter-label` narrows the reports only; the webhook, baseline and exit
+    // code below still see every dependency
+    let rendered: Cow<[ProjectScan]> = if cli.filter_label.is_empty() {
+        Cow::Borrowed(&projects)
+    } else {
+        Cow::Owned(
+            projects
                 .iter()
-                .map(|p| ProjectScanJson {
-                    project: &p.name,
-                    path: p.path.display().to_string(),
-                    dependencies: &p.deps,
+                .map(|p| ProjectScan {
+                    deps: labeled(&p.deps, &cli.filter_label).into_owned(),
+                    ..p.clone()
                 })
-                .collect();
-            println!("{}", serde_json::to_string_pretty(&out)?);
-        }
-        ReportFormat::Pdf => {
-            report::pdf::render_workspace(&projects, pdf_path)?;
+                .filter(|p| !p.deps.is_empty())
+                .collect(),
+        )
+    };
+    let min_risk = cli.min_risk.as_ref().map(LicenseRisk::from);
+    let written = report::render_workspace(
+        report_formats,
+        &rendered,
+        root.unwrap_or(Path::new(".")),
+        &render_options(
+            cli,
+            min_risk.as_ref(),
+            pdf_path,
+            &scanned_at,
+            online_stats.as_ref(),
+        ),
+    )?;
+    print_written_reports(&written);
+
+    let name = match root {
+        Some(root) => root
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| root.display().to_string()),
+        None => "workspace".to_string(),
+    };
+    let pairs = projects
+        .iter()
+        .flat_map(|p| p.deps.iter().map(move |d| (Some(p.name.as_str()), d)));
+    notify_webhook(cli, &name, pairs, &scanned_at).await;
+
+    let all_refs: Vec<&models::Dependency> = projects.iter().flat_map(|p| &p.deps).collect();
+    let baseline = process_baseline(cli, &all_refs)?;
+    let known = baseline.as_deref().filter(|_| cli.fail_on_new);
+
+    let exit_codes = ExitCodes::from_cli(cli);
+    for project in &projects {
+        outcome.add_violations(&project.deps, Some(&project.name), known, &exit_codes);
+    }
+
+    Ok(outcome)
+}
+
+/// `--webhook`: best-effort notification — a failure is reported as a warning and
+/// never changes the exit code.
+async fn notify_webhook<'a>(
+    cli: &Cli,
+    project: &str,
+    deps: impl IntoIterator<Item = (Option<&'a str>, &'a models::Dependency)> + Clone,
+    scanned_at: &str,
+) {
+    let Some(url) = &cli.webhook else {
+        return;
+    };
+    let fail_on = cli.fail_on.threshold();
+    let payload = webhook::Payload::new(project, deps, scanned_at, fail_on.as_ref());
+    if !payload.should_send(cli.webhook_always) {
+        return;
+    }
+    if let Err(e) = webhook::send(url, &payload).await {
+        Diagnostic::warning(format!("{:#}", e)).emit();
+    }
+}
+
+fn render_options<'a>(
+    cli: &'a Cli,
+    min_risk: Option<&'a LicenseRisk>,
+    pdf_path: &'a Path,
+    scanned_at: &'a str,
+    online_stats: Option<&'a registry::OnlineStats>,
+) -> report::RenderOptions<'a> {
+    report::RenderOptions {
+        verbose: cli.verbose,
+        quiet: cli.quiet,
+        min_risk,
+        sort: cli.sort.as_ref(),
+        columns: &cli.columns,
+        group_by: cli.group_by.as_ref(),
+        pdf_path,
+        pdf_branding: report::pdf::Branding {
+            title: cli.pdf_title.as_deref(),
+            logo: cli.pdf_logo.as_deref(),
+        },
+        output_dir: cli.output_dir.as_deref(),
+        output: cli.output.as_deref(),
+        scanned_at,
+        online_stats,
+        summary_format: cli.summary_format.as_ref(),
+    }
+}
+
+/// The `--online` lookup cache, unless the scan is offline or `--no-cache` is set.
+fn open_registry_cache(cli: &Cli) -> Result<Option<registry::cache::RegistryCache>> {
+    if !cli.online || cli.no_cache {
+        return Ok(None);
+    }
+    let file = registry::cache::cache_path(cli.cache_dir.as_deref())?;
+    Ok(Some(registry::cache::RegistryCache::open(&file)))
+}
+
+/// `--list-licenses`: the known SPDX ids per risk level, as indented text or as
+/// [`known_licenses_json`].
+fn list_licenses(json: bool) {
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&known_licenses_json()).expect("JSON serialization")
+        );
+        return;
+    }
+    for (risk, ids) in license::spdx::KNOWN_LICENSES {
+        println!("{} ({})", risk.to_string().bold(), ids.len());
+        for id in *ids {
+            println!("  {}", id);
         }
     }
+}
 
-    let has_errors = projects
+/// `[{"risk": "Permissive", "licenses": ["MIT", …]}, …]`, one entry per risk level.
+fn known_licenses_json() -> serde_json::Value {
+    license::spdx::KNOWN_LICENSES
         .iter()
-        .flat_map(|p| &p.deps)
-        .any(|d| d.verdict == PolicyVerdict::Error);
+        .map(|(risk, ids)| serde_json::json!({ "risk": risk, "licenses": ids }))
+        .collect()
+}
+
+/// Under `--verbose`, summarize the `--online` lookups on stderr.
+fn print_online_stats(cli: &Cli, stats: Option<&registry::OnlineStats>) {
+    if let Some(stats) = stats.filter(|_| cli.verbose) {
+        Diagnostic::info(stats.to_string()).emit();
+    }
+}
+
+/// Print the `    · Rust 12 dependencies` progress line on stderr.
+fn print_dep_count(ecosystem: &dyn std::fmt::Display, count: usize) {
+    if diagnostics::is_json() {
+        Diagnostic::info(format!("{} {} dependencies", ecosystem, count)).emit();
+    } else {
+        eprintln!("    {} {} {} dependencies", "·".dimmed(), ecosystem, count);
+    }
+}
+
+/// List the files written by `--output-dir` or `--output` on stderr.
+fn print_written_reports(written: &[std::path::PathBuf]) {
+    if written.is_empty() {
+        return;
+    }
+    if diagnostics::is_json() {
+        for file in written {
+            Diagnostic::info("report written").file(file).emit();
+        }
+        return;
+    }
+    eprintln!("Reports written:");
+    for file in written {
+        eprintln!("  {}", file.display());
+    }
+}
+
+/// Print a sanity-limit violation to stderr — as an error under `--strict`, otherwise a warning.
+fn report_limit_violation(msg: &str, strict: bool) {
+    if strict {
+        Diagnostic::error(msg).emit();
+    } else {
+        Diagnostic::warning(msg).emit();
+    }
+}
+
+/// Load the policy config for `path` and merge `--profile` over it. An unknown
+/// profile is a warning, and the base policy applies.
+fn load_profiled_config(
+    path: &Path,
+    config_override: Option<&Path>,
+    profile: Option<&str>,
+) -> Result<config::Config> {
+    let mut config = load_config(path, config_override)?;
+    if let Some(name) = profile {
+        if !config.apply_profile(name) {
+            Diagnostic::warning(format!(
+                "profile '{}' not found in config for {}, using the base policy",
+                name,
+                path.display()
+            ))
+            .emit();
+        }
+    }
+    Ok(config)
+}
+
+// ── Baseline ──────────────────────────────────────────────────────────────────
+
+/// Compare against `--baseline` and write `--baseline-update`, if requested.
+///
+/// Returns the loaded baseline so callers can apply `--fail-on-new`.
+fn process_baseline(
+    cli: &Cli,
+    deps: &[&models::Dependency],
+) -> Result<Option<Vec<models::Dependency>>> {
+    let loaded = match &cli.baseline {
+        Some(path) => {
+            let previous = baseline::read(path)?;
+            let diff = baseline::diff(&previous, deps);
+            if diagnostics::is_json() {
+                // Counts line only: the grouped listing is colored, multi-line text
+                Diagnostic::info(report::diff::render_text(&diff, true).trim_end()).emit();
+            } else {
+                eprint!("{}", report::diff::render_text(&diff, cli.quiet));
+            }
+            if let Some(md_path) = &cli.baseline_markdown {
+                std::fs::write(md_path, report::diff::render_markdown(&diff)).with_context(
+                    || format!("Failed to write baseline Markdown to {}", md_path.display()),
+                )?;
+            }
+            Some(previous)
+        }
+        None => None,
+    };
+
+    if let Some(path) = &cli.baseline_update {
+        baseline::write(path, deps)?;
+        Diagnostic::info(format!("Baseline written to: {}", path.display()))
+            .file(path)
+            .emit();
+    }
 
-    Ok(has_errors)
+    Ok(loaded)
 }
 
 // ── Shared scan logic ─────────────────────────────────────────────────────────
 
-/// Detect ecosystems, analyze manifests, and optionally enrich online.
-/// Returns an empty `Vec` (not an error) when no ecosystems are detected.
-async fn scan_project(
+/// Canonicalize SPDX expressions, classify risk, and apply policy to each dependency.
+fn evaluate_deps(deps: &mut [models::Dependency], config: &config::Config) {
+    for dep in deps {
+        // `[normalize]` entries and fuzzy descriptors ("BSD-like") become SPDX ids.
+        // Config entries win over every built-in mapping, so they also match the
+        // raw string an analyzer may already have normalized.
+        let fuzzy = dep
+            .license_raw
+            .as_deref()
+            .and_then(|l| config.custom_license(l))
+            .or_else(|| {
+                dep.license_spdx
+                    .as_deref()
+                    .or(dep.license_raw.as_deref())
+                    .and_then(|l| config.normalized_license(l))
+            });
+        if fuzzy.is_some() {
+            dep.license_spdx = fuzzy;
+        }
+        dep.license_spdx = dep.license_spdx.as_deref().map(canonicalize);
+        let license = dep
+            .license_spdx
+            .as_deref()
+            .or(dep.license_raw.as_deref())
+            .unwrap_or("unknown");
+        dep.risk = config.classify(license);
+        dep.verdict = apply_package_policy(config, &dep.name, &dep.version)
+            .unwrap_or_else(|| apply_policy(config, Some(license)));
+        dep.labels = config.labels_for(&dep.name, &dep.version);
+    }
+}
+
+/// `--filter-label`: the dependencies to report, those carrying at least one of
+/// `labels` (all of them when none are given).
+fn labeled<'a>(deps: &'a [models::Dependency], labels: &[String]) -> Cow<'a, [models::Dependency]> {
+    if labels.is_empty() {
+        return Cow::Borrowed(deps);
+    }
+    Cow::Owned(
+        deps.iter()
+            .filter(|d| d.labels.iter().any(|l| labels.contains(l)))
+            .cloned()
+            .collect(),
+    )
+}
+
+/// Dependencies dropped before policy evaluation (`--exclude-optional`,
+/// `--exclude-dev`, `--ignore`, `--ignore-tree`).
+#[derive(Debug, Clone)]
+struct Exclusions {
+    optional: bool,
+    dev: bool,
+    /// `name` or `name@version` entries of packages removed on their own.
+    packages: Vec<String>,
+    /// Packages removed together with the dependencies only they pull in.
+    trees: Vec<String>,
+}
+
+impl Exclusions {
+    fn from_cli(cli: &Cli) -> Self {
+        Exclusions {
+            optional: cli.exclude_optional,
+            dev: cli.exclude_dev,
+            packages: cli.ignore.clone(),
+            trees: cli.ignore_tree.clone(),
+        }
+    }
+
+    /// Whether `dep` survives the optional / dev / `--ignore` exclusions.
+    fn keeps(&self, dep: &models::Dependency) -> bool {
+        !((self.optional && dep.optional)
+            || (self.dev && dep.dev)
+            || self.packages.iter().any(|entry| ignores(entry, dep)))
+    }
+
+    /// Drop excluded dependencies of the project at `path`. Ignored trees are
+    /// resolved against the project's lockfile graphs; without one, only the
+    /// named package is dropped.
+    fn apply(&self, path: &Path, deps: &mut Vec<models::Dependency>) -> Result<()> {
+        deps.retain(|d| self.keeps(d));
+        if self.trees.is_empty() {
+            return Ok(());
+        }
+
+        let graphs = analyzer::graph::load(path)?;
+        let mut subtrees: Vec<(&Ecosystem, HashSet<String>)> = Vec::new();
+        for (ecosystem, graph) in &graphs {
+            for ignored in &self.trees {
+                subtrees.push((ecosystem, graph.exclusive_subtree(ignored)));
+            }
+        }
+        deps.retain(|d| {
+            !(self.trees.contains(&d.name)
+                || subtrees
+                    .iter()
+                    .any(|(eco, names)| **eco == d.ecosystem && names.contains(&d.name)))
+        });
+        Ok(())
+    }
+}
+
+/// Match an `--ignore` entry: `name`, compared case-insensitively, optionally
+/// followed by `@version`. A leading `@` belongs to the name (scoped npm packages).
+fn ignores(entry: &str, dep: &models::Dependency) -> bool {
+    match entry.rfind('@').filter(|&i| i > 0) {
+        Some(i) => entry[..i].eq_ignore_ascii_case(&dep.name) && entry[i + 1..] == dep.version,
+        None => entry.eq_ignore_ascii_case(&dep.name),
+    }
+}
+
+/// `--max-deps`: cap on the dependencies found by the whole run (all projects of
+/// a workspace together), checked after analysis and before `--online` lookups.
+#[derive(Debug, Clone)]
+struct DependencyLimit {
+    max: Option<usize>,
+    /// `--truncate`: keep the first dependencies up to the cap instead of failing.
+    truncate: bool,
+}
+
+impl DependencyLimit {
+    fn from_cli(cli: &Cli) -> Self {
+        DependencyLimit {
+            max: cli.max_deps,
+            truncate: cli.truncate,
+        }
+    }
+
+    /// Count the dependencies of every project of the run, given by path, against
+    /// the cap: an error once it is exceeded, or a warning per project cut short
+    /// and the truncated lists with `--truncate` (earlier projects keep theirs).
+    fn apply<'a>(
+        &self,
+        projects: impl IntoIterator<Item = (&'a Path, &'a mut Vec<models::Dependency>)>,
+    ) -> Result<()> {
+        let Some(max) = self.max else {
+            return Ok(());
+        };
+        let mut projects: Vec<_> = projects.into_iter().collect();
+        let total: usize = projects.iter().map(|(_, deps)| deps.len()).sum();
+        if total <= max {
+            return Ok(());
+        }
+        if !self.truncate {
+            anyhow::bail!(
+                "{} dependencies found, more than --max-deps {} — check that the scan did not \
+                 descend into vendored or generated directories (or pass --truncate)",
+                total,
+                max
+            );
+        }
+        let mut left = max;
+        for (path, deps) in &mut projects {
+            let keep = left.min(deps.len());
+            left -= keep;
+            if keep == deps.len() {
+                continue;
+            }
+            Diagnostic::warning(format!(
+                "--max-deps {} reached: keeping {} of {} dependencies in {}",
+                max,
+                keep,
+                deps.len(),
+                path.display()
+            ))
+            .file(path)
+            .emit();
+            deps.truncate(keep);
+        }
+        Ok(())
+    }
+}
+
+/// Detect ecosystems and analyze manifests, dropping `exclusions`. Returns an
+/// empty `Vec` (not an error) when no ecosystems are detected.
+fn analyze_project(
     path: &Path,
-    _config: &config::Config,
     excluded: &[Ecosystem],
-    online: bool,
+    exclusions: Exclusions,
+    analysis_cache: bool,
     quiet: bool,
 ) -> Result<Vec<models::Dependency>> {
     let ecosystems: Vec<Ecosystem> = detect_ecosystems(path)
@@ -278,47 +1156,139 @@ async fn scan_project(
     }
 
     let mut all_deps = Vec::new();
+    let mut cache = if analysis_cache {
+        let file = analyzer::cache::cache_path(path)?;
+        Some(analyzer::cache::AnalysisCache::open(&file, path))
+    } else {
+        None
+    };
 
     for ecosystem in &ecosystems {
-        let deps = match ecosystem {
-            Ecosystem::Rust => analyzer::rust::RustAnalyzer::new().analyze(path)?,
-            Ecosystem::Python => analyzer::python::PythonAnalyzer::new().analyze(path)?,
-            Ecosystem::Java => analyzer::java::JavaAnalyzer::new().analyze(path)?,
-            Ecosystem::Node => analyzer::node::NodeAnalyzer::new().analyze(path)?,
-            Ecosystem::DotNet => analyzer::dotnet::DotNetAnalyzer::new().analyze(path)?,
+        let deps = match &mut cache {
+            Some(cache) => cache.analyze(ecosystem, || analyze_ecosystem(path, ecosystem))?,
+            None => analyze_ecosystem(path, ecosystem)?,
         };
 
         if !quiet {
-            eprintln!(
-                "    {} {} {} dependencies",
-                "·".dimmed(),
-                ecosystem,
-                deps.len()
-            );
+            print_dep_count(ecosystem, deps.len());
         }
 
         all_deps.extend(deps);
     }
 
-    if online {
-        enrich_online(&mut all_deps, quiet).await?;
+    if let Some(cache) = &cache {
+        if !quiet && cache.hits > 0 {
+            Diagnostic::note(format!(
+                "{} of {} ecosystems unchanged, reused from the analysis cache",
+                cache.hits,
+                ecosystems.len()
+            ))
+            .emit();
+        }
+        if let Err(e) = cache.save() {
+            Diagnostic::warning(format!("{:#}", e)).emit();
+        }
     }
 
+    exclusions.apply(path, &mut all_deps)?;
     Ok(all_deps)
 }
 
+/// Run the analyzer for `ecosystem` on the project at `path`.
+fn analyze_ecosystem(path: &Path, ecosystem: &Ecosystem) -> Result<Vec<models::Dependency>> {
+    match ecosystem {
+        Ecosystem::Rust => analyzer::rust::RustAnalyzer::new().analyze(path),
+        Ecosystem::Python => analyzer::python::PythonAnalyzer::new().analyze(path),
+        Ecosystem::Java => analyzer::java::JavaAnalyzer::new().analyze(path),
+        Ecosystem::Node => analyzer::node::NodeAnalyzer::new().analyze(path),
+        Ecosystem::DotNet => analyzer::dotnet::DotNetAnalyzer::new().analyze(path),
+        Ecosystem::Clojure => analyzer::clojure::ClojureAnalyzer::new().analyze(path),
+        Ecosystem::OCaml => analyzer::ocaml::OCamlAnalyzer::new().analyze(path),
+        Ecosystem::Go => analyzer::go::GoAnalyzer::new().analyze(path),
+        Ecosystem::Ruby => analyzer::ruby::RubyAnalyzer::new().analyze(path),
+        Ecosystem::Php => analyzer::php::ComposerAnalyzer::new().analyze(path),
+        // Never detected on their own: conda-lock.yml is read by the Python analyzer,
+        // deno.lock / deno.json by the Node analyzer
+        Ecosystem::Conda | Ecosystem::Deno => Ok(Vec::new()),
+    }
+}
+
 // ── Online enrichment ─────────────────────────────────────────────────────────
 
-async fn enrich_online(deps: &mut [models::Dependency], quiet: bool) -> Result<()> {
-    use futures::future::join_all;
+/// `--online` lookup settings; `None` from [`OnlineOptions::from_cli`] when the
+/// scan is offline.
+#[derive(Debug, Clone)]
+struct OnlineOptions {
+    /// Ask deps.dev when a package's own registry has no license (`--no-depsdev`
+    /// turns it off).
+    depsdev: bool,
+    /// Per-request timeout (`--registry-timeout`).
+    timeout: Duration,
+    /// `--user-agent`.
+    user_agent: String,
+}
 
-    const BATCH_SIZE: usize = 50;
+impl OnlineOptions {
+    fn from_cli(cli: &Cli) -> Option<Self> {
+        cli.online.then(|| OnlineOptions {
+            depsdev: !cli.no_depsdev,
+            timeout: Duration::from_secs(cli.registry_timeout),
+            user_agent: cli.user_agent.clone(),
+        })
+    }
+}
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .build()?;
+/// How `--online` enrichment reports progress (`--progress` / `--no-progress`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Progress {
+    /// Nothing (`--quiet`, `--no-progress`, workspace scans).
+    Off,
+    /// A progress bar, drawn only on an interactive terminal (the default).
+    Bar,
+    /// `--progress`: the bar on a terminal, otherwise (or with `--json-diagnostics`)
+    /// a line every 50 lookups so CI logs show the run is alive.
+    Always,
+}
 
-    let pb = if !quiet {
+impl Progress {
+    fn from_cli(cli: &Cli) -> Self {
+        if cli.no_progress {
+            Progress::Off
+        } else if cli.progress {
+            Progress::Always
+        } else if cli.quiet {
+            Progress::Off
+        } else {
+            Progress::Bar
+        }
+    }
+}
+
+/// Fetch registry licenses concurrently: at most `--jobs` requests are in flight,
+/// and each registry host is further limited by `limits`.
+///
+/// Licenses already read from a local cache are for the exact version and are
+/// not looked up again, nor are packages whose answer is in `registry_cache`;
+/// successful lookups are added to it. A package its registry has no license
+/// for (or, like Go modules, no registry at all) is looked up on deps.dev unless
+/// `options` disable it. Returns the lookup outcome counters.
+async fn enrich_online(
+    deps: &mut [models::Dependency],
+    options: &OnlineOptions,
+    limits: &registry::HostLimits,
+    registry_cache: Option<&registry::cache::RegistryCache>,
+    progress: Progress,
+) -> Result<registry::OnlineStats> {
+    use futures::stream::{FuturesUnordered, StreamExt};
+
+    /// With `--progress` and no terminal, print a line every this many lookups.
+    const PROGRESS_LINE_EVERY: usize = 50;
+
+    let client = registry::client(options.timeout, &options.user_agent)?;
+
+    let progress_lines = progress == Progress::Always
+        && (diagnostics::is_json() || !std::io::stderr().is_terminal());
+    let pb = if progress != Progress::Off && !progress_lines && !diagnostics::is_json() {
         let pb = ProgressBar::new(deps.len() as u64);
         pb.set_style(
             ProgressStyle::default_bar()
@@ -332,51 +1302,539 @@ async fn enrich_online(deps: &mut [models::Dependency], quiet: bool) -> Result<(
         None
     };
 
-    for batch in deps.chunks_mut(BATCH_SIZE) {
-        let handles: Vec<_> = batch
-            .iter()
-            .map(|dep| {
-                let client = client.clone();
-                let name = dep.name.clone();
-                let version = dep.version.clone();
-                let ecosystem = dep.ecosystem.clone();
-                tokio::spawn(async move {
-                    match ecosystem {
-                        Ecosystem::Rust => {
-                            registry::crates_io::fetch_license(&client, &name, &version).await
-                        }
-                        Ecosystem::Python => {
-                            registry::pypi::fetch_license(&client, &name, &version).await
-                        }
-                        Ecosystem::Java => {
-                            registry::maven::fetch_license(&client, &name, &version).await
-                        }
-                        Ecosystem::Node => {
-                            registry::npm::fetch_license(&client, &name, &version).await
-                        }
-                        Ecosystem::DotNet => Ok(None),
-                    }
-                })
-            })
-            .collect();
-
-        let results = join_all(handles).await;
-
-        for (dep, join_result) in batch.iter_mut().zip(results) {
-            if let Ok(Ok(Some(license))) = join_result {
+    let mut stats = registry::OnlineStats::default();
+    let mut lookups = FuturesUnordered::new();
+    for (index, dep) in deps.iter_mut().enumerate() {
+        if matches!(dep.source, LicenseSource::Cache) {
+            stats.cache_hits += 1;
+            continue;
+        }
+        if let Some(cached) =
+            registry_cache.and_then(|c| c.get(&dep.ecosystem, &dep.name, &dep.version))
+        {
+            stats.cache_hits += 1;
+            if let Some(license) = cached {
                 dep.license_raw = Some(license.clone());
                 dep.license_spdx = Some(license);
                 dep.source = LicenseSource::Registry;
             }
-            if let Some(pb) = &pb {
-                pb.inc(1);
+            continue;
+        }
+        let depsdev = options.depsdev && registry::depsdev::system_for(&dep.ecosystem).is_some();
+        if (registry::host_for(&dep.ecosystem).is_none() && !depsdev)
+            || (dep.ecosystem == Ecosystem::Java
+                && analyzer::java::is_unresolved_version(&dep.version))
+        {
+            continue;
+        }
+
+        let client = client.clone();
+        let name = dep.name.clone();
+        let version = dep.version.clone();
+        let ecosystem = dep.ecosystem.clone();
+        let host = limits.semaphore(&ecosystem);
+        let depsdev_host = limits.host_semaphore(registry::depsdev::HOST);
+        let jobs = limits.jobs();
+        let handle = tokio::spawn(async move {
+            // Wait for the host first, so lookups queued behind a busy registry
+            // do not hold `--jobs` permits that other registries could use
+            let _host_permit = match host {
+                Some(s) => Some(s.acquire_owned().await?),
+                None => None,
+            };
+            let _permit = jobs.acquire_owned().await?;
+            let mut note = None;
+            let result = match ecosystem {
+                Ecosystem::Rust => registry::crates_io::fetch_license(&client, &name, &version)
+                    .await
+                    .map(|found| {
+                        found.map(|found| {
+                            note = found.fallback_note;
+                            found.license
+                        })
+                    }),
+                Ecosystem::Python => registry::pypi::fetch_license(&client, &name, &version).await,
+                Ecosystem::Java | Ecosystem::Clojure => {
+                    registry::maven::fetch_license(&client, &name, &version).await
+                }
+                Ecosystem::Node => registry::npm::fetch_license(&client, &name, &version).await,
+                Ecosystem::OCaml => registry::opam::fetch_license(&client, &name, &version).await,
+                Ecosystem::DotNet => registry::nuget::fetch_license(&client, &name, &version).await,
+                Ecosystem::Conda
+                | Ecosystem::Deno
+                | Ecosystem::Go
+                | Ecosystem::Ruby
+                | Ecosystem::Php => Ok(None),
+            };
+            if !depsdev || !matches!(result, Ok(None)) {
+                return result.map(|license| (license, note));
+            }
+            let _depsdev_permit = match depsdev_host {
+                Some(s) => Some(s.acquire_owned().await?),
+                None => None,
+            };
+            registry::depsdev::fetch_license(&client, &ecosystem, &name, &version)
+                .await
+                .map(|license| (license, None))
+        });
+        lookups.push(async move { (index, handle.await) });
+    }
+
+    // Dependencies that needed no request are done already
+    let total = deps.len();
+    let mut done = total - lookups.len();
+    if let Some(pb) = &pb {
+        pb.inc(done as u64);
+    }
+
+    while let Some((index, join_result)) = lookups.next().await {
+        let dep = &mut deps[index];
+        let (result, note) = match join_result.unwrap_or_else(|e| Err(e.into())) {
+            Ok((license, note)) => (Ok(license), note),
+            Err(e) => (Err(e), None),
+        };
+        stats.record(&result);
+        // The cache holds exact-version answers only, so a fallback license is
+        // looked up (and noted) again on the next run
+        if let (Some(cache), Ok(license), None) = (registry_cache, &result, &note) {
+            cache.insert(&dep.ecosystem, &dep.name, &dep.version, license.clone());
+        }
+        if let Some(note) = note {
+            let diagnostic = Diagnostic::note(note.clone()).package(&dep.name);
+            match &pb {
+                Some(pb) => pb.suspend(|| diagnostic.emit()),
+                None => diagnostic.emit(),
+            }
+            dep.notes.push(note);
+        }
+        // Text output only counts failures (`--verbose` prints the totals)
+        if let Err(e) = &result {
+            if diagnostics::is_json() {
+                Diagnostic::warning(format!("online lookup failed: {:#}", e))
+                    .package(&dep.name)
+                    .emit();
             }
         }
+        if let Ok(Some(license)) = result {
+            dep.license_raw = Some(license.clone());
+            dep.license_spdx = Some(license);
+            dep.source = LicenseSource::Registry;
+        }
+
+        done += 1;
+        if let Some(pb) = &pb {
+            pb.inc(1);
+        }
+        if progress_lines && done.is_multiple_of(PROGRESS_LINE_EVERY) && done < total {
+            Diagnostic::info(format!("online: {}/{} dependencies looked up", done, total)).emit();
+        }
+    }
+    if progress_lines {
+        Diagnostic::info(format!(
+            "online: {}/{} dependencies looked up",
+            total, total
+        ))
+        .emit();
     }
 
     if let Some(pb) = pb {
         pb.finish_with_message("Done");
     }
+    if let Some(cache) = registry_cache {
+        if let Err(e) = cache.save() {
+            Diagnostic::warning(format!("{:#}", e)).emit();
+        }
+    }
 
-    Ok(())
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_explicit_paths_scanned_as_separate_projects() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let lock = r#"{
+  "lockfileVersion": 3,
+  "packages": {
+    "": { "name": "app" },
+    "node_modules/express": { "version": "4.18.2", "license": "MIT" }
+  }
+}"#;
+        let frontend = tmp.path().join("frontend");
+        let backend = tmp.path().join("services").join("backend");
+        for dir in [&frontend, &backend] {
+            std::fs::create_dir_all(dir).unwrap();
+            std::fs::write(dir.join("package-lock.json"), lock).unwrap();
+        }
+
+        let cli = Cli::parse_from([
+            "license-checkr".as_ref(),
+            frontend.as_os_str(),
+            backend.as_os_str(),
+        ]);
+        assert_eq!(cli.path.len(), 2);
+
+        let (results, online_stats) = scan_projects(&cli, cli.path.clone(), &[]).await.unwrap();
+        assert!(online_stats.is_none());
+        let names: Vec<&str> = results.iter().map(|(p, _)| p.name.as_str()).collect();
+        assert_eq!(names, ["frontend", "backend"]);
+        assert!(results.iter().all(|(p, _)| p.deps.len() == 1));
+    }
+
+    #[test]
+    fn test_nonexistent_path_among_several_is_rejected() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let frontend = tmp.path().join("frontend");
+        std::fs::create_dir_all(&frontend).unwrap();
+        std::fs::write(frontend.join("package-lock.json"), "{}").unwrap();
+        let missing = tmp.path().join("nonexistent");
+
+        assert!(check_scan_path(false, &frontend, &[]).is_ok());
+        let err = check_scan_path(false, &missing, &[]).unwrap_err();
+        assert!(err.to_string().contains("does not exist"), "{err}");
+        // Empty directories are rejected too, unlike with `--recursive` sub-projects
+        assert!(check_scan_path(false, tmp.path(), &[]).is_err());
+        assert!(check_scan_path(true, tmp.path(), &[]).is_ok());
+    }
+
+    #[test]
+    fn test_subcommand_after_path_is_not_a_second_path() {
+        let cli = Cli::parse_from(["license-checkr", "p", "doctor"]);
+        assert_eq!(cli.path, [std::path::PathBuf::from("p")]);
+        assert!(matches!(cli.command, Some(cli::Command::Doctor)));
+    }
+
+    #[tokio::test]
+    async fn test_ignore_tree_drops_exclusive_dependencies() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let lock = r#"{
+  "lockfileVersion": 3,
+  "packages": {
+    "": { "dependencies": { "express": "^4.0.0" }, "devDependencies": { "jest": "^29.0.0" } },
+    "node_modules/express": { "version": "4.18.2", "license": "MIT", "dependencies": { "debug": "2.6.9" } },
+    "node_modules/jest": { "version": "29.7.0", "license": "MIT", "dependencies": { "jest-cli": "29.7.0", "debug": "2.6.9" } },
+    "node_modules/jest-cli": { "version": "29.7.0", "license": "MIT" },
+    "node_modules/debug": { "version": "2.6.9", "license": "MIT" }
+  }
+}"#;
+        std::fs::write(tmp.path().join("package-lock.json"), lock).unwrap();
+
+        let cli = Cli::parse_from([
+            "license-checkr".as_ref(),
+            tmp.path().as_os_str(),
+            "--ignore-tree".as_ref(),
+            "jest".as_ref(),
+        ]);
+        let (results, _) = scan_projects(&cli, cli.path.clone(), &[]).await.unwrap();
+        let mut names: Vec<&str> = results[0].0.deps.iter().map(|d| d.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, ["debug", "express"]);
+    }
+
+    #[tokio::test]
+    async fn test_ignored_error_dependency_does_not_fail_the_run() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let lock = r#"{
+  "lockfileVersion": 3,
+  "packages": {
+    "": { "name": "app" },
+    "node_modules/express": { "version": "4.18.2", "license": "MIT" },
+    "node_modules/gpl-thing": { "version": "1.0.0", "license": "GPL-3.0" }
+  }
+}"#;
+        std::fs::write(tmp.path().join("package-lock.json"), lock).unwrap();
+        let config = tmp.path().join("config.toml");
+        std::fs::write(
+            &config,
+            "[policy]\n[policy.licenses]\n\"MIT\" = \"pass\"\n\"GPL-3.0\" = \"error\"\n",
+        )
+        .unwrap();
+
+        let exit_code = |ignore: &'static [&'static str]| {
+            let mut args = vec![
+                "license-checkr".into(),
+                tmp.path().as_os_str().to_owned(),
+                "--config".into(),
+                config.as_os_str().to_owned(),
+            ];
+            for entry in ignore {
+                args.extend(["--ignore".into(), (*entry).into()]);
+            }
+            async move {
+                let cli = Cli::parse_from(args);
+                let (results, _) = scan_projects(&cli, cli.path.clone(), &[]).await.unwrap();
+                let mut outcome = ScanOutcome::default();
+                outcome.add_violations(&results[0].0.deps, None, None, &ExitCodes::from_cli(&cli));
+                (results[0].0.deps.len(), outcome.exit_code())
+            }
+        };
+
+        assert_eq!(exit_code(&[]).await, (2, 1));
+        assert_eq!(exit_code(&["GPL-Thing"]).await, (1, 0));
+        assert_eq!(exit_code(&["gpl-thing@1.0.0"]).await, (1, 0));
+        // A different version is not ignored
+        assert_eq!(exit_code(&["gpl-thing@2.0.0"]).await, (2, 1));
+    }
+
+    #[tokio::test]
+    async fn test_max_deps_limits_the_whole_run() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let lock = r#"{
+  "lockfileVersion": 3,
+  "packages": {
+    "": { "name": "app" },
+    "node_modules/express": { "version": "4.18.2", "license": "MIT" },
+    "node_modules/debug": { "version": "2.6.9", "license": "MIT" }
+  }
+}"#;
+        let (a, b) = (tmp.path().join("a"), tmp.path().join("b"));
+        for dir in [&a, &b] {
+            std::fs::create_dir_all(dir).unwrap();
+            std::fs::write(dir.join("package-lock.json"), lock).unwrap();
+        }
+        let cli = |args: &[&str]| {
+            let mut argv = vec![
+                std::ffi::OsStr::new("license-checkr"),
+                a.as_os_str(),
+                b.as_os_str(),
+            ];
+            argv.extend(args.iter().map(std::ffi::OsStr::new));
+            Cli::parse_from(argv)
+        };
+
+        // 4 dependencies in total
+        let cli_ok = cli(&["--max-deps", "4"]);
+        assert!(scan_projects(&cli_ok, cli_ok.path.clone(), &[])
+            .await
+            .is_ok());
+
+        let cli_over = cli(&["--max-deps", "3"]);
+        let err = scan_projects(&cli_over, cli_over.path.clone(), &[])
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("more than --max-deps 3"),
+            "{}",
+            err
+        );
+
+        let cli_truncate = cli(&["--max-deps", "3", "--truncate"]);
+        let (results, _) = scan_projects(&cli_truncate, cli_truncate.path.clone(), &[])
+            .await
+            .unwrap();
+        // The cap is checked once all projects are analyzed, so the cut is stable:
+        // earlier projects keep their dependencies
+        let kept: Vec<usize> = results.iter().map(|(p, _)| p.deps.len()).collect();
+        assert_eq!(kept, [2, 1]);
+    }
+
+    #[test]
+    fn test_filter_label_keeps_labeled_dependencies() {
+        let config: config::Config = toml::from_str(
+            "[policy]\n[labels]\n\"@acme/*\" = \"customer-facing\"\n\"jest\" = \"internal-tooling\"\n",
+        )
+        .unwrap();
+        let dep = |name: &str| models::dep(name, Some("MIT"), PolicyVerdict::Warn);
+        let mut deps = vec![dep("@acme/ui"), dep("jest"), dep("left-pad")];
+        evaluate_deps(&mut deps, &config);
+        assert_eq!(deps[0].labels, ["customer-facing"]);
+
+        assert_eq!(labeled(&deps, &[]).len(), 3);
+
+        let filtered = labeled(&deps, &["customer-facing".to_string()]);
+        let names: Vec<&str> = filtered.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["@acme/ui"]);
+        // Only the rendered slice is narrowed; the outcome still sees every dependency
+        assert_eq!(deps.len(), 3);
+    }
+
+    #[test]
+    fn test_normalize_table_maps_unknown_license_to_policy() {
+        let dep = |raw: &str, spdx: &str| models::Dependency {
+            license_spdx: Some(spdx.to_string()),
+            ..models::dep("pkg", Some(raw), PolicyVerdict::Warn)
+        };
+        let policy = "[policy]\ndefault = \"warn\"\n[policy.licenses]\n\"MIT\" = \"pass\"\n";
+        let acme = "Acme Corp Open License";
+
+        let plain: config::Config = toml::from_str(policy).unwrap();
+        let mut deps = vec![dep(acme, acme)];
+        evaluate_deps(&mut deps, &plain);
+        assert_eq!(deps[0].risk, LicenseRisk::Unknown);
+        assert_eq!(deps[0].verdict, PolicyVerdict::Warn);
+
+        let mapped: config::Config = toml::from_str(&format!(
+            "{}[normalize]\n\"{}\" = \"MIT\"\n\"GNU GPL v3\" = \"MIT\"\n",
+            policy, acme
+        ))
+        .unwrap();
+        // The second dependency's analyzer already mapped its raw string built-in
+        let mut deps = vec![dep(acme, acme), dep("GNU GPL v3", "GPL-3.0")];
+        evaluate_deps(&mut deps, &mapped);
+        assert_eq!(deps[0].license_spdx.as_deref(), Some("MIT"));
+        assert_eq!(deps[0].risk, LicenseRisk::Permissive);
+        assert_eq!(deps[0].verdict, PolicyVerdict::Pass);
+        assert_eq!(deps[1].license_spdx.as_deref(), Some("MIT"));
+    }
+
+    #[test]
+    fn test_exclude_dev_filters_dev_dependencies() {
+        let dep = |dev: bool| models::Dependency {
+            dev,
+            ..models::dep("pytest", Some("MIT"), PolicyVerdict::Pass)
+        };
+        let keeps_dev = |args: &[&str]| {
+            Exclusions::from_cli(&Cli::parse_from(["license-checkr", "."].iter().chain(args)))
+                .keeps(&dep(true))
+        };
+
+        assert!(keeps_dev(&[]));
+        assert!(keeps_dev(&["--include-dev"]));
+        assert!(!keeps_dev(&["--exclude-dev"]));
+        // The last of the two flags wins
+        assert!(keeps_dev(&["--exclude-dev", "--include-dev"]));
+        assert!(!keeps_dev(&["--include-dev", "--exclude-dev"]));
+        assert!(
+            Exclusions::from_cli(&Cli::parse_from(["license-checkr", ".", "--exclude-dev"]))
+                .keeps(&dep(false))
+        );
+    }
+
+    #[test]
+    fn test_progress_is_independent_of_quiet() {
+        let progress = |args: &[&str]| {
+            Progress::from_cli(&Cli::parse_from(["license-checkr", "."].iter().chain(args)))
+        };
+        assert_eq!(progress(&[]), Progress::Bar);
+        assert_eq!(progress(&["--quiet"]), Progress::Off);
+        assert_eq!(progress(&["--quiet", "--progress"]), Progress::Always);
+        assert_eq!(progress(&["--no-progress"]), Progress::Off);
+        assert!(Cli::try_parse_from(["license-checkr", "--progress", "--no-progress"]).is_err());
+    }
+
+    #[test]
+    fn test_fail_on_review_also_fails_review_verdicts() {
+        let dep = |name: &str, verdict: PolicyVerdict| models::dep(name, Some("MPL-2.0"), verdict);
+        let deps = vec![
+            dep("a", PolicyVerdict::Warn),
+            dep("b", PolicyVerdict::Review),
+            dep("c", PolicyVerdict::Error),
+        ];
+
+        let codes = |args: &[&str]| {
+            ExitCodes::from_cli(&Cli::parse_from(["license-checkr", "."].iter().chain(args)))
+        };
+
+        let mut outcome = ScanOutcome::default();
+        outcome.add_violations(&deps, None, None, &codes(&[]));
+        assert_eq!(outcome.violations, ["c (MPL-2.0)"]);
+        assert!(outcome.reviews.is_empty());
+        assert_eq!(outcome.exit_code(), 1);
+
+        let mut outcome = ScanOutcome::default();
+        outcome.add_violations(&deps, None, None, &codes(&["--fail-on", "review"]));
+        assert_eq!(outcome.reviews, ["b (MPL-2.0)"]);
+        assert!(outcome
+            .summary()
+            .contains("1 dependency requires review: b (MPL-2.0)"));
+        assert_eq!(outcome.exit_code(), 1);
+    }
+
+    #[test]
+    fn test_known_licenses_json_groups_by_risk() {
+        let json = known_licenses_json();
+        let licenses = |risk: &str| {
+            json.as_array()
+                .unwrap()
+                .iter()
+                .find(|group| group["risk"] == risk)
+                .unwrap()["licenses"]
+                .clone()
+        };
+        assert!(licenses("Permissive")
+            .as_array()
+            .unwrap()
+            .contains(&"MIT".into()));
+        assert!(licenses("StrongCopyleft")
+            .as_array()
+            .unwrap()
+            .contains(&"GPL-3.0".into()));
+        assert!(!licenses("Permissive")
+            .as_array()
+            .unwrap()
+            .contains(&"GPL-3.0".into()));
+    }
+
+    #[test]
+    fn test_depsdev_fallback_is_on_by_default() {
+        assert!(OnlineOptions::from_cli(&Cli::parse_from(["license-checkr"])).is_none());
+        let online = OnlineOptions::from_cli(&Cli::parse_from(["license-checkr", "--online"]));
+        assert!(online.unwrap().depsdev);
+        let online = OnlineOptions::from_cli(&Cli::parse_from([
+            "license-checkr",
+            "--online",
+            "--no-depsdev",
+        ]));
+        assert!(!online.unwrap().depsdev);
+    }
+
+    #[test]
+    fn test_fail_on_levels() {
+        let dep = |verdict: PolicyVerdict| models::dep("pkg", Some("MPL-2.0"), verdict);
+        let exit_code = |fail_on: &str, verdict: PolicyVerdict| {
+            let cli = Cli::parse_from(["license-checkr", ".", "--fail-on", fail_on]);
+            let mut outcome = ScanOutcome::default();
+            outcome.add_violations(&[dep(verdict)], None, None, &ExitCodes::from_cli(&cli));
+            outcome.exit_code()
+        };
+
+        assert_eq!(exit_code("error", PolicyVerdict::Error), 1);
+        assert_eq!(exit_code("error", PolicyVerdict::Warn), 0);
+
+        assert_eq!(exit_code("warn", PolicyVerdict::Warn), 1);
+        assert_eq!(exit_code("warn", PolicyVerdict::Review), 1);
+        assert_eq!(exit_code("warn", PolicyVerdict::Error), 1);
+        assert_eq!(exit_code("warn", PolicyVerdict::Pass), 0);
+
+        assert_eq!(exit_code("never", PolicyVerdict::Error), 0);
+        assert_eq!(exit_code("never", PolicyVerdict::Warn), 0);
+    }
+
+    #[test]
+    fn test_exit_code_is_highest_mapped_verdict() {
+        let dep = |name: &str, verdict: PolicyVerdict| models::dep(name, Some("MPL-2.0"), verdict);
+        let exit_code = |deps: &[models::Dependency], args: &[&str]| {
+            let cli = Cli::parse_from(["license-checkr", "."].iter().chain(args));
+            let mut outcome = ScanOutcome::default();
+            outcome.add_violations(deps, None, None, &ExitCodes::from_cli(&cli));
+            outcome.exit_code()
+        };
+        let mapping = [
+            "--exit-code-error",
+            "2",
+            "--exit-code-review",
+            "3",
+            "--exit-code-warn",
+            "4",
+        ];
+        let pass = dep("a", PolicyVerdict::Pass);
+        let warn = dep("b", PolicyVerdict::Warn);
+        let error = dep("c", PolicyVerdict::Error);
+
+        // Defaults: only errors fail, with 1
+        assert_eq!(exit_code(&[pass.clone(), warn.clone()], &[]), 0);
+        assert_eq!(exit_code(&[warn.clone(), error.clone()], &[]), 1);
+
+        assert_eq!(exit_code(std::slice::from_ref(&pass), &mapping), 0);
+        assert_eq!(exit_code(&[pass.clone(), error.clone()], &mapping), 2);
+        // The highest code present wins, not the most severe verdict
+        assert_eq!(exit_code(&[warn.clone(), error.clone()], &mapping), 4);
+        assert_eq!(
+            exit_code(&[warn.clone(), error.clone()], &["--exit-code-error", "0"]),
+            0
+        );
+    }
 }
@@ -19,6 +19,64 @@ pub struct Dependency {
     pub verdict: PolicyVerdict,
     /// Where the license information was obtained from.
     pub source: LicenseSource,
+    /// Auditor-facing annotations (e.g. "patched via `[patch]`").
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+    /// Newly added or newly erroring since the previous `--track-history` run.
+    /// Display-only; never serialized.
+    #[serde(skip)]
+    pub is_new: bool,
+    /// Only installed as an optional dependency (from manifests that record it, e.g. npm
+    /// lockfiles, Maven `<optional>`).
+    #[serde(default)]
+    pub optional: bool,
+    /// Only needed for development or not shipped (npm `dev`, PDM dev groups, Hatch
+    /// environments, Maven `test` / `provided` scope).
+    #[serde(default)]
+    pub dev: bool,
+    /// Report labels from the config's `[labels]` table (e.g. `customer-facing`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+}
+
+/// Test fixture: a Node dependency `name@1.0.0` whose manifest declares `license`
+/// (as both raw and SPDX string), with unknown risk and the given `verdict`.
+/// Tests adjust the rest with the `with_*` methods or struct update syntax.
+#[cfg(test)]
+pub fn dep(name: &str, license: Option<&str>, verdict: PolicyVerdict) -> Dependency {
+    Dependency {
+        name: name.to_string(),
+        version: "1.0.0".to_string(),
+        ecosystem: Ecosystem::Node,
+        license_raw: license.map(str::to_string),
+        license_spdx: license.map(str::to_string),
+        risk: LicenseRisk::Unknown,
+        verdict,
+        source: LicenseSource::Manifest,
+        notes: Vec::new(),
+        is_new: false,
+        optional: false,
+        dev: false,
+        labels: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+impl Dependency {
+    pub fn with_version(self, version: &str) -> Self {
+        Dependency {
+            version: version.to_string(),
+            ..self
+        }
+    }
+
+    pub fn with_ecosystem(self, ecosystem: Ecosystem) -> Self {
+        Dependency { ecosystem, ..self }
+    }
+
+    pub fn with_risk(self, risk: LicenseRisk) -> Self {
+        Dependency { risk, ..self }
+    }
 }
 
 /// Risk level associated with a license type.
@@ -36,6 +94,30 @@ pub enum LicenseRisk {
     Unknown,
 }
 
+impl LicenseRisk {
+    /// Severity rank used for `--min-risk` filtering:
+    /// Permissive < Weak Copyleft < Strong Copyleft < Proprietary.
+    ///
+    /// [`LicenseRisk::Unknown`] has no rank — its actual risk cannot be ruled out.
+    pub fn severity(&self) -> Option<u8> {
+        match self {
+            LicenseRisk::Permissive => Some(0),
+            LicenseRisk::WeakCopyleft => Some(1),
+            LicenseRisk::StrongCopyleft => Some(2),
+            LicenseRisk::Proprietary => Some(3),
+            LicenseRisk::Unknown => None,
+        }
+    }
+
+    /// Returns `true` if this risk is at or above `min`. Unknown risk always qualifies.
+    pub fn at_least(&self, min: &LicenseRisk) -> bool {
+        match (self.severity(), min.severity()) {
+            (Some(own), Some(min)) => own >= min,
+            _ => true,
+        }
+    }
+}
+
 impl std::fmt::Display for LicenseRisk {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -55,15 +137,30 @@ pub enum PolicyVerdict {
     Pass,
     /// License is not blocked but warrants attention.
     Warn,
+    /// License needs sign-off (e.g. legal review) before it can ship.
+    Review,
     /// License violates the policy; the CLI exits with code 1.
     Error,
 }
 
+impl PolicyVerdict {
+    /// Severity rank: Pass < Warn < Review < Error.
+    pub fn severity(&self) -> u8 {
+        match self {
+            PolicyVerdict::Pass => 0,
+            PolicyVerdict::Warn => 1,
+            PolicyVerdict::Review => 2,
+            PolicyVerdict::Error => 3,
+        }
+    }
+}
+
 impl std::fmt::Display for PolicyVerdict {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             PolicyVerdict::Pass => write!(f, "pass"),
             PolicyVerdict::Warn => write!(f, "warn"),
+            PolicyVerdict::Review => write!(f, "review"),
             PolicyVerdict::Error => write!(f, "error"),
         }
     }
@@ -82,6 +179,22 @@ pub enum Ecosystem {
     Node,
     /// .NET NuGet packages (SDK-style projects, `packages.config`, Paket).
     DotNet,
+    /// Clojure libraries (Maven artifacts) from `deps.edn` or Leiningen `project.clj`.
+    Clojure,
+    /// Conda packages from `conda-lock.yml` (read by the Python analyzer; `pip`
+    /// entries of the lock stay [`Ecosystem::Python`]).
+    Conda,
+    /// OCaml opam packages from `*.opam` files or `dune-project`.
+    OCaml,
+    /// JSR packages of Deno projects from `deno.lock` / `deno.json` (read by the
+    /// Node analyzer; `npm:` specifiers are [`Ecosystem::Node`]).
+    Deno,
+    /// Go modules from `go.mod` (versions pinned by `go.sum` when present).
+    Go,
+    /// Ruby gems from the `GEM` section of Bundler's `Gemfile.lock`.
+    Ruby,
+    /// PHP packages managed by Composer (`composer.lock` / `composer.json`).
+    Php,
 }
 
 impl std::fmt::Display for Ecosystem {
@@ -92,6 +205,13 @@ impl std::fmt::Display for Ecosystem {
             Ecosystem::Java => write!(f, "Java"),
             Ecosystem::Node => write!(f, "Node"),
             Ecosystem::DotNet => write!(f, ".NET"),
+            Ecosystem::Clojure => write!(f, "Clojure"),
+            Ecosystem::Conda => write!(f, "Conda"),
+            Ecosystem::OCaml => write!(f, "OCaml"),
+            Ecosystem::Deno => write!(f, "Deno"),
+            Ecosystem::Go => write!(f, "Go"),
+            Ecosystem::Ruby => write!(f, "Ruby"),
+            Ecosystem::Php => write!(f, "PHP"),
         }
     }
 }
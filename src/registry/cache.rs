@@ -0,0 +1,121 @@
+//! On-disk cache of `--online` license lookups, so repeated scans do not query
+//! the registries again for versions they have already answered.
+//!
+//! Entries live in `~/.cache/license-checkr/registry.json` (or `registry.json`
+//! under `--cache-dir`), keyed by `ecosystem:name:version`. A lookup that found
+//! no license is cached too; failed lookups (network errors, 404s) are not, and
+//! neither are unpinned `*` versions, whose latest release changes over time.
+//! `--no-cache` bypasses the file entirely.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result};
+
+use crate::models::Ecosystem;
+
+/// Location of the registry cache: `registry.json` in `cache_dir`, or in
+/// `~/.cache/license-checkr` by default.
+pub fn cache_path(cache_dir: Option<&Path>) -> Result<PathBuf> {
+    let dir = match cache_dir {
+        Some(dir) => dir.to_path_buf(),
+        None => dirs::cache_dir()
+            .context("Could not determine the user cache directory")?
+            .join("license-checkr"),
+    };
+    Ok(dir.join("registry.json"))
+}
+
+/// Cached lookup results, shared by every project of a run.
+#[derive(Debug, Clone)]
+pub struct RegistryCache {
+    file: PathBuf,
+    /// `ecosystem:name:version` → license, `None` when the registry had none.
+    entries: Arc<Mutex<HashMap<String, Option<String>>>>,
+}
+
+impl RegistryCache {
+    /// Load the cache stored at `file`; a missing or unreadable cache starts empty.
+    pub fn open(file: &Path) -> Self {
+        let entries = std::fs::read_to_string(file)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        RegistryCache {
+            file: file.to_path_buf(),
+            entries: Arc::new(Mutex::new(entries)),
+        }
+    }
+
+    /// The cached answer for a package: `Some(None)` means "no license".
+    pub fn get(&self, ecosystem: &Ecosystem, name: &str, version: &str) -> Option<Option<String>> {
+        let entries = self.entries.lock().expect("registry cache lock poisoned");
+        entries.get(&key(ecosystem, name, version)).cloned()
+    }
+
+    /// Remember a successful lookup; unpinned `*` versions are skipped.
+    pub fn insert(
+        &self,
+        ecosystem: &Ecosystem,
+        name: &str,
+        version: &str,
+        license: Option<String>,
+    ) {
+        if version == "*" {
+            return;
+        }
+        let mut entries = self.entries.lock().expect("registry cache lock poisoned");
+        entries.insert(key(ecosystem, name, version), license);
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(dir) = self.file.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let json = {
+            let entries = self.entries.lock().expect("registry cache lock poisoned");
+            serde_json::to_string(&*entries)?
+        };
+        std::fs::write(&self.file, json)
+            .with_context(|| format!("Failed to write registry cache {}", self.file.display()))
+    }
+}
+
+fn key(ecosystem: &Ecosystem, name: &str, version: &str) -> String {
+    format!("{}:{}:{}", ecosystem, name, version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_round_trips_entries() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let file = cache_path(Some(&tmp.path().join("cache"))).unwrap();
+
+        let cache = RegistryCache::open(&file);
+        assert_eq!(cache.get(&Ecosystem::Rust, "serde", "1.0.200"), None);
+        cache.insert(
+            &Ecosystem::Rust,
+            "serde",
+            "1.0.200",
+            Some("MIT OR Apache-2.0".to_string()),
+        );
+        cache.insert(&Ecosystem::Node, "left-pad", "1.3.0", None);
+        cache.insert(&Ecosystem::Node, "express", "*", Some("MIT".to_string()));
+        cache.save().unwrap();
+
+        let cache = RegistryCache::open(&file);
+        assert_eq!(
+            cache.get(&Ecosystem::Rust, "serde", "1.0.200"),
+            Some(Some("MIT OR Apache-2.0".to_string()))
+        );
+        // A package without a license is a cached answer too
+        assert_eq!(cache.get(&Ecosystem::Node, "left-pad", "1.3.0"), Some(None));
+        assert_eq!(cache.get(&Ecosystem::Node, "express", "*"), None);
+        assert_eq!(cache.get(&Ecosystem::Python, "serde", "1.0.200"), None);
+    }
+}
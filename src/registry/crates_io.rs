@@ -1,26 +1,199 @@
 use anyhow::Result;
 use reqwest::Client;
+use serde_json::Value;
 
-/// Fetch the license for a crate from crates.io.
-pub async fn fetch_license(client: &Client, name: &str, version: &str) -> Result<Option<String>> {
-    let url = format!("https://crates.io/api/v1/crates/{}/{}", name, version);
+/// crates.io API root; tests point [`fetch_license_from`] at a local server instead.
+const API: &str = "https://crates.io/api/v1";
 
-    let response = client
-        .get(&url)
-        .header("User-Agent", "license-checkr/0.1.0 (license compliance tool)")
-        .send()
-        .await?;
+/// A crates.io license, with an auditor-facing note when it was not read from
+/// the version that was looked up.
+#[derive(Debug, PartialEq)]
+pub struct CrateLicense {
+    pub license: String,
+    pub fallback_note: Option<String>,
+}
+
+/// Fetch the license for a crate from crates.io (see [`fetch_license_from`]).
+pub async fn fetch_license(
+    client: &Client,
+    name: &str,
+    version: &str,
+) -> Result<Option<CrateLicense>> {
+    fetch_license_from(client, API, name, version).await
+}
 
-    if !response.status().is_success() {
+/// Fetch the license for a crate from the crates.io API at `base`.
+///
+/// A pinned version crates.io does not know (yanked and purged, or a local
+/// pre-release) falls back to the crate's latest published license, flagged
+/// by [`CrateLicense::fallback_note`].
+pub async fn fetch_license_from(
+    client: &Client,
+    base: &str,
+    name: &str,
+    version: &str,
+) -> Result<Option<CrateLicense>> {
+    let url = format!("{}/crates/{}/{}", base, name, version);
+    if let Some(data) = get_json(client, &url).await? {
+        return Ok(data
+            .get("version")
+            .and_then(|v| v.get("license"))
+            .and_then(|l| l.as_str())
+            .map(|license| CrateLicense {
+                license: license.to_string(),
+                fallback_note: None,
+            }));
+    }
+
+    let url = format!("{}/crates/{}", base, name);
+    let Some(data) = get_json(client, &url).await? else {
         return Ok(None);
+    };
+    Ok(latest_license(&data).map(|license| CrateLicense {
+        fallback_note: Some(format!(
+            "{} {} not found on crates.io; license ({}) taken from the latest version",
+            name, version, license
+        )),
+        license,
+    }))
+}
+
+/// GET `url` as JSON; `None` on a 404.
+async fn get_json(client: &Client, url: &str) -> Result<Option<Value>> {
+    let response = client.get(url).send().await?;
+    match super::check_status(response)? {
+        Some(response) => Ok(Some(response.json().await?)),
+        None => Ok(None),
     }
+}
 
-    let data: serde_json::Value = response.json().await?;
-    let license = data
-        .get("version")
+/// License of a `GET /api/v1/crates/{name}` response: `crate.license` when
+/// present, otherwise that of the newest stable (or newest) version.
+fn latest_license(data: &Value) -> Option<String> {
+    let krate = data.get("crate");
+    if let Some(license) = krate
+        .and_then(|c| c.get("license"))
+        .and_then(|l| l.as_str())
+    {
+        return Some(license.to_string());
+    }
+    let versions = data.get("versions").and_then(|v| v.as_array())?;
+    let latest = ["max_stable_version", "max_version", "newest_version"]
+        .iter()
+        .find_map(|key| krate?.get(*key)?.as_str());
+    latest
+        .and_then(|num| {
+            versions
+                .iter()
+                .find(|v| v.get("num").and_then(|n| n.as_str()) == Some(num))
+        })
+        .or(versions.first())
         .and_then(|v| v.get("license"))
         .and_then(|l| l.as_str())
-        .map(str::to_string);
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::mock_registry;
+    use serde_json::json;
 
-    Ok(license)
+    #[tokio::test]
+    async fn test_version_404_falls_back_to_latest_license() {
+        let (server_url, server) = mock_registry(&[
+            (
+                "/api/v1/crates/relicensed/0.9.0-local",
+                404,
+                r#"{"errors":[]}"#,
+            ),
+            (
+                "/api/v1/crates/relicensed",
+                200,
+                r#"{"crate":{"max_stable_version":"2.0.0"},
+                    "versions":[{"num":"2.0.0","license":"MIT OR Apache-2.0"}]}"#,
+            ),
+        ])
+        .await;
+        let base = format!("{}/api/v1", server_url);
+
+        let client = Client::new();
+        let found = fetch_license_from(&client, &base, "relicensed", "0.9.0-local")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.license, "MIT OR Apache-2.0");
+        assert_eq!(
+            found.fallback_note.as_deref(),
+            Some(
+                "relicensed 0.9.0-local not found on crates.io; \
+                 license (MIT OR Apache-2.0) taken from the latest version"
+            )
+        );
+        assert_eq!(
+            server.await.unwrap(),
+            [
+                "/api/v1/crates/relicensed/0.9.0-local",
+                "/api/v1/crates/relicensed"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_crate_is_none() {
+        let (server_url, server) = mock_registry(&[
+            ("/api/v1/crates/missing/1.0.0", 404, r#"{"errors":[]}"#),
+            ("/api/v1/crates/missing", 404, r#"{"errors":[]}"#),
+        ])
+        .await;
+        let base = format!("{}/api/v1", server_url);
+
+        let found = fetch_license_from(&Client::new(), &base, "missing", "1.0.0")
+            .await
+            .unwrap();
+        assert_eq!(found, None);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_exact_version_has_no_fallback_note() {
+        let (server_url, server) = mock_registry(&[(
+            "/api/v1/crates/serde/1.0.0",
+            200,
+            r#"{"version":{"num":"1.0.0","license":"MIT/Apache-2.0"}}"#,
+        )])
+        .await;
+        let base = format!("{}/api/v1", server_url);
+
+        let found = fetch_license_from(&Client::new(), &base, "serde", "1.0.0")
+            .await
+            .unwrap();
+        assert_eq!(
+            found,
+            Some(CrateLicense {
+                license: "MIT/Apache-2.0".to_string(),
+                fallback_note: None,
+            })
+        );
+        server.await.unwrap();
+    }
+
+    #[test]
+    fn test_latest_license_from_crate_endpoint() {
+        let data = json!({
+            "crate": { "name": "relicensed", "max_stable_version": "2.0.0", "max_version": "3.0.0-rc.1" },
+            "versions": [
+                { "num": "3.0.0-rc.1", "license": "Apache-2.0" },
+                { "num": "2.0.0", "license": "MIT OR Apache-2.0" },
+                { "num": "1.0.0", "license": "MIT" }
+            ]
+        });
+        assert_eq!(latest_license(&data).as_deref(), Some("MIT OR Apache-2.0"));
+
+        let data = json!({ "crate": { "license": "MIT" }, "versions": [] });
+        assert_eq!(latest_license(&data).as_deref(), Some("MIT"));
+
+        let data = json!({ "crate": {}, "versions": [{ "num": "0.1.0", "license": "ISC" }] });
+        assert_eq!(latest_license(&data).as_deref(), Some("ISC"));
+    }
 }
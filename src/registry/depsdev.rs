@@ -0,0 +1,124 @@
+use anyhow::Result;
+use reqwest::Client;
+use serde_json::Value;
+
+use crate::models::Ecosystem;
+
+/// Host of the deps.dev API, queried when a package's own registry has no
+/// license (`--no-depsdev` turns this off).
+pub const HOST: &str = "api.deps.dev";
+
+/// deps.dev system name for `ecosystem`, or `None` when deps.dev does not index it.
+pub fn system_for(ecosystem: &Ecosystem) -> Option<&'static str> {
+    match ecosystem {
+        Ecosystem::Rust => Some("CARGO"),
+        Ecosystem::Node => Some("NPM"),
+        Ecosystem::Python => Some("PYPI"),
+        Ecosystem::Java | Ecosystem::Clojure => Some("MAVEN"),
+        Ecosystem::Go => Some("GO"),
+        _ => None,
+    }
+}
+
+/// Fetch the license of a package version from deps.dev.
+pub async fn fetch_license(
+    client: &Client,
+    ecosystem: &Ecosystem,
+    name: &str,
+    version: &str,
+) -> Result<Option<String>> {
+    let Some(system) = system_for(ecosystem) else {
+        return Ok(None);
+    };
+    let url = format!(
+        "https://{}/v3/systems/{}/packages/{}/versions/{}",
+        HOST,
+        system,
+        encode(name),
+        encode(version)
+    );
+
+    let response = client.get(&url).send().await?;
+    let Some(response) = super::check_status(response)? else {
+        return Ok(None);
+    };
+
+    let data: Value = response.json().await?;
+    Ok(license_from_version(&data))
+}
+
+/// License of a deps.dev `versions/{version}` response. Every entry of the
+/// `licenses` array applies, so several are joined with ` AND `; deps.dev's
+/// `non-standard` placeholder is skipped.
+fn license_from_version(data: &Value) -> Option<String> {
+    let licenses: Vec<&str> = data
+        .get("licenses")
+        .and_then(|l| l.as_array())?
+        .iter()
+        .filter_map(|l| l.as_str())
+        .filter(|l| !l.is_empty() && *l != "non-standard")
+        .collect();
+    match licenses.as_slice() {
+        [] => None,
+        [license] => Some(license.to_string()),
+        _ => Some(
+            licenses
+                .iter()
+                .map(|l| {
+                    if l.contains(' ') {
+                        format!("({})", l)
+                    } else {
+                        l.to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" AND "),
+        ),
+    }
+}
+
+/// Percent-encode a path segment (`@scope/pkg` → `%40scope%2Fpkg`,
+/// `group:artifact` → `group%3Aartifact`).
+fn encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_license_from_version_response() {
+        let data = json!({
+            "versionKey": { "system": "NPM", "name": "@scope/pkg", "version": "1.0.0" },
+            "licenses": ["MIT"],
+            "links": []
+        });
+        assert_eq!(license_from_version(&data).as_deref(), Some("MIT"));
+
+        let data = json!({ "licenses": ["Apache-2.0", "MIT OR ISC", "non-standard"] });
+        assert_eq!(
+            license_from_version(&data).as_deref(),
+            Some("Apache-2.0 AND (MIT OR ISC)")
+        );
+
+        assert_eq!(license_from_version(&json!({ "licenses": [] })), None);
+        assert_eq!(license_from_version(&json!({})), None);
+    }
+
+    #[test]
+    fn test_encode_package_names() {
+        assert_eq!(encode("@scope/pkg"), "%40scope%2Fpkg");
+        assert_eq!(encode("org.slf4j:slf4j-api"), "org.slf4j%3Aslf4j-api");
+        assert_eq!(encode("github.com/x/y"), "github.com%2Fx%2Fy");
+    }
+}
@@ -3,6 +3,8 @@ use quick_xml::events::Event;
 use quick_xml::Reader;
 use reqwest::Client;
 
+use crate::license::spdx::normalize;
+
 /// Fetch the license for a Maven artifact from Maven Central.
 ///
 /// The `name` is expected in `groupId:artifactId` format (as stored in our models).
@@ -22,22 +24,20 @@ pub async fn fetch_license(client: &Client, name: &str, version: &str) -> Result
         group_path, artifact_id, version, artifact_id, version
     );
 
-    let response = client
-        .get(&pom_url)
-        .header("User-Agent", "license-checkr/0.1.0")
-        .send()
-        .await?;
-
-    if !response.status().is_success() {
+    let response = client.get(&pom_url).send().await?;
+    let Some(response) = super::check_status(response)? else {
         return Ok(None);
-    }
+    };
 
     let pom_xml = response.text().await?;
     Ok(extract_license_from_pom(&pom_xml))
 }
 
-/// Extract the first `<license><name>` from a POM XML string.
-fn extract_license_from_pom(xml: &str) -> Option<String> {
+/// Extract the `<license><name>` from a POM XML string.
+///
+/// A POM listing several licenses offers a choice between them: each name is
+/// normalized to SPDX and the result joined with ` OR `.
+pub fn extract_license_from_pom(xml: &str) -> Option<String> {
     let mut reader = Reader::from_str(xml);
     reader.config_mut().trim_text(true);
 
@@ -47,6 +47,7 @@ fn extract_license_from_pom(xml: &str) -> Option<String> {
     let mut in_name = false;
     let mut depth: u32 = 0;
     let mut licenses_depth: u32 = 0;
+    let mut names = Vec::new();
 
     loop {
         match reader.read_event_into(&mut buf) {
@@ -70,7 +71,7 @@ fn extract_license_from_pom(xml: &str) -> Option<String> {
             }
             Ok(Event::Text(ref e)) if in_name => {
                 if let Ok(text) = e.unescape() {
-                    return Some(text.to_string());
+                    names.push(text.to_string());
                 }
             }
             Ok(Event::End(ref e)) => {
@@ -93,7 +94,17 @@ fn extract_license_from_pom(xml: &str) -> Option<String> {
         buf.clear();
     }
 
-    None
+    match names.len() {
+        0 => None,
+        1 => names.pop(),
+        _ => Some(
+            names
+                .iter()
+                .map(|name| normalize(name))
+                .collect::<Vec<_>>()
+                .join(" OR "),
+        ),
+    }
 }
 
 #[cfg(test)]
@@ -114,4 +125,24 @@ mod tests {
         let license = extract_license_from_pom(pom);
         assert_eq!(license, Some("Apache License, Version 2.0".to_string()));
     }
+
+    #[test]
+    fn test_extract_dual_license_from_pom() {
+        let pom = r#"<project>
+  <licenses>
+    <license>
+      <name>Apache License, Version 2.0</name>
+    </license>
+    <license>
+      <name>GNU General Public License v2</name>
+      <comments>with the Classpath Exception</comments>
+    </license>
+  </licenses>
+  <developers><developer><name>Jane</name></developer></developers>
+</project>"#;
+        assert_eq!(
+            extract_license_from_pom(pom).as_deref(),
+            Some("Apache-2.0 OR GPL-2.0")
+        );
+    }
 }
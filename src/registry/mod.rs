@@ -1,11 +1,368 @@
 //! Async HTTP clients for fetching license data from upstream package registries.
 //!
 //! Each module exposes a single `fetch_license(client, name, version)` function
+//! (taking a [`client`], which carries the timeout and `User-Agent`)
 //! that returns `Ok(Some(license_string))` on success, `Ok(None)` when the
-//! package is not found or has no license field, and `Err` on network failures.
+//! package is not found (HTTP 404) or has no license field, and `Err` on network
+//! failures or other non-success responses ([`HttpStatus`]). crates.io
+//! answers a [`crates_io::CrateLicense`], which notes when the license comes
+//! from another version than the one looked up.
+//!
+//! [`HostLimits`] caps concurrent requests per registry host; [`OnlineStats`]
+//! counts lookup outcomes for `--verbose`; [`cache::RegistryCache`] keeps earlier
+//! answers on disk.
 
+pub mod cache;
 pub mod crates_io;
+pub mod depsdev;
 pub mod maven;
 pub mod npm;
+pub mod nuget;
+pub mod opam;
 pub mod pypi;
 
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use reqwest::{Client, Response, StatusCode};
+use serde::Serialize;
+use tokio::sync::Semaphore;
+
+use crate::models::Ecosystem;
+
+/// Default in-flight request cap per registry host (`--registry-concurrency-per-host`).
+pub const DEFAULT_CONCURRENCY_PER_HOST: usize = 6;
+
+/// Default in-flight request cap over all registries (`--jobs`).
+pub const DEFAULT_JOBS: usize = 16;
+
+/// Default per-request timeout of `--online` lookups (`--registry-timeout`).
+pub const DEFAULT_TIMEOUT_SECS: u64 = 10;
+
+/// Default `User-Agent` of `--online` lookups (`--user-agent`).
+pub const DEFAULT_USER_AGENT: &str = concat!(
+    "license-checkr/",
+    env!("CARGO_PKG_VERSION"),
+    " (license compliance tool)"
+);
+
+/// HTTP client for registry lookups: every request gives up after `timeout`
+/// and identifies itself as `user_agent`.
+pub fn client(timeout: Duration, user_agent: &str) -> Result<Client> {
+    Ok(Client::builder()
+        .timeout(timeout)
+        .user_agent(user_agent)
+        .build()?)
+}
+
+/// Registry host queried for `ecosystem`, or `None` when there is no registry lookup.
+pub fn host_for(ecosystem: &Ecosystem) -> Option<&'static str> {
+    match ecosystem {
+        Ecosystem::Rust => Some("crates.io"),
+        Ecosystem::Python => Some("pypi.org"),
+        Ecosystem::Java | Ecosystem::Clojure => Some("repo1.maven.org"),
+        Ecosystem::Node => Some("registry.npmjs.org"),
+        Ecosystem::OCaml => Some("raw.githubusercontent.com"),
+        Ecosystem::DotNet => Some("api.nuget.org"),
+        Ecosystem::Conda | Ecosystem::Deno | Ecosystem::Go | Ecosystem::Ruby | Ecosystem::Php => {
+            None
+        }
+    }
+}
+
+/// Every registry host queried by `--online` (including the deps.dev fallback),
+/// in a stable order.
+pub fn hosts() -> Vec<&'static str> {
+    let mut hosts: Vec<&'static str> = [
+        Ecosystem::Rust,
+        Ecosystem::Python,
+        Ecosystem::Java,
+        Ecosystem::Node,
+        Ecosystem::OCaml,
+        Ecosystem::DotNet,
+    ]
+    .iter()
+    .filter_map(host_for)
+    .chain([depsdev::HOST])
+    .collect();
+    hosts.dedup();
+    hosts
+}
+
+/// One semaphore per registry host, so a scan dominated by one ecosystem cannot
+/// flood that registry while the others sit idle, plus one shared by all hosts
+/// that caps the total number of requests in flight.
+#[derive(Clone)]
+pub struct HostLimits {
+    semaphores: HashMap<&'static str, Arc<Semaphore>>,
+    jobs: Arc<Semaphore>,
+}
+
+impl HostLimits {
+    /// Allow at most `per_host` concurrent requests to each registry host and
+    /// `jobs` in total (each minimum 1).
+    pub fn new(per_host: usize, jobs: usize) -> Self {
+        let per_host = per_host.max(1);
+        let semaphores = hosts()
+            .into_iter()
+            .map(|host| (host, Arc::new(Semaphore::new(per_host))))
+            .collect();
+        Self {
+            semaphores,
+            jobs: Arc::new(Semaphore::new(jobs.max(1))),
+        }
+    }
+
+    /// Semaphore shared by every lookup (`--jobs`).
+    pub fn jobs(&self) -> Arc<Semaphore> {
+        self.jobs.clone()
+    }
+
+    /// Semaphore guarding the registry host for `ecosystem`.
+    pub fn semaphore(&self, ecosystem: &Ecosystem) -> Option<Arc<Semaphore>> {
+        host_for(ecosystem).and_then(|host| self.host_semaphore(host))
+    }
+
+    /// Semaphore guarding `host` (one of [`hosts`]).
+    pub fn host_semaphore(&self, host: &str) -> Option<Arc<Semaphore>> {
+        self.semaphores.get(host).cloned()
+    }
+}
+
+/// A registry answered with a non-success HTTP status.
+#[derive(Debug)]
+pub struct HttpStatus(pub StatusCode);
+
+impl std::fmt::Display for HttpStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "registry returned HTTP {}", self.0)
+    }
+}
+
+impl std::error::Error for HttpStatus {}
+
+/// Pass a successful response through, `None` for a 404 (the registry does not
+/// know the package); turn any other status into [`HttpStatus`].
+pub fn check_status(response: Response) -> Result<Option<Response>> {
+    match response.status() {
+        status if status.is_success() => Ok(Some(response)),
+        StatusCode::NOT_FOUND => Ok(None),
+        status => Err(HttpStatus(status).into()),
+    }
+}
+
+/// Outcome counters for one `--online` enrichment pass, e.g.
+/// `online: 312 requests, 280 ok, 20 not-found, 12 failed, 45 cache hits`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+pub struct OnlineStats {
+    /// Registry lookups issued.
+    pub requests: usize,
+    /// Lookups answered with a license.
+    pub ok: usize,
+    /// Lookups that found no license: the package is unknown (HTTP 404) or its
+    /// metadata has no license field.
+    pub not_found: usize,
+    /// Lookups that hit a network error, another HTTP error, or an unreadable body.
+    pub failed: usize,
+    /// Dependencies skipped because their license already came from a local cache
+    /// or the registry lookup cache already held the answer.
+    pub cache_hits: usize,
+}
+
+impl OnlineStats {
+    /// Count the result of one `fetch_license` call.
+    pub fn record(&mut self, result: &Result<Option<String>>) {
+        self.requests += 1;
+        match result {
+            Ok(Some(_)) => self.ok += 1,
+            Ok(None) => self.not_found += 1,
+            Err(_) => self.failed += 1,
+        }
+    }
+
+    /// Add another pass's counters (e.g. from another workspace project).
+    pub fn merge(&mut self, other: &OnlineStats) {
+        self.requests += other.requests;
+        self.ok += other.ok;
+        self.not_found += other.not_found;
+        self.failed += other.failed;
+        self.cache_hits += other.cache_hits;
+    }
+}
+
+impl std::fmt::Display for OnlineStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "online: {} requests, {} ok, {} not-found, {} failed, {} cache hits",
+            self.requests, self.ok, self.not_found, self.failed, self.cache_hits
+        )
+    }
+}
+
+/// Serve `routes` (path → status and JSON body) on a local port, one
+/// response per connection, and return the base URL and the paths requested.
+#[cfg(test)]
+pub async fn mock_registry(
+    routes: &'static [(&'static str, u16, &'static str)],
+) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let handle = tokio::spawn(async move {
+        let mut paths = Vec::new();
+        for _ in 0..routes.len() {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            let head = String::from_utf8_lossy(&request).to_string();
+            let path = head.split_whitespace().nth(1).unwrap().to_string();
+            let (status, body) = routes
+                .iter()
+                .find(|(p, ..)| *p == path)
+                .map(|(_, status, body)| (*status, *body))
+                .unwrap_or((500, "{}"));
+            let response = format!(
+                "HTTP/1.1 {} X\r\nContent-Type: application/json\r\n\
+                 Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            paths.push(path);
+        }
+        paths
+    });
+    (base, handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_online_stats_count_mixed_responses() {
+        // One lookup per route, answered by status as a real registry would
+        let (base, server) = mock_registry(&[
+            (
+                "/licensed/1.0.0.json",
+                200,
+                r#"{"catalogEntry": {"licenseExpression": "MIT"}}"#,
+            ),
+            ("/unlicensed/1.0.0.json", 200, r#"{"catalogEntry": {}}"#),
+            ("/unknown/1.0.0.json", 404, r#"{"error": "not found"}"#),
+            ("/broken/1.0.0.json", 500, r#"{"error": "internal"}"#),
+            ("/garbled/1.0.0.json", 200, "<html>"),
+        ])
+        .await;
+        let client = Client::new();
+        let mut stats = OnlineStats {
+            cache_hits: 3,
+            ..OnlineStats::default()
+        };
+        for name in ["licensed", "unlicensed", "unknown", "broken", "garbled"] {
+            stats.record(&nuget::fetch_license_from(&client, &base, name, "1.0.0").await);
+        }
+        assert_eq!(server.await.unwrap().len(), 5);
+
+        assert_eq!(
+            stats,
+            OnlineStats {
+                requests: 5,
+                ok: 1,
+                not_found: 2,
+                failed: 2,
+                cache_hits: 3,
+            }
+        );
+        assert_eq!(
+            stats.to_string(),
+            "online: 5 requests, 1 ok, 2 not-found, 2 failed, 3 cache hits"
+        );
+
+        let mut total = OnlineStats::default();
+        total.merge(&stats);
+        total.merge(&stats);
+        assert_eq!(total.requests, 10);
+    }
+
+    #[tokio::test]
+    async fn test_client_applies_timeout_and_user_agent() {
+        use tokio::io::AsyncReadExt;
+
+        // A server that reads the request and never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = vec![0; 4096];
+            let n = socket.read(&mut request).await.unwrap();
+            tx.send(String::from_utf8_lossy(&request[..n]).to_lowercase())
+                .unwrap();
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        });
+
+        let client = client(Duration::from_millis(200), "corp-proxy-friendly/1.0").unwrap();
+        let started = std::time::Instant::now();
+        let err = client
+            .get(format!("http://{}/", addr))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(err.is_timeout());
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(rx
+            .await
+            .unwrap()
+            .contains("user-agent: corp-proxy-friendly/1.0"));
+        server.abort();
+    }
+
+    #[test]
+    fn test_each_host_has_its_own_cap() {
+        let limits = HostLimits::new(2, DEFAULT_JOBS);
+        let npm = limits.semaphore(&Ecosystem::Node).unwrap();
+        let crates = limits.semaphore(&Ecosystem::Rust).unwrap();
+
+        let _a = npm.clone().try_acquire_owned().unwrap();
+        let _b = npm.clone().try_acquire_owned().unwrap();
+        // npm is saturated, crates.io is unaffected
+        assert!(npm.clone().try_acquire_owned().is_err());
+        assert!(crates.try_acquire_owned().is_ok());
+        // Same host → same semaphore
+        assert_eq!(
+            limits
+                .semaphore(&Ecosystem::Node)
+                .unwrap()
+                .available_permits(),
+            0
+        );
+        assert!(limits.semaphore(&Ecosystem::Conda).is_none());
+    }
+
+    #[test]
+    fn test_jobs_cap_is_shared_by_all_hosts() {
+        let limits = HostLimits::new(DEFAULT_CONCURRENCY_PER_HOST, 2);
+        let _a = limits.jobs().try_acquire_owned().unwrap();
+        // Clones (one per workspace project) draw from the same permits
+        let _b = limits.clone().jobs().try_acquire_owned().unwrap();
+        assert!(limits.jobs().try_acquire_owned().is_err());
+    }
+
+    #[test]
+    fn test_zero_per_host_is_clamped() {
+        let limits = HostLimits::new(0, 0);
+        let npm = limits.semaphore(&Ecosystem::Node).unwrap();
+        assert_eq!(npm.available_permits(), 1);
+        assert_eq!(limits.jobs().available_permits(), 1);
+    }
+}
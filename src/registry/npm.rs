@@ -1,5 +1,6 @@
 use anyhow::Result;
 use reqwest::Client;
+use serde_json::Value;
 
 /// Fetch the license for an npm package from the npm registry.
 pub async fn fetch_license(client: &Client, name: &str, version: &str) -> Result<Option<String>> {
@@ -14,14 +15,12 @@ pub async fn fetch_license(client: &Client, name: &str, version: &str) -> Result
 
     let response = client
         .get(&url)
-        .header("User-Agent", "license-checkr/0.1.0")
         .header("Accept", "application/json")
         .send()
         .await?;
-
-    if !response.status().is_success() {
+    let Some(response) = super::check_status(response)? else {
         return Ok(None);
-    }
+    };
 
     let data: serde_json::Value = response.json().await?;
 
@@ -35,17 +34,69 @@ pub async fn fetch_license(client: &Client, name: &str, version: &str) -> Result
         if let Some(ver) = latest {
             data.get("versions")
                 .and_then(|vs| vs.get(ver))
-                .and_then(|v| v.get("license"))
-                .and_then(|l| l.as_str())
-                .map(str::to_string)
+                .and_then(license_field)
         } else {
             None
         }
     } else {
-        data.get("license")
-            .and_then(|l| l.as_str())
-            .map(str::to_string)
+        license_field(&data)
     };
 
     Ok(license)
 }
+
+/// License declared by a `package.json` (or a registry/lock entry shaped like
+/// one).
+///
+/// Accepts the SPDX string form, the legacy `{ "type": "MIT", "url": ... }`
+/// object, and the deprecated `licenses` array, whose entries are joined with
+/// ` OR `.
+pub fn license_field(manifest: &Value) -> Option<String> {
+    fn single(value: &Value) -> Option<String> {
+        match value {
+            Value::String(s) => Some(s.trim().to_string()),
+            Value::Object(o) => o.get("type").and_then(single),
+            _ => None,
+        }
+        .filter(|s| !s.is_empty())
+    }
+
+    if let Some(license) = manifest.get("license").and_then(single) {
+        return Some(license);
+    }
+    let licenses: Vec<String> = manifest
+        .get("licenses")
+        .and_then(Value::as_array)?
+        .iter()
+        .filter_map(single)
+        .collect();
+    (!licenses.is_empty()).then(|| licenses.join(" OR "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_license_field_shapes() {
+        assert_eq!(
+            license_field(&json!({"license": "MIT"})).as_deref(),
+            Some("MIT")
+        );
+        assert_eq!(
+            license_field(&json!({"license": {"type": "ISC", "url": "https://x"}})).as_deref(),
+            Some("ISC")
+        );
+        assert_eq!(
+            license_field(&json!({"licenses": [
+                {"type": "MIT", "url": "https://x"},
+                {"type": "Apache-2.0", "url": "https://y"}
+            ]}))
+            .as_deref(),
+            Some("MIT OR Apache-2.0")
+        );
+        assert_eq!(license_field(&json!({"license": ""})), None);
+        assert_eq!(license_field(&json!({"name": "x"})), None);
+    }
+}
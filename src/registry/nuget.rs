@@ -0,0 +1,171 @@
+use std::io::Read;
+
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use reqwest::Client;
+use serde_json::Value;
+
+/// NuGet v3 registration hive with SemVer 2.0 packages; responses are gzipped.
+/// Tests point [`fetch_license_from`] at a local server instead.
+const REGISTRATION: &str = "https://api.nuget.org/v3/registration5-gz-semver2";
+
+/// Fetch the license for a NuGet package from nuget.org (see [`fetch_license_from`]).
+pub async fn fetch_license(client: &Client, name: &str, version: &str) -> Result<Option<String>> {
+    fetch_license_from(client, REGISTRATION, name, version).await
+}
+
+/// Fetch the license for a NuGet package from its registration leaf in the
+/// registration hive at `base`; `None` when the package or version is unknown (404).
+///
+/// Prefers the package's `licenseExpression`; older packages only have a
+/// `licenseUrl`, which is mapped to an SPDX id where the URL names one.
+/// Version ranges (`[1.0,2.0)`) and floating versions (`1.*`) are not looked up.
+pub async fn fetch_license_from(
+    client: &Client,
+    base: &str,
+    name: &str,
+    version: &str,
+) -> Result<Option<String>> {
+    if version.is_empty() || version.contains(['[', '(', '*', ',']) {
+        return Ok(None);
+    }
+
+    let url = format!(
+        "{}/{}/{}.json",
+        base,
+        name.to_lowercase(),
+        version.to_lowercase()
+    );
+    let Some(leaf) = get_json(client, &url).await? else {
+        return Ok(None);
+    };
+
+    // The leaf links to its catalog entry; some responses inline it instead
+    let entry = match leaf.get("catalogEntry") {
+        Some(Value::String(entry_url)) => match get_json(client, entry_url).await? {
+            Some(entry) => entry,
+            None => return Ok(None),
+        },
+        Some(entry) => entry.clone(),
+        None => return Ok(None),
+    };
+    Ok(license_from_catalog_entry(&entry))
+}
+
+/// GET `url` as JSON, decompressing a gzipped body; `None` on a 404.
+async fn get_json(client: &Client, url: &str) -> Result<Option<Value>> {
+    let response = client
+        .get(url)
+        .header("Accept-Encoding", "gzip")
+        .send()
+        .await?;
+    let Some(response) = super::check_status(response)? else {
+        return Ok(None);
+    };
+
+    let bytes = response.bytes().await?;
+    // Check the gzip magic rather than `Content-Encoding`, which CDNs may drop
+    if bytes.starts_with(&[0x1f, 0x8b]) {
+        let mut body = String::new();
+        GzDecoder::new(&bytes[..])
+            .read_to_string(&mut body)
+            .with_context(|| format!("Failed to decompress {}", url))?;
+        Ok(Some(serde_json::from_str(&body)?))
+    } else {
+        Ok(Some(serde_json::from_slice(&bytes)?))
+    }
+}
+
+/// `licenseExpression` of a catalog entry, else an SPDX id derived from `licenseUrl`.
+fn license_from_catalog_entry(entry: &Value) -> Option<String> {
+    let field = |key: &str| {
+        entry
+            .get(key)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    };
+    field("licenseExpression")
+        .map(str::to_string)
+        .or_else(|| field("licenseUrl").and_then(license_from_url))
+}
+
+/// SPDX id named by a well-known license URL, e.g. `https://licenses.nuget.org/MIT`
+/// or `https://opensource.org/licenses/Apache-2.0`; `None` for anything else
+/// (project-specific LICENSE files, `https://aka.ms/deprecateLicenseUrl`, …).
+fn license_from_url(url: &str) -> Option<String> {
+    let url = url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("www.")
+        .trim_end_matches('/');
+    if let Some(id) = url
+        .strip_prefix("licenses.nuget.org/")
+        .or_else(|| url.strip_prefix("opensource.org/licenses/"))
+    {
+        let id = id.trim_end_matches(".php").trim_end_matches(".html");
+        return (!id.is_empty()).then(|| id.replace("%20", " "));
+    }
+    let known = match url {
+        "apache.org/licenses/LICENSE-2.0" | "apache.org/licenses/LICENSE-2.0.txt" => "Apache-2.0",
+        "gnu.org/licenses/gpl-3.0.html" | "gnu.org/licenses/gpl-3.0.txt" => "GPL-3.0",
+        "gnu.org/licenses/lgpl-3.0.html" | "gnu.org/licenses/lgpl-3.0.txt" => "LGPL-3.0",
+        "mozilla.org/MPL/2.0" => "MPL-2.0",
+        _ => return None,
+    };
+    Some(known.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::mock_registry;
+
+    #[tokio::test]
+    async fn test_unknown_package_is_none() {
+        let (base, server) = mock_registry(&[(
+            "/no.such.package/1.0.0.json",
+            404,
+            r#"<Error><Code>BlobNotFound</Code></Error>"#,
+        )])
+        .await;
+
+        let found = fetch_license_from(&Client::new(), &base, "No.Such.Package", "1.0.0")
+            .await
+            .unwrap();
+        assert_eq!(found, None);
+        assert_eq!(server.await.unwrap(), ["/no.such.package/1.0.0.json"]);
+    }
+
+    #[test]
+    fn test_license_from_catalog_entry() {
+        let entry = serde_json::json!({
+            "id": "Newtonsoft.Json",
+            "licenseExpression": "MIT",
+            "licenseUrl": "https://licenses.nuget.org/MIT"
+        });
+        assert_eq!(license_from_catalog_entry(&entry).as_deref(), Some("MIT"));
+
+        // Older packages: only a license URL
+        let entry = serde_json::json!({
+            "licenseExpression": "",
+            "licenseUrl": "http://www.apache.org/licenses/LICENSE-2.0"
+        });
+        assert_eq!(
+            license_from_catalog_entry(&entry).as_deref(),
+            Some("Apache-2.0")
+        );
+        let entry = serde_json::json!({
+            "licenseUrl": "https://licenses.nuget.org/MIT%20OR%20Apache-2.0"
+        });
+        assert_eq!(
+            license_from_catalog_entry(&entry).as_deref(),
+            Some("MIT OR Apache-2.0")
+        );
+
+        let entry = serde_json::json!({
+            "licenseUrl": "https://github.com/acme/lib/blob/main/LICENSE"
+        });
+        assert_eq!(license_from_catalog_entry(&entry), None);
+    }
+}
@@ -0,0 +1,119 @@
+use anyhow::Result;
+use regex::Regex;
+use reqwest::Client;
+
+use crate::license::spdx::normalize;
+use crate::version::compare_versions;
+
+/// Raw package files of the default opam repository.
+const OPAM_REPOSITORY: &str =
+    "https://raw.githubusercontent.com/ocaml/opam-repository/master/packages";
+
+/// Fetch the license for an opam package from the opam repository metadata.
+///
+/// `version` is an exact version or, as recorded by the OCaml analyzer, a
+/// constraint such as `>= 5.0` / `*`; constraints resolve to the newest release.
+pub async fn fetch_license(client: &Client, name: &str, version: &str) -> Result<Option<String>> {
+    let version = match exact_version(version) {
+        Some(v) => v.to_string(),
+        None => match latest_version(client, name).await? {
+            Some(v) => v,
+            None => return Ok(None),
+        },
+    };
+
+    let url = format!("{}/{}/{}.{}/opam", OPAM_REPOSITORY, name, name, version);
+    let response = client.get(&url).send().await?;
+    let Some(response) = super::check_status(response)? else {
+        return Ok(None);
+    };
+
+    let opam = response.text().await?;
+    Ok(extract_license_from_opam(&opam))
+}
+
+/// `version` when it names a single release rather than a constraint.
+fn exact_version(version: &str) -> Option<&str> {
+    let exact = version.trim().trim_start_matches("= ");
+    (!exact.is_empty() && exact != "*" && !exact.contains(' ')).then_some(exact)
+}
+
+/// Newest release of `name`, from the opam repository's package directory listing.
+async fn latest_version(client: &Client, name: &str) -> Result<Option<String>> {
+    let url = format!(
+        "https://api.github.com/repos/ocaml/opam-repository/contents/packages/{}",
+        name
+    );
+    let response = client
+        .get(&url)
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await?;
+    let Some(response) = super::check_status(response)? else {
+        return Ok(None);
+    };
+
+    let entries: Vec<serde_json::Value> = response.json().await?;
+    let prefix = format!("{}.", name);
+    Ok(entries
+        .iter()
+        .filter_map(|e| e.get("name")?.as_str()?.strip_prefix(&prefix))
+        .max_by(|a, b| compare_versions(a, b))
+        .map(str::to_string))
+}
+
+/// Extract the `license:` field of an opam file. Lists of licenses
+/// (`license: ["MIT" "ISC"]`) all apply and are joined with `AND`; legacy
+/// spellings such as `LGPL-2.1-with-OCaml-exception` become SPDX `WITH` expressions.
+fn extract_license_from_opam(opam: &str) -> Option<String> {
+    let field = Regex::new(r#"(?m)^license:\s*(\[[^\]]*\]|"[^"]*")"#).ok()?;
+    let value = field.captures(opam)?.get(1)?.as_str();
+    let licenses: Vec<String> = value
+        .split('"')
+        .skip(1)
+        .step_by(2)
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(normalize)
+        .collect();
+    (!licenses.is_empty()).then(|| licenses.join(" AND "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_license_from_opam() {
+        let opam = r#"opam-version: "2.0"
+maintainer: "dev@example.org"
+license: "LGPL-2.1-only WITH OCaml-LGPL-linking-exception"
+depends: ["ocaml" "dune"]"#;
+        assert_eq!(
+            extract_license_from_opam(opam).as_deref(),
+            Some("LGPL-2.1-only WITH OCaml-LGPL-linking-exception")
+        );
+        assert_eq!(
+            extract_license_from_opam("license: [\"MIT\" \"ISC\"]").as_deref(),
+            Some("MIT AND ISC")
+        );
+        assert_eq!(
+            extract_license_from_opam("license: \"LGPL-2.1-with-OCaml-exception\"").as_deref(),
+            Some("LGPL-2.1 WITH OCaml-LGPL-linking-exception")
+        );
+        assert_eq!(extract_license_from_opam("opam-version: \"2.0\""), None);
+    }
+
+    #[test]
+    fn test_constraints_resolve_to_newest_release() {
+        assert_eq!(exact_version("5.6.1"), Some("5.6.1"));
+        assert_eq!(exact_version(">= 5.0"), None);
+        assert_eq!(exact_version("*"), None);
+        assert_eq!(
+            ["1.9.2", "1.10.0", "1.10.0~beta1"]
+                .into_iter()
+                .max_by(|a, b| compare_versions(a, b)),
+            Some("1.10.0")
+        );
+    }
+}
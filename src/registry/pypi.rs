@@ -1,5 +1,8 @@
 use anyhow::Result;
 use reqwest::Client;
+use serde_json::Value;
+
+use crate::license::spdx::normalize;
 
 /// Fetch the license for a Python package from PyPI.
 pub async fn fetch_license(client: &Client, name: &str, version: &str) -> Result<Option<String>> {
@@ -9,23 +12,81 @@ pub async fn fetch_license(client: &Client, name: &str, version: &str) -> Result
         format!("https://pypi.org/pypi/{}/{}/json", name, version)
     };
 
-    let response = client
-        .get(&url)
-        .header("User-Agent", "license-checkr/0.1.0")
-        .send()
-        .await?;
-
-    if !response.status().is_success() {
+    let response = client.get(&url).send().await?;
+    let Some(response) = super::check_status(response)? else {
         return Ok(None);
-    }
+    };
 
-    let data: serde_json::Value = response.json().await?;
-    let license = data
-        .get("info")
-        .and_then(|i| i.get("license"))
+    let data: Value = response.json().await?;
+    Ok(data.get("info").and_then(license_from_info))
+}
+
+/// License from a PyPI `info` object: `info.license` when set, otherwise the
+/// most specific `License ::` trove classifier.
+fn license_from_info(info: &Value) -> Option<String> {
+    let license = info
+        .get("license")
         .and_then(|l| l.as_str())
-        .filter(|s| !s.is_empty())
-        .map(str::to_string);
+        .map(str::trim)
+        .filter(|s| !s.is_empty() && !s.eq_ignore_ascii_case("UNKNOWN"));
+    if let Some(license) = license {
+        return Some(license.to_string());
+    }
+
+    // "License :: OSI Approved :: MIT License" beats "License :: OSI Approved"
+    let segments = info
+        .get("classifiers")
+        .and_then(|c| c.as_array())?
+        .iter()
+        .filter_map(|c| c.as_str()?.strip_prefix("License :: "))
+        .map(|c| c.split(" :: ").collect::<Vec<_>>())
+        .filter(|segments| segments != &["OSI Approved"])
+        .reduce(|best, next| if next.len() > best.len() { next } else { best })?;
+    segments.last().map(|name| classifier_to_spdx(name))
+}
+
+/// Map the last segment of a `License ::` trove classifier to an SPDX id.
+pub fn classifier_to_spdx(name: &str) -> String {
+    match name {
+        "MIT License" => "MIT",
+        "Apache Software License" => "Apache-2.0",
+        "BSD License" => "BSD-3-Clause",
+        "ISC License (ISCL)" => "ISC",
+        "Python Software Foundation License" => "PSF-2.0",
+        "Mozilla Public License 2.0 (MPL 2.0)" => "MPL-2.0",
+        "GNU General Public License v2 (GPLv2)" => "GPL-2.0",
+        "GNU General Public License v3 (GPLv3)" => "GPL-3.0",
+        "GNU Lesser General Public License v2 (LGPLv2)" => "LGPL-2.0",
+        "GNU Lesser General Public License v3 (LGPLv3)" => "LGPL-3.0",
+        "GNU Affero General Public License v3" => "AGPL-3.0",
+        "The Unlicense (Unlicense)" => "Unlicense",
+        other => return normalize(other),
+    }
+    .to_string()
+}
 
-    Ok(license)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_license_from_classifiers_when_license_empty() {
+        let info = json!({
+            "license": "",
+            "classifiers": [
+                "Programming Language :: Python :: 3",
+                "License :: OSI Approved",
+                "License :: OSI Approved :: MIT License"
+            ]
+        });
+        assert_eq!(license_from_info(&info).as_deref(), Some("MIT"));
+
+        let info =
+            json!({ "license": "BSD", "classifiers": ["License :: OSI Approved :: MIT License"] });
+        assert_eq!(license_from_info(&info).as_deref(), Some("BSD"));
+
+        let info = json!({ "license": null, "classifiers": ["License :: OSI Approved"] });
+        assert_eq!(license_from_info(&info), None);
+    }
 }
@@ -0,0 +1,229 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::license::expression::{tokenize_spdx, Token};
+use crate::license::spdx::classify_spdx_id;
+use crate::models::{Dependency, Ecosystem, LicenseRisk, ProjectScan};
+
+/// A CycloneDX 1.5 JSON BOM with one `library` component per dependency.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Bom<'a> {
+    bom_format: &'static str,
+    spec_version: &'static str,
+    version: u32,
+    metadata: Metadata<'a>,
+    components: Vec<Component<'a>>,
+}
+
+#[derive(Serialize)]
+struct Metadata<'a> {
+    timestamp: &'a str,
+    tools: Tools,
+}
+
+#[derive(Serialize)]
+struct Tools {
+    components: [Tool; 1],
+}
+
+#[derive(Serialize)]
+struct Tool {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    name: &'static str,
+    version: &'static str,
+}
+
+#[derive(Serialize)]
+struct Component<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    name: &'a str,
+    version: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    purl: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    licenses: Vec<LicenseChoice<'a>>,
+    properties: Vec<Property>,
+}
+
+/// A component license: a known SPDX id, a free-form name, or an SPDX expression.
+#[derive(Serialize)]
+enum LicenseChoice<'a> {
+    #[serde(rename = "license")]
+    License(License<'a>),
+    #[serde(rename = "expression")]
+    Expression(&'a str),
+}
+
+#[derive(Serialize)]
+enum License<'a> {
+    #[serde(rename = "id")]
+    Id(&'a str),
+    #[serde(rename = "name")]
+    Name(&'a str),
+}
+
+#[derive(Serialize)]
+struct Property {
+    name: &'static str,
+    value: String,
+}
+
+/// Serialize `deps` as a pretty-printed CycloneDX 1.5 JSON document.
+///
+/// Each dependency keeps its policy verdict as the `license-checkr:verdict`
+/// property. Workspace scans list the dependencies of every project; the same
+/// package used by two projects appears twice, since each has its own verdict.
+pub fn to_cyclonedx<'a>(
+    deps: impl IntoIterator<Item = &'a Dependency>,
+    scanned_at: &str,
+) -> Result<String> {
+    let bom = Bom {
+        bom_format: "CycloneDX",
+        spec_version: "1.5",
+        version: 1,
+        metadata: Metadata {
+            timestamp: scanned_at,
+            tools: Tools {
+                components: [Tool {
+                    kind: "application",
+                    name: "license-checkr",
+                    version: env!("CARGO_PKG_VERSION"),
+                }],
+            },
+        },
+        components: deps.into_iter().map(component).collect(),
+    };
+    Ok(serde_json::to_string_pretty(&bom)?)
+}
+
+fn component(dep: &Dependency) -> Component<'_> {
+    Component {
+        kind: "library",
+        name: &dep.name,
+        version: &dep.version,
+        purl: purl(dep),
+        licenses: license_choice(dep).into_iter().collect(),
+        properties: vec![Property {
+            name: "license-checkr:verdict",
+            value: dep.verdict.to_string(),
+        }],
+    }
+}
+
+/// The SPDX license when it is a single known id or an expression; anything
+/// else (unknown ids, raw registry strings) is reported by name.
+fn license_choice(dep: &Dependency) -> Option<LicenseChoice<'_>> {
+    if let Some(spdx) = dep.license_spdx.as_deref() {
+        return Some(match tokenize_spdx(spdx).as_slice() {
+            [Token::Id(id)] if classify_spdx_id(id) != LicenseRisk::Unknown => {
+                LicenseChoice::License(License::Id(spdx.trim()))
+            }
+            [Token::Id(_)] => LicenseChoice::License(License::Name(spdx)),
+            _ => LicenseChoice::Expression(spdx),
+        });
+    }
+    dep.license_raw
+        .as_deref()
+        .map(|raw| LicenseChoice::License(License::Name(raw)))
+}
+
+/// Package URL of a dependency, e.g. `pkg:cargo/serde@1.0.200` or
+/// `pkg:maven/com.google.guava/guava@32.1.2-jre`. `None` for ecosystems
+/// without a purl type (opam, JSR). Unpinned `*` versions are left out.
+pub fn purl(dep: &Dependency) -> Option<String> {
+    let (kind, name) = match dep.ecosystem {
+        Ecosystem::Rust => ("cargo", dep.name.clone()),
+        Ecosystem::Python => ("pypi", dep.name.to_lowercase().replace('_', "-")),
+        Ecosystem::Java | Ecosystem::Clojure => ("maven", dep.name.replacen(':', "/", 1)),
+        Ecosystem::Node => ("npm", dep.name.replacen('@', "%40", 1)),
+        Ecosystem::DotNet => ("nuget", dep.name.clone()),
+        Ecosystem::Conda => ("conda", dep.name.clone()),
+        Ecosystem::Go => ("golang", dep.name.clone()),
+        Ecosystem::Ruby => ("gem", dep.name.clone()),
+        Ecosystem::Php => ("composer", dep.name.clone()),
+        Ecosystem::OCaml | Ecosystem::Deno => return None,
+    };
+    let mut purl = format!("pkg:{}/{}", kind, name);
+    if !dep.version.is_empty() && dep.version != "*" {
+        purl.push('@');
+        purl.push_str(&dep.version);
+    }
+    Some(purl)
+}
+
+/// Print the CycloneDX BOM to stdout.
+pub fn render(deps: &[Dependency], scanned_at: &str) -> Result<()> {
+    println!("{}", to_cyclonedx(deps, scanned_at)?);
+    Ok(())
+}
+
+/// Print one CycloneDX BOM covering every project.
+pub fn render_workspace(projects: &[ProjectScan], scanned_at: &str) -> Result<()> {
+    println!(
+        "{}",
+        to_cyclonedx(projects.iter().flat_map(|p| &p.deps), scanned_at)?
+    );
+    Ok(())
+}
+
+/// Write the [`render`] output to `path` instead of stdout.
+pub fn write(deps: &[Dependency], scanned_at: &str, path: &Path) -> Result<()> {
+    std::fs::write(path, to_cyclonedx(deps, scanned_at)?)
+        .with_context(|| format!("Failed to write CycloneDX report to {}", path.display()))
+}
+
+/// Write the [`render_workspace`] output to `path` instead of stdout.
+pub fn write_workspace(projects: &[ProjectScan], scanned_at: &str, path: &Path) -> Result<()> {
+    let bom = to_cyclonedx(projects.iter().flat_map(|p| &p.deps), scanned_at)?;
+    std::fs::write(path, bom)
+        .with_context(|| format!("Failed to write CycloneDX report to {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{dep, PolicyVerdict};
+
+    #[test]
+    fn test_cyclonedx_components() {
+        let deps = vec![
+            dep("serde", Some("MIT OR Apache-2.0"), PolicyVerdict::Pass)
+                .with_ecosystem(Ecosystem::Rust),
+            dep("@babel/core", Some("MIT"), PolicyVerdict::Pass).with_ecosystem(Ecosystem::Node),
+            dep("com.google.guava:guava", None, PolicyVerdict::Pass)
+                .with_ecosystem(Ecosystem::Java),
+        ];
+        let json: serde_json::Value =
+            serde_json::from_str(&to_cyclonedx(&deps, "2024-03-14T09:21:00Z").unwrap()).unwrap();
+
+        assert_eq!(json["bomFormat"], "CycloneDX");
+        assert_eq!(json["specVersion"], "1.5");
+        assert_eq!(json["metadata"]["timestamp"], "2024-03-14T09:21:00Z");
+        let components = json["components"].as_array().unwrap();
+        assert_eq!(components.len(), 3);
+
+        assert_eq!(components[0]["type"], "library");
+        assert_eq!(components[0]["purl"], "pkg:cargo/serde@1.0.0");
+        assert_eq!(
+            components[0]["licenses"][0]["expression"],
+            "MIT OR Apache-2.0"
+        );
+        assert_eq!(components[1]["purl"], "pkg:npm/%40babel/core@1.0.0");
+        assert_eq!(components[1]["licenses"][0]["license"]["id"], "MIT");
+        assert_eq!(
+            components[1]["properties"][0]["name"],
+            "license-checkr:verdict"
+        );
+        assert_eq!(components[1]["properties"][0]["value"], "pass");
+        assert_eq!(
+            components[2]["purl"],
+            "pkg:maven/com.google.guava/guava@1.0.0"
+        );
+        assert!(components[2].get("licenses").is_none());
+    }
+}
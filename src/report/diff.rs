@@ -0,0 +1,231 @@
+//! Presentation of a [`BaselineDiff`] for `--baseline`: colored, aligned text for
+//! the terminal and a Markdown variant for PR comments (`--baseline-markdown`).
+
+use colored::*;
+
+use crate::baseline::BaselineDiff;
+use crate::models::Dependency;
+
+/// Kind of a rendered diff line, which decides its prefix and color.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Change {
+    Added,
+    Removed,
+    Changed,
+    Relicensed,
+}
+
+impl Change {
+    fn prefix(self) -> char {
+        match self {
+            Change::Added => '+',
+            Change::Removed => '-',
+            Change::Changed => '~',
+            Change::Relicensed => '!',
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Change::Added => "Added",
+            Change::Removed => "Removed",
+            Change::Changed => "Changed",
+            Change::Relicensed => "Relicensed",
+        }
+    }
+}
+
+/// One group of the diff: its kind and `(name@version, details)` rows.
+type Group = (Change, Vec<(String, String)>);
+
+/// Render the diff for the terminal: a counts line, then one colored group per
+/// change type (green `+`, red `-`, yellow `~`). `quiet` keeps the counts line only.
+///
+/// Colors follow the global `colored` setting, so `--no-color` and `NO_COLOR`
+/// yield plain text.
+pub fn render_text(diff: &BaselineDiff, quiet: bool) -> String {
+    if diff.is_empty() {
+        return "Baseline: no changes\n".to_string();
+    }
+    let mut out = format!("Baseline: {}\n", counts(diff));
+    if quiet {
+        return out;
+    }
+
+    let groups = groups(diff);
+    let width = label_width(&groups);
+    for (change, rows) in &groups {
+        out.push_str(&format!("\n {} ({})\n", change.title().bold(), rows.len()));
+        for (label, details) in rows {
+            let line = format!("  {} {:<width$}  {}", change.prefix(), label, details);
+            let line = match change {
+                Change::Added => line.green(),
+                Change::Removed => line.red(),
+                Change::Changed => line.yellow(),
+                Change::Relicensed => line.magenta(),
+            };
+            out.push_str(&format!("{}\n", line));
+        }
+    }
+    out
+}
+
+/// Render the diff as Markdown for a PR comment: a heading with the counts and
+/// a fenced `diff` block, so `+` / `-` lines are highlighted by the forge.
+pub fn render_markdown(diff: &BaselineDiff) -> String {
+    let mut out = String::from("### License baseline\n\n");
+    if diff.is_empty() {
+        out.push_str("No changes.\n");
+        return out;
+    }
+    out.push_str(&format!("**{}**\n\n```diff\n", counts(diff)));
+
+    let groups = groups(diff);
+    let width = label_width(&groups);
+    for (change, rows) in &groups {
+        out.push_str(&format!("@@ {} ({}) @@\n", change.title(), rows.len()));
+        for (label, details) in rows {
+            let line = format!("{} {:<width$}  {}", change.prefix(), label, details);
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+    }
+    out.push_str("```\n");
+    out
+}
+
+fn counts(diff: &BaselineDiff) -> String {
+    format!(
+        "{} added, {} removed, {} changed ({} relicensed)",
+        diff.added.len(),
+        diff.removed.len(),
+        diff.changed.len(),
+        diff.relicensed.len()
+    )
+}
+
+/// Non-empty groups in display order.
+fn groups(diff: &BaselineDiff) -> Vec<Group> {
+    let added = diff
+        .added
+        .iter()
+        .map(|d| (label(d), format!("[{}]  {}", license(d), d.verdict)))
+        .collect();
+    let removed = diff
+        .removed
+        .iter()
+        .map(|d| (label(d), format!("[{}]", license(d))))
+        .collect();
+    let changed = diff
+        .changed
+        .iter()
+        .map(|(prev, cur)| (label(cur), change_details(prev, cur)))
+        .collect();
+    let relicensed = diff
+        .relicensed
+        .iter()
+        .map(|(prev, cur)| {
+            (
+                label(cur),
+                format!("license {} → {}", license(prev), license(cur)),
+            )
+        })
+        .collect();
+
+    [
+        (Change::Added, added),
+        (Change::Removed, removed),
+        (Change::Changed, changed),
+        (Change::Relicensed, relicensed),
+    ]
+    .into_iter()
+    .filter(|(_, rows): &Group| !rows.is_empty())
+    .collect()
+}
+
+/// What differs between two versions of a dependency, e.g.
+/// `version 1.0.0 → 1.1.0, verdict warn → error`.
+fn change_details(prev: &Dependency, cur: &Dependency) -> String {
+    let mut parts = Vec::new();
+    if prev.version != cur.version {
+        parts.push(format!("version {} → {}", prev.version, cur.version));
+    }
+    if license(prev) != license(cur) {
+        parts.push(format!("license {} → {}", license(prev), license(cur)));
+    }
+    if prev.verdict != cur.verdict {
+        parts.push(format!("verdict {} → {}", prev.verdict, cur.verdict));
+    }
+    parts.join(", ")
+}
+
+fn label(dep: &Dependency) -> String {
+    format!("{}@{}", dep.name, dep.version)
+}
+
+fn license(dep: &Dependency) -> &str {
+    dep.license_spdx
+        .as_deref()
+        .or(dep.license_raw.as_deref())
+        .unwrap_or("unknown")
+}
+
+fn label_width(groups: &[Group]) -> usize {
+    groups
+        .iter()
+        .flat_map(|(_, rows)| rows.iter().map(|(label, _)| label.chars().count()))
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::baseline;
+    use crate::models::{dep, PolicyVerdict};
+
+    fn sample_diff() -> BaselineDiff {
+        let previous = vec![
+            dep("chardet", Some("LGPL-2.1"), PolicyVerdict::Warn).with_version("5.0.0"),
+            dep("request", Some("Apache-2.0"), PolicyVerdict::Pass).with_version("2.88.2"),
+        ];
+        let current = [
+            dep("chardet", Some("LGPL-2.1"), PolicyVerdict::Error).with_version("5.0.0"),
+            dep("left-pad", Some("WTFPL"), PolicyVerdict::Pass).with_version("1.3.0"),
+        ];
+        let refs: Vec<&Dependency> = current.iter().collect();
+        baseline::diff(&previous, &refs)
+    }
+
+    #[test]
+    fn test_text_groups_use_expected_prefixes() {
+        colored::control::set_override(false);
+        let text = render_text(&sample_diff(), false);
+
+        assert!(text.starts_with("Baseline: 1 added, 1 removed, 1 changed (0 relicensed)\n"));
+        assert!(text.contains(" Added (1)\n  + left-pad@1.3.0  [WTFPL]  pass\n"));
+        assert!(text.contains(" Removed (1)\n  - request@2.88.2  [Apache-2.0]\n"));
+        assert!(text.contains(" Changed (1)\n  ~ chardet@5.0.0   verdict warn → error\n"));
+    }
+
+    #[test]
+    fn test_markdown_wraps_lines_in_diff_block() {
+        let md = render_markdown(&sample_diff());
+
+        assert!(md.starts_with("### License baseline\n\n**1 added, 1 removed, 1 changed"));
+        assert!(md.contains("```diff\n@@ Added (1) @@\n+ left-pad@1.3.0  [WTFPL]  pass\n"));
+        assert!(md.contains("\n- request@2.88.2  [Apache-2.0]\n"));
+        assert!(md.contains("\n~ chardet@5.0.0   verdict warn → error\n"));
+        assert!(md.ends_with("```\n"));
+    }
+
+    #[test]
+    fn test_quiet_text_keeps_counts_only() {
+        let text = render_text(&sample_diff(), true);
+        assert_eq!(text.lines().count(), 1);
+        assert_eq!(
+            render_text(&BaselineDiff::default(), false),
+            "Baseline: no changes\n"
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use super::RenderOptions;
+use crate::license::obligations::{obligations, Obligation};
+use crate::models::{Dependency, PolicyVerdict, ProjectScan};
+use crate::registry::OnlineStats;
+
+/// Aggregate verdict counts emitted by `--report json --quiet`.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct Counts {
+    /// RFC 3339 scan time (see [`super::format_timestamp`]).
+    pub scanned_at: String,
+    pub total: usize,
+    pub pass: usize,
+    pub warn: usize,
+    pub review: usize,
+    pub error: usize,
+    /// `--online` lookup counters, when registries were queried.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub online: Option<OnlineStats>,
+}
+
+impl Counts {
+    pub fn from_deps<'a>(deps: impl IntoIterator<Item = &'a Dependency>, scanned_at: &str) -> Self {
+        let mut counts = Counts {
+            scanned_at: scanned_at.to_string(),
+            ..Counts::default()
+        };
+        for dep in deps {
+            counts.total += 1;
+            match dep.verdict {
+                PolicyVerdict::Pass => counts.pass += 1,
+                PolicyVerdict::Warn => counts.warn += 1,
+                PolicyVerdict::Review => counts.review += 1,
+                PolicyVerdict::Error => counts.error += 1,
+            }
+        }
+        counts
+    }
+}
+
+/// A dependency with the obligations of its license.
+#[derive(Serialize)]
+struct DependencyJson<'a> {
+    #[serde(flatten)]
+    dep: &'a Dependency,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    obligations: Vec<Obligation>,
+}
+
+impl<'a> DependencyJson<'a> {
+    fn all(deps: &'a [Dependency]) -> Vec<Self> {
+        deps.iter()
+            .map(|dep| DependencyJson {
+                dep,
+                obligations: dep
+                    .license_spdx
+                    .as_deref()
+                    .or(dep.license_raw.as_deref())
+                    .map(obligations)
+                    .unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+#[derive(Serialize)]
+struct ProjectScanJson<'a> {
+    project: &'a str,
+    path: String,
+    dependencies: Vec<DependencyJson<'a>>,
+}
+
+/// Print a JSON report: the full dependency array, or only [`Counts`] when `quiet`.
+pub fn render(deps: &[Dependency], opts: &RenderOptions<'_>) -> Result<()> {
+    println!("{}", to_json(deps, opts)?);
+    Ok(())
+}
+
+/// Print a workspace JSON report: one entry per project, or workspace-wide
+/// [`Counts`] when `quiet`.
+pub fn render_workspace(projects: &[ProjectScan], opts: &RenderOptions<'_>) -> Result<()> {
+    println!("{}", workspace_to_json(projects, opts)?);
+    Ok(())
+}
+
+/// Write the [`render`] output to `path` instead of stdout.
+pub fn write(deps: &[Dependency], opts: &RenderOptions<'_>, path: &Path) -> Result<()> {
+    std::fs::write(path, to_json(deps, opts)?)
+        .with_context(|| format!("Failed to write JSON report to {}", path.display()))
+}
+
+/// Write the [`render_workspace`] output to `path` instead of stdout.
+pub fn write_workspace(
+    projects: &[ProjectScan],
+    opts: &RenderOptions<'_>,
+    path: &Path,
+) -> Result<()> {
+    std::fs::write(path, workspace_to_json(projects, opts)?)
+        .with_context(|| format!("Failed to write JSON report to {}", path.display()))
+}
+
+fn to_json(deps: &[Dependency], opts: &RenderOptions<'_>) -> Result<String> {
+    if opts.quiet {
+        let counts = Counts {
+            online: opts.online_stats.copied(),
+            ..Counts::from_deps(deps, opts.scanned_at)
+        };
+        return Ok(serde_json::to_string(&counts)?);
+    }
+    Ok(serde_json::to_string_pretty(&DependencyJson::all(deps))?)
+}
+
+fn workspace_to_json(projects: &[ProjectScan], opts: &RenderOptions<'_>) -> Result<String> {
+    if opts.quiet {
+        let counts = Counts {
+            online: opts.online_stats.copied(),
+            ..Counts::from_deps(projects.iter().flat_map(|p| &p.deps), opts.scanned_at)
+        };
+        return Ok(serde_json::to_string(&counts)?);
+    }
+    let out: Vec<ProjectScanJson<'_>> = projects
+        .iter()
+        .map(|p| ProjectScanJson {
+            project: &p.name,
+            path: p.path.display().to_string(),
+            dependencies: DependencyJson::all(&p.deps),
+        })
+        .collect();
+    Ok(serde_json::to_string_pretty(&out)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::dep;
+
+    const STAMP: &str = "2024-03-14T09:21:00Z";
+
+    fn opts(quiet: bool, online_stats: Option<&OnlineStats>) -> RenderOptions<'_> {
+        RenderOptions {
+            verbose: false,
+            quiet,
+            min_risk: None,
+            sort: None,
+            columns: &[],
+            group_by: None,
+            pdf_path: Path::new("unused.pdf"),
+            pdf_branding: Default::default(),
+            output_dir: None,
+            output: None,
+            scanned_at: STAMP,
+            online_stats,
+            summary_format: None,
+        }
+    }
+
+    #[test]
+    fn test_quiet_json_is_counts_only() {
+        let deps = vec![
+            dep("a", Some("MIT"), PolicyVerdict::Pass),
+            dep("b", Some("MIT"), PolicyVerdict::Pass),
+            dep("c", Some("MIT"), PolicyVerdict::Warn),
+            dep("d", Some("MIT"), PolicyVerdict::Error),
+        ];
+
+        let quiet: serde_json::Value =
+            serde_json::from_str(&to_json(&deps, &opts(true, None)).unwrap()).unwrap();
+        assert_eq!(
+            quiet,
+            serde_json::json!({ "scanned_at": STAMP, "total": 4, "pass": 2, "warn": 1, "review": 0, "error": 1 })
+        );
+
+        let full: serde_json::Value =
+            serde_json::from_str(&to_json(&deps, &opts(false, None)).unwrap()).unwrap();
+        assert!(full.is_array());
+        assert_eq!(full.as_array().unwrap().len(), 4);
+        assert_eq!(full[0]["obligations"], serde_json::json!(["attribution"]));
+        assert_ne!(quiet, full);
+    }
+
+    #[test]
+    fn test_quiet_workspace_json_aggregates_projects() {
+        let projects = vec![
+            ProjectScan {
+                name: "api".to_string(),
+                path: "/ws/api".into(),
+                deps: vec![dep("a", Some("MIT"), PolicyVerdict::Pass)],
+            },
+            ProjectScan {
+                name: "web".to_string(),
+                path: "/ws/web".into(),
+                deps: vec![dep("b", Some("MIT"), PolicyVerdict::Error)],
+            },
+        ];
+
+        let quiet: serde_json::Value =
+            serde_json::from_str(&workspace_to_json(&projects, &opts(true, None)).unwrap())
+                .unwrap();
+        assert_eq!(
+            quiet,
+            serde_json::json!({ "scanned_at": STAMP, "total": 2, "pass": 1, "warn": 0, "review": 0, "error": 1 })
+        );
+
+        let stats = OnlineStats {
+            requests: 2,
+            ok: 1,
+            not_found: 1,
+            ..OnlineStats::default()
+        };
+        let online: serde_json::Value =
+            serde_json::from_str(&workspace_to_json(&projects, &opts(true, Some(&stats))).unwrap())
+                .unwrap();
+        assert_eq!(
+            online["online"],
+            serde_json::json!({ "requests": 2, "ok": 1, "not_found": 1, "failed": 0, "cache_hits": 0 })
+        );
+
+        let full: serde_json::Value =
+            serde_json::from_str(&workspace_to_json(&projects, &opts(false, None)).unwrap())
+                .unwrap();
+        assert_eq!(full[0]["project"], "api");
+    }
+}
@@ -0,0 +1,147 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use quick_xml::escape::escape;
+
+use crate::models::{Dependency, PolicyVerdict, ProjectScan};
+
+/// Render `(suite name, dependencies)` pairs as a JUnit XML `<testsuites>`
+/// document with one `<testcase name="pkg@version">` per dependency.
+///
+/// An `error` verdict is a `<failure>` naming the license; `warn` and `review`
+/// verdicts are `<skipped>` with the reason, so dashboards show them without
+/// failing the build; passing dependencies are plain test cases.
+pub fn to_junit<'a>(suites: impl IntoIterator<Item = (&'a str, &'a [Dependency])>) -> String {
+    let mut body = String::new();
+    let (mut tests, mut failures, mut skipped) = (0, 0, 0);
+    for (name, deps) in suites {
+        let suite_failures = deps
+            .iter()
+            .filter(|d| d.verdict == PolicyVerdict::Error)
+            .count();
+        let suite_skipped = deps
+            .iter()
+            .filter(|d| matches!(d.verdict, PolicyVerdict::Warn | PolicyVerdict::Review))
+            .count();
+        tests += deps.len();
+        failures += suite_failures;
+        skipped += suite_skipped;
+
+        body.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\">\n",
+            escape(name),
+            deps.len(),
+            suite_failures,
+            suite_skipped
+        ));
+        for dep in deps {
+            push_testcase(&mut body, dep);
+        }
+        body.push_str("  </testsuite>\n");
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <testsuites name=\"license-checkr\" tests=\"{}\" failures=\"{}\" skipped=\"{}\">\n\
+         {}</testsuites>\n",
+        tests, failures, skipped, body
+    )
+}
+
+fn push_testcase(out: &mut String, dep: &Dependency) {
+    let license = dep
+        .license_spdx
+        .as_deref()
+        .or(dep.license_raw.as_deref())
+        .unwrap_or("unknown");
+    let open = format!(
+        "    <testcase name=\"{}@{}\" classname=\"{}\"",
+        escape(&dep.name),
+        escape(&dep.version),
+        escape(&dep.ecosystem.to_string())
+    );
+    let child = match dep.verdict {
+        PolicyVerdict::Pass => None,
+        PolicyVerdict::Error => Some(format!(
+            "<failure message=\"license {} violates the license policy\" type=\"error\"/>",
+            escape(license)
+        )),
+        PolicyVerdict::Warn => Some(format!(
+            "<skipped message=\"license {} needs attention (warn)\"/>",
+            escape(license)
+        )),
+        PolicyVerdict::Review => Some(format!(
+            "<skipped message=\"license {} needs review\"/>",
+            escape(license)
+        )),
+    };
+    match child {
+        Some(child) => out.push_str(&format!("{}>\n      {}\n    </testcase>\n", open, child)),
+        None => out.push_str(&format!("{}/>\n", open)),
+    }
+}
+
+/// Print the JUnit report to stdout.
+pub fn render(deps: &[Dependency], scan_path: &Path) {
+    print!(
+        "{}",
+        to_junit([(super::project_name(scan_path).as_str(), deps)])
+    );
+}
+
+/// Print the workspace JUnit report to stdout, one test suite per project.
+pub fn render_workspace(projects: &[ProjectScan]) {
+    print!("{}", to_junit(workspace(projects)));
+}
+
+/// Write the [`render`] output to `path` instead of stdout.
+pub fn write(deps: &[Dependency], scan_path: &Path, path: &Path) -> Result<()> {
+    let xml = to_junit([(super::project_name(scan_path).as_str(), deps)]);
+    std::fs::write(path, xml)
+        .with_context(|| format!("Failed to write JUnit report to {}", path.display()))
+}
+
+/// Write the [`render_workspace`] output to `path` instead of stdout.
+pub fn write_workspace(projects: &[ProjectScan], path: &Path) -> Result<()> {
+    std::fs::write(path, to_junit(workspace(projects)))
+        .with_context(|| format!("Failed to write JUnit report to {}", path.display()))
+}
+
+fn workspace(projects: &[ProjectScan]) -> impl Iterator<Item = (&str, &[Dependency])> {
+    projects
+        .iter()
+        .map(|p| (p.name.as_str(), p.deps.as_slice()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::dep;
+
+    #[test]
+    fn test_junit_failures_match_error_verdicts() {
+        let deps = vec![
+            dep("express", Some("MIT"), PolicyVerdict::Pass),
+            dep("gpl-lib", Some("GPL-3.0"), PolicyVerdict::Error),
+            dep("<odd>", Some("AGPL-3.0"), PolicyVerdict::Error),
+            dep("mystery", None, PolicyVerdict::Warn),
+        ];
+        let xml = to_junit([("app", deps.as_slice())]);
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"license-checkr\" tests=\"4\" failures=\"2\" skipped=\"1\">\n"));
+        assert_eq!(xml.matches("<failure ").count(), 2);
+        assert_eq!(xml.matches("<testcase ").count(), 4);
+        assert!(xml.contains(
+            "    <testcase name=\"gpl-lib@1.0.0\" classname=\"Node\">\n      \
+             <failure message=\"license GPL-3.0 violates the license policy\" type=\"error\"/>\n    \
+             </testcase>\n"
+        ));
+        assert!(xml.contains("<testcase name=\"express@1.0.0\" classname=\"Node\"/>"));
+        assert!(xml.contains("name=\"&lt;odd&gt;@1.0.0\""));
+        assert!(xml.contains("<skipped message=\"license unknown needs attention (warn)\"/>"));
+
+        // Well-formed XML
+        let mut reader = quick_xml::Reader::from_str(&xml);
+        while reader.read_event().unwrap() != quick_xml::events::Event::Eof {}
+    }
+}
@@ -0,0 +1,155 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use super::json::Counts;
+use crate::models::{Dependency, PolicyVerdict, ProjectScan};
+
+/// Render a single-project scan as GitHub-flavored Markdown for a PR comment.
+pub fn to_markdown(deps: &[Dependency]) -> String {
+    let mut out = String::from("### License report\n\n");
+    push_project(&mut out, deps);
+    out
+}
+
+/// Render a workspace scan with one `####` section per project.
+pub fn workspace_to_markdown(projects: &[ProjectScan]) -> String {
+    let mut out = String::from("### License report\n\n");
+    for project in projects {
+        out.push_str(&format!("#### {}\n\n", cell(&project.name)));
+        push_project(&mut out, &project.deps);
+        out.push('\n');
+    }
+    if out.ends_with("\n\n") {
+        out.pop();
+    }
+    out
+}
+
+/// The totals line, then the dependencies that did not pass in a collapsed table
+/// sorted by name and version, so that reruns diff cleanly.
+fn push_project(out: &mut String, deps: &[Dependency]) {
+    let counts = Counts::from_deps(deps, "");
+    out.push_str(&format!(
+        "**{} dependencies: {} pass, {} warn, {} review, {} error**\n",
+        counts.total, counts.pass, counts.warn, counts.review, counts.error
+    ));
+
+    let mut flagged: Vec<&Dependency> = deps
+        .iter()
+        .filter(|d| d.verdict != PolicyVerdict::Pass)
+        .collect();
+    if flagged.is_empty() {
+        return;
+    }
+    flagged.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.version.cmp(&b.version)));
+
+    out.push_str(&format!(
+        "\n<details>\n<summary>{} {} not passing</summary>\n\n",
+        flagged.len(),
+        if flagged.len() == 1 {
+            "dependency"
+        } else {
+            "dependencies"
+        }
+    ));
+    out.push_str("| Name | Version | License | Verdict |\n");
+    out.push_str("|------|---------|---------|---------|\n");
+    for dep in flagged {
+        let license = dep
+            .license_spdx
+            .as_deref()
+            .or(dep.license_raw.as_deref())
+            .unwrap_or("unknown");
+        out.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            cell(&dep.name),
+            cell(&dep.version),
+            cell(license),
+            dep.verdict
+        ));
+    }
+    out.push_str("\n</details>\n");
+}
+
+/// Escape a table cell: pipes would split it, line breaks would end the row.
+fn cell(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+/// Print the Markdown report to stdout.
+pub fn render(deps: &[Dependency]) {
+    print!("{}", to_markdown(deps));
+}
+
+/// Print the workspace Markdown report to stdout.
+pub fn render_workspace(projects: &[ProjectScan]) {
+    print!("{}", workspace_to_markdown(projects));
+}
+
+/// Write the [`render`] output to `path` instead of stdout.
+pub fn write(deps: &[Dependency], path: &Path) -> Result<()> {
+    std::fs::write(path, to_markdown(deps))
+        .with_context(|| format!("Failed to write Markdown report to {}", path.display()))
+}
+
+/// Write the [`render_workspace`] output to `path` instead of stdout.
+pub fn write_workspace(projects: &[ProjectScan], path: &Path) -> Result<()> {
+    std::fs::write(path, workspace_to_markdown(projects))
+        .with_context(|| format!("Failed to write Markdown report to {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::dep;
+
+    #[test]
+    fn test_markdown_report() {
+        let deps = vec![
+            dep("zlib-wrapper", Some("GPL-3.0"), PolicyVerdict::Error),
+            dep("express", Some("MIT"), PolicyVerdict::Pass),
+            dep("mystery", None, PolicyVerdict::Warn),
+            dep("odd", Some("MIT | Custom"), PolicyVerdict::Review),
+        ];
+        assert_eq!(
+            to_markdown(&deps),
+            "### License report\n\
+             \n\
+             **4 dependencies: 1 pass, 1 warn, 1 review, 1 error**\n\
+             \n\
+             <details>\n\
+             <summary>3 dependencies not passing</summary>\n\
+             \n\
+             | Name | Version | License | Verdict |\n\
+             |------|---------|---------|---------|\n\
+             | mystery | 1.0.0 | unknown | warn |\n\
+             | odd | 1.0.0 | MIT \\| Custom | review |\n\
+             | zlib-wrapper | 1.0.0 | GPL-3.0 | error |\n\
+             \n\
+             </details>\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown_workspace_sections() {
+        let projects = vec![
+            ProjectScan {
+                name: "api".to_string(),
+                path: "api".into(),
+                deps: vec![dep("express", Some("MIT"), PolicyVerdict::Pass)],
+            },
+            ProjectScan {
+                name: "web".to_string(),
+                path: "web".into(),
+                deps: vec![dep("gpl-lib", Some("GPL-3.0"), PolicyVerdict::Error)],
+            },
+        ];
+        let md = workspace_to_markdown(&projects);
+        assert!(md.starts_with(
+            "### License report\n\n#### api\n\n**1 dependencies: 1 pass, 0 warn, 0 review, 0 error**\n\n#### web\n"
+        ));
+        assert!(md.contains("<summary>1 dependency not passing</summary>"));
+        assert!(md.ends_with("</details>\n"));
+    }
+}
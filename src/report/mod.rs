@@ -1,8 +1,358 @@
 //! Report renderers for license scan results.
 //!
 //! - [`terminal`] — colored, tabular output with summary box; respects `--verbose` / `--quiet`.
+//! - [`json`] — full dependency array, or verdict counts only under `--quiet`.
+//! - [`cyclonedx`] — CycloneDX 1.5 JSON BOM with package URLs, licenses and verdicts.
+//! - [`sarif`] — SARIF 2.1.0 log of policy findings for code scanning.
+//! - [`spdx`] — SPDX 2.3 JSON document with one package per dependency.
+//! - [`tsv`] — header-less `name\tversion\tecosystem\tlicense\tverdict` lines for scripts.
+//! - [`junit`] — JUnit XML with a test case per dependency for CI dashboards.
+//! - [`markdown`] — GitHub-flavored summary with the non-passing dependencies, for PR comments.
 //! - [`pdf`] — multi-page PDF with cover, bar charts (risk + ecosystem distribution),
 //!   and a full dependency table.
+//! - [`diff`] — `--baseline` changes as colored text or Markdown.
+//! - [`summary`] — `--summary-format` templates for the `--quiet` summary line.
+//!
+//! [`render`] / [`render_workspace`] dispatch every requested `--report` format and,
+//! with `--output-dir`, write each file-based format under a fixed name (or the
+//! single one to `--output`).
 
+pub mod cyclonedx;
+pub mod diff;
+pub mod json;
+pub mod junit;
+pub mod markdown;
 pub mod pdf;
+pub mod sarif;
+pub mod spdx;
+pub mod summary;
 pub mod terminal;
+pub mod tsv;
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+
+use crate::cli::{Column, GroupBy, ReportFormat, SortKey};
+use crate::models::{Dependency, LicenseRisk, ProjectScan};
+use crate::registry::OnlineStats;
+use summary::SummaryFormat;
+
+/// Output settings shared by every report format.
+pub struct RenderOptions<'a> {
+    pub verbose: bool,
+    pub quiet: bool,
+    pub min_risk: Option<&'a LicenseRisk>,
+    /// `--sort`: order of the terminal table rows (manifest order when unset).
+    pub sort: Option<&'a SortKey>,
+    /// `--columns`: terminal table columns, in order (all of them when empty).
+    pub columns: &'a [Column],
+    /// `--group-by`: split the terminal tables into sections.
+    pub group_by: Option<&'a GroupBy>,
+    /// PDF destination when `output_dir` is not set (`--pdf`).
+    pub pdf_path: &'a Path,
+    /// `--pdf-title` / `--pdf-logo` for the PDF cover.
+    pub pdf_branding: pdf::Branding<'a>,
+    /// `--output-dir`: write every file-based format here under [`file_name`].
+    pub output_dir: Option<&'a Path>,
+    /// `--output`: write the single file-based format here (see [`check_output`]).
+    pub output: Option<&'a Path>,
+    /// Scan time shown by every report, from [`format_timestamp`].
+    pub scanned_at: &'a str,
+    /// `--online` lookup counters, included in the `--quiet` JSON counts.
+    pub online_stats: Option<&'a OnlineStats>,
+    /// `--summary-format`: replaces the terminal `--quiet` summary line.
+    pub summary_format: Option<&'a SummaryFormat>,
+}
+
+/// Format a scan time as RFC 3339 with second precision: UTC with a `Z` suffix
+/// (e.g. `2024-03-14T09:21:00Z`), or the local offset when `local` is set
+/// (`--local-time`, e.g. `2024-03-14T10:21:00+01:00`).
+pub fn format_timestamp(time: DateTime<Utc>, local: bool) -> String {
+    if local {
+        time.with_timezone(&Local)
+            .to_rfc3339_opts(SecondsFormat::Secs, false)
+    } else {
+        time.to_rfc3339_opts(SecondsFormat::Secs, true)
+    }
+}
+
+/// Number of dependencies per ecosystem, keyed by its display name in sorted order.
+pub fn ecosystem_counts<'a>(
+    deps: impl IntoIterator<Item = &'a Dependency>,
+) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for dep in deps {
+        *counts.entry(dep.ecosystem.to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Display name of a single-project scan: the project directory's name, also
+/// for a relative `scan_path` such as `.`.
+pub fn project_name(scan_path: &Path) -> String {
+    scan_path
+        .canonicalize()
+        .ok()
+        .as_deref()
+        .unwrap_or(scan_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("project")
+        .to_string()
+}
+
+/// File name used for `format` under `--output-dir`; `None` for the terminal report.
+pub fn file_name(format: &ReportFormat) -> Option<&'static str> {
+    match format {
+        ReportFormat::Terminal => None,
+        ReportFormat::Json => Some("license-report.json"),
+        ReportFormat::Tsv => Some("license-report.tsv"),
+        ReportFormat::Pdf => Some("license-report.pdf"),
+        ReportFormat::CycloneDx => Some("license-report.cdx.json"),
+        ReportFormat::Spdx => Some("license-report.spdx.json"),
+        ReportFormat::Sarif => Some("license-report.sarif"),
+        ReportFormat::Markdown => Some("license-report.md"),
+        ReportFormat::JUnit => Some("license-report.junit.xml"),
+    }
+}
+
+/// Check that `--output` has exactly one file-based format to write.
+pub fn check_output(formats: &[ReportFormat], output: Option<&Path>) -> Result<()> {
+    if output.is_none() {
+        return Ok(());
+    }
+    let files = formats.iter().filter(|f| file_name(f).is_some()).count();
+    match files {
+        1 => Ok(()),
+        0 => anyhow::bail!("--output needs a file-based --report format (e.g. --report json)"),
+        n => anyhow::bail!(
+            "--output takes a single file-based --report format, got {}; use --output-dir for several",
+            n
+        ),
+    }
+}
+
+/// Where a file-based format goes: `--output-dir` or `--output` if given, else its
+/// usual location. `None` means stdout.
+fn destination(format: &ReportFormat, opts: &RenderOptions<'_>) -> Result<Option<PathBuf>> {
+    let Some(name) = file_name(format) else {
+        return Ok(None);
+    };
+    if let Some(dir) = opts.output_dir {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create output directory {}", dir.display()))?;
+        return Ok(Some(dir.join(name)));
+    }
+    if let Some(file) = opts.output {
+        return Ok(Some(file.to_path_buf()));
+    }
+    Ok(match format {
+        ReportFormat::Pdf => Some(opts.pdf_path.to_path_buf()),
+        _ => None,
+    })
+}
+
+/// Render a single-project scan in every requested format.
+///
+/// Returns the files written under `--output-dir` or to `--output` (empty otherwise).
+pub fn render(
+    formats: &[ReportFormat],
+    deps: &[Dependency],
+    scan_path: &Path,
+    opts: &RenderOptions<'_>,
+) -> Result<Vec<PathBuf>> {
+    let to_file = opts.output_dir.is_some() || opts.output.is_some();
+    let mut written = Vec::new();
+    for format in formats {
+        let dest = destination(format, opts)?;
+        match (format, &dest) {
+            (ReportFormat::Terminal, _) => terminal::render(deps, scan_path, opts)?,
+            (ReportFormat::Json, None) => json::render(deps, opts)?,
+            (ReportFormat::Json, Some(file)) => json::write(deps, opts, file)?,
+            (ReportFormat::Tsv, None) => tsv::render(deps),
+            (ReportFormat::Tsv, Some(file)) => tsv::write(deps, file)?,
+            (ReportFormat::CycloneDx, None) => cyclonedx::render(deps, opts.scanned_at)?,
+            (ReportFormat::CycloneDx, Some(file)) => cyclonedx::write(deps, opts.scanned_at, file)?,
+            (ReportFormat::Spdx, None) => spdx::render(deps, scan_path, opts.scanned_at)?,
+            (ReportFormat::Spdx, Some(file)) => {
+                spdx::write(deps, scan_path, opts.scanned_at, file)?
+            }
+            (ReportFormat::Sarif, None) => sarif::render(deps, scan_path)?,
+            (ReportFormat::Sarif, Some(file)) => sarif::write(deps, scan_path, file)?,
+            (ReportFormat::Markdown, None) => markdown::render(deps),
+            (ReportFormat::Markdown, Some(file)) => markdown::write(deps, file)?,
+            (ReportFormat::JUnit, None) => junit::render(deps, scan_path),
+            (ReportFormat::JUnit, Some(file)) => junit::write(deps, scan_path, file)?,
+            (ReportFormat::Pdf, _) => {
+                let file = dest.as_deref().unwrap_or(opts.pdf_path);
+                pdf::render(deps, scan_path, file, opts.scanned_at, &opts.pdf_branding)?;
+                if !to_file {
+                    println!("PDF report written to: {}", file.display());
+                }
+            }
+        }
+        if to_file {
+            written.extend(dest);
+        }
+    }
+    Ok(written)
+}
+
+/// Render a workspace scan in every requested format.
+///
+/// `root` is the directory the projects were found under (the current directory
+/// when they were listed explicitly); SARIF paths are relative to it.
+///
+/// Returns the files written under `--output-dir` or to `--output` (empty otherwise).
+pub fn render_workspace(
+    formats: &[ReportFormat],
+    projects: &[ProjectScan],
+    root: &Path,
+    opts: &RenderOptions<'_>,
+) -> Result<Vec<PathBuf>> {
+    let to_file = opts.output_dir.is_some() || opts.output.is_some();
+    let mut written = Vec::new();
+    for format in formats {
+        let dest = destination(format, opts)?;
+        match (format, &dest) {
+            (ReportFormat::Terminal, _) => terminal::render_workspace(projects, opts)?,
+            (ReportFormat::Json, None) => json::render_workspace(projects, opts)?,
+            (ReportFormat::Json, Some(file)) => json::write_workspace(projects, opts, file)?,
+            (ReportFormat::Tsv, None) => tsv::render_workspace(projects),
+            (ReportFormat::Tsv, Some(file)) => tsv::write_workspace(projects, file)?,
+            (ReportFormat::CycloneDx, None) => {
+                cyclonedx::render_workspace(projects, opts.scanned_at)?
+            }
+            (ReportFormat::CycloneDx, Some(file)) => {
+                cyclonedx::write_workspace(projects, opts.scanned_at, file)?
+            }
+            (ReportFormat::Spdx, None) => spdx::render_workspace(projects, opts.scanned_at)?,
+            (ReportFormat::Spdx, Some(file)) => {
+                spdx::write_workspace(projects, opts.scanned_at, file)?
+            }
+            (ReportFormat::Sarif, None) => sarif::render_workspace(projects, root)?,
+            (ReportFormat::Sarif, Some(file)) => sarif::write_workspace(projects, root, file)?,
+            (ReportFormat::Markdown, None) => markdown::render_workspace(projects),
+            (ReportFormat::Markdown, Some(file)) => markdown::write_workspace(projects, file)?,
+            (ReportFormat::JUnit, None) => junit::render_workspace(projects),
+            (ReportFormat::JUnit, Some(file)) => junit::write_workspace(projects, file)?,
+            (ReportFormat::Pdf, _) => {
+                let file = dest.as_deref().unwrap_or(opts.pdf_path);
+                pdf::render_workspace(projects, file, opts.scanned_at, &opts.pdf_branding)?;
+                if !to_file {
+                    println!("PDF workspace report written to: {}", file.display());
+                }
+            }
+        }
+        if to_file {
+            written.extend(dest);
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{dep, Ecosystem, PolicyVerdict};
+
+    #[test]
+    fn test_output_dir_writes_each_requested_format() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let out = tmp.path().join("out");
+        let deps = vec![dep("serde", Some("MIT OR Apache-2.0"), PolicyVerdict::Pass)
+            .with_version("1.0.200")
+            .with_ecosystem(Ecosystem::Rust)];
+        let opts = RenderOptions {
+            verbose: false,
+            quiet: false,
+            min_risk: None,
+            sort: None,
+            columns: &[],
+            group_by: None,
+            pdf_path: Path::new("unused.pdf"),
+            pdf_branding: Default::default(),
+            output_dir: Some(&out),
+            output: None,
+            scanned_at: "2024-03-14T09:21:00Z",
+            online_stats: None,
+            summary_format: None,
+        };
+
+        let written = render(
+            &[ReportFormat::Json, ReportFormat::Pdf],
+            &deps,
+            tmp.path(),
+            &opts,
+        )
+        .unwrap();
+
+        assert_eq!(
+            written,
+            [
+                out.join("license-report.json"),
+                out.join("license-report.pdf")
+            ]
+        );
+        assert!(written.iter().all(|f| f.is_file()));
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&written[0]).unwrap()).unwrap();
+        assert_eq!(json[0]["name"], "serde");
+    }
+
+    #[test]
+    fn test_output_writes_single_format_to_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let file = tmp.path().join("findings.sarif");
+        let opts = RenderOptions {
+            verbose: false,
+            quiet: false,
+            min_risk: None,
+            sort: None,
+            columns: &[],
+            group_by: None,
+            pdf_path: Path::new("unused.pdf"),
+            pdf_branding: Default::default(),
+            output_dir: None,
+            output: Some(&file),
+            scanned_at: "2024-03-14T09:21:00Z",
+            online_stats: None,
+            summary_format: None,
+        };
+
+        let written = render(&[ReportFormat::Sarif], &[], tmp.path(), &opts).unwrap();
+
+        assert_eq!(written, [file.as_path()]);
+        let sarif: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        assert_eq!(sarif["version"], "2.1.0");
+    }
+
+    #[test]
+    fn test_check_output_needs_one_file_format() {
+        let out = Some(Path::new("report.json"));
+        assert!(check_output(&[ReportFormat::Terminal, ReportFormat::Json], out).is_ok());
+        assert!(check_output(&[ReportFormat::Terminal], out).is_err());
+        assert!(check_output(&[ReportFormat::Json, ReportFormat::Sarif], out).is_err());
+        assert!(check_output(&[ReportFormat::Terminal], None).is_ok());
+    }
+
+    #[test]
+    fn test_timestamp_is_rfc3339_utc() {
+        let time = DateTime::parse_from_rfc3339("2024-03-14T10:21:00.750+01:00")
+            .unwrap()
+            .with_timezone(&Utc);
+        let stamp = format_timestamp(time, false);
+        assert_eq!(stamp, "2024-03-14T09:21:00Z");
+        assert!(DateTime::parse_from_rfc3339(&stamp).is_ok());
+
+        let local = format_timestamp(time, true);
+        assert_eq!(
+            DateTime::parse_from_rfc3339(&local).unwrap(),
+            DateTime::parse_from_rfc3339("2024-03-14T09:21:00Z").unwrap()
+        );
+    }
+}
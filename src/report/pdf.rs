@@ -2,17 +2,23 @@ use std::path::Path;
 
 use anyhow::{Context, Result};
 use printpdf::{
-    BuiltinFont, Color, IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference,
-    PdfLayerIndex, PdfLayerReference, PdfPageIndex, Point, Polygon, Rgb,
+    BuiltinFont, Color, Image, ImageTransform, IndirectFontRef, Line, Mm, PdfDocument,
+    PdfDocumentReference, PdfLayerIndex, PdfLayerReference, PdfPageIndex, Point, Polygon, Rgb,
 };
+use printpdf::image_crate::codecs::png::PngDecoder;
 use printpdf::path::{PaintMode, WindingOrder};
 
+use crate::diagnostics::Diagnostic;
+use crate::license::obligations;
 use crate::models::{Dependency, LicenseRisk, PolicyVerdict, ProjectScan};
 
 const PAGE_W: f32 = 210.0;
 const PAGE_H: f32 = 297.0;
 const MARGIN: f32 = 18.0;
 const COVER_HDR_H: f32 = 72.0; // gradient header height on cover page
+const TITLE_WRAP: usize = 18;  // cover title characters per line (two lines at most)
+const LOGO_H: f32 = 16.0;      // --pdf-logo height on the cover
+const LOGO_MAX_W: f32 = 50.0;  // wider logos are scaled down to this width
 
 // ── Light Liquid Glass colour palette ─────────────────────────────────────────
 const BG:           (f32, f32, f32) = (1.00, 1.00, 1.00); // pure white page
@@ -58,34 +64,62 @@ const DESC_WRAP: usize = 36;
 const DEPS_WRAP: usize = 28;
 const DEPS_MAX_LINES: usize = 4;
 
+// ── Cover ecosystem chart layout ──────────────────────────────────────────────
+const ECO_MAX_BARS: usize = 6;   // further ecosystems are folded into "Other"
+const ECO_PITCH: f32 = 7.5;
+const ECO_BAR_H: f32 = 4.5;
+const ECO_LABEL_W: f32 = 26.0;
+const ECO_COUNT_W: f32 = 12.0;
+
 // ── Public entry point ────────────────────────────────────────────────────────
 
+/// Cover branding from `--pdf-title` / `--pdf-logo`; the defaults keep the
+/// standard cover.
+#[derive(Default)]
+pub struct Branding<'a> {
+    /// Replaces the "License Compliance Report" heading.
+    pub title: Option<&'a str>,
+    /// PNG drawn in the top-right of the cover header.
+    pub logo: Option<&'a Path>,
+}
+
 /// Render a PDF report: cover page → risk summary table → full dependency table.
-pub fn render(deps: &[Dependency], project_path: &Path, output_path: &Path) -> Result<()> {
+///
+/// `scanned_at` is the RFC 3339 scan time shown on the cover and in the footer.
+pub fn render(
+    deps: &[Dependency],
+    project_path: &Path,
+    output_path: &Path,
+    scanned_at: &str,
+    branding: &Branding<'_>,
+) -> Result<()> {
     let project_name = project_path
         .file_name()
         .and_then(|n| n.to_str())
         .unwrap_or("Unknown Project");
 
-    let doc = PdfDocument::empty("License Report");
+    let doc = PdfDocument::empty(branding.title.unwrap_or("License Report"));
 
-    add_cover_page(&doc, deps, project_name)?;
+    add_cover_page(&doc, deps, project_name, scanned_at, branding)?;
     add_risk_summary_page(&doc, deps, None)?;
     add_table_pages(&doc, deps, None)?;
 
     let bytes = doc.save_to_bytes()?;
     std::fs::write(output_path, &bytes)
         .with_context(|| format!("Failed to write PDF to {}", output_path.display()))?;
-
-    println!("PDF report written to: {}", output_path.display());
     Ok(())
 }
 
 /// Render a workspace PDF: workspace cover → per-project Risk Summary + Dependency Table.
-pub fn render_workspace(projects: &[ProjectScan], output_path: &Path) -> Result<()> {
-    let doc = PdfDocument::empty("License Report — Workspace");
+pub fn render_workspace(
+    projects: &[ProjectScan],
+    output_path: &Path,
+    scanned_at: &str,
+    branding: &Branding<'_>,
+) -> Result<()> {
+    let doc = PdfDocument::empty(branding.title.unwrap_or("License Report — Workspace"));
 
-    add_workspace_cover_page(&doc, projects)?;
+    add_workspace_cover_page(&doc, projects, scanned_at, branding)?;
 
     for proj in projects {
         add_risk_summary_page(&doc, &proj.deps, Some(&proj.name))?;
@@ -95,14 +129,17 @@ pub fn render_workspace(projects: &[ProjectScan], output_path: &Path) -> Result<
     let bytes = doc.save_to_bytes()?;
     std::fs::write(output_path, &bytes)
         .with_context(|| format!("Failed to write PDF to {}", output_path.display()))?;
-
-    println!("PDF workspace report written to: {}", output_path.display());
     Ok(())
 }
 
 // ── Workspace cover page ──────────────────────────────────────────────────────
 
-fn add_workspace_cover_page(doc: &PdfDocumentReference, projects: &[ProjectScan]) -> Result<()> {
+fn add_workspace_cover_page(
+    doc: &PdfDocumentReference,
+    projects: &[ProjectScan],
+    scanned_at: &str,
+    branding: &Branding<'_>,
+) -> Result<()> {
     let (page_idx, layer_idx) = doc.add_page(Mm(PAGE_W), Mm(PAGE_H), "Cover");
     let layer = doc.get_page(page_idx).get_layer(layer_idx);
 
@@ -125,10 +162,9 @@ fn add_workspace_cover_page(doc: &PdfDocumentReference, projects: &[ProjectScan]
         7.5, Mm(PAGE_W - MARGIN - 44.0), Mm(PAGE_H - 10.5), &font_r,
     );
 
-    set_color(&layer, WHITE);
-    layer.use_text("License Compliance", 28.0, Mm(MARGIN), Mm(PAGE_H - 26.0), &font_b);
-    set_color(&layer, WHITE_DIM);
-    layer.use_text("Workspace Report", 28.0, Mm(MARGIN), Mm(PAGE_H - 41.0), &font_b);
+    let title = branding.title.unwrap_or("License Compliance Workspace Report");
+    draw_cover_title(&layer, title, &font_b);
+    draw_logo(&layer, branding.logo);
 
     // Workspace chip
     let chip_y = hdr_bot - 18.0;
@@ -149,7 +185,7 @@ fn add_workspace_cover_page(doc: &PdfDocumentReference, projects: &[ProjectScan]
     // Scan date
     set_color(&layer, TEXT_SEC);
     layer.use_text(
-        format!("Scanned  {}", chrono_now()),
+        format!("Scanned  {}", scanned_at),
         9.0, Mm(MARGIN), Mm(chip_y - 8.0), &font_r,
     );
 
@@ -260,7 +296,7 @@ fn add_workspace_cover_page(doc: &PdfDocumentReference, projects: &[ProjectScan]
         format!("Generated by license-checkr v{}", env!("CARGO_PKG_VERSION")),
         7.5, Mm(MARGIN), Mm(15.0), &font_r,
     );
-    layer.use_text(chrono_now(), 7.5, Mm(PAGE_W - MARGIN - 22.0), Mm(15.0), &font_r);
+    layer.use_text(scanned_at, 7.5, Mm(PAGE_W - MARGIN - 34.0), Mm(15.0), &font_r);
 
     Ok(())
 }
@@ -271,6 +307,8 @@ fn add_cover_page(
     doc: &PdfDocumentReference,
     deps: &[Dependency],
     project_name: &str,
+    scanned_at: &str,
+    branding: &Branding<'_>,
 ) -> Result<()> {
     let (page_idx, layer_idx) = doc.add_page(Mm(PAGE_W), Mm(PAGE_H), "Cover");
     let layer = doc.get_page(page_idx).get_layer(layer_idx);
@@ -296,11 +334,9 @@ fn add_cover_page(
         7.5, Mm(PAGE_W - MARGIN - 44.0), Mm(PAGE_H - 10.5), &font_r,
     );
 
-    // Title
-    set_color(&layer, WHITE);
-    layer.use_text("License Compliance", 28.0, Mm(MARGIN), Mm(PAGE_H - 26.0), &font_b);
-    set_color(&layer, WHITE_DIM);
-    layer.use_text("Report", 28.0, Mm(MARGIN), Mm(PAGE_H - 41.0), &font_b);
+    // Title + optional logo
+    draw_cover_title(&layer, branding.title.unwrap_or("License Compliance Report"), &font_b);
+    draw_logo(&layer, branding.logo);
 
     // ── Project chip (just below header) ──────────────────────────────────────
     let chip_y = hdr_bot - 18.0;
@@ -322,7 +358,7 @@ fn add_cover_page(
     // ── Scan date ─────────────────────────────────────────────────────────────
     set_color(&layer, TEXT_SEC);
     layer.use_text(
-        format!("Scanned  {}", chrono_now()),
+        format!("Scanned  {}", scanned_at),
         9.0, Mm(MARGIN), Mm(chip_y - 8.0), &font_r,
     );
 
@@ -352,8 +388,42 @@ fn add_cover_page(
                        &font_r, &font_b);
     }
 
+    // ── Ecosystems scanned (chips wrap onto further rows) ─────────────────────
+    let eco_y = card_y - 13.0;
+    draw_hline(&layer, MARGIN, PAGE_W - MARGIN, eco_y, PANEL_BORDER);
+    set_color(&layer, TEXT_MUT);
+    layer.use_text("ECOSYSTEMS SCANNED", 6.5, Mm(MARGIN), Mm(eco_y - 7.5), &font_b);
+
+    let bars = ecosystem_bars(&super::ecosystem_counts(deps));
+    let max = bars.iter().map(|(_, n)| *n).max().unwrap_or(0);
+    let track_x = MARGIN + ECO_LABEL_W;
+    let track_w = T_END - ECO_COUNT_W - track_x;
+    let mut row_y = eco_y - 17.0;
+    if bars.is_empty() {
+        set_color(&layer, TEXT_SEC);
+        layer.use_text("No dependencies detected", 8.0, Mm(MARGIN), Mm(row_y + 2.2), &font_r);
+    }
+    for (i, (eco, count)) in bars.iter().enumerate() {
+        if i > 0 {
+            row_y -= ECO_PITCH;
+        }
+        set_color(&layer, TEXT_PRI);
+        layer.use_text(truncate(eco, 14), 7.5, Mm(MARGIN), Mm(row_y + 1.2), &font_b);
+
+        fill_rect(&layer, track_x, row_y, track_w, ECO_BAR_H, PANEL_ALT);
+        let w = bar_width(*count, max, track_w);
+        let steps = ((w / 4.0).ceil() as usize).max(1);
+        fill_gradient_h(&layer, track_x, row_y, w, ECO_BAR_H, ACCENT_BLU, ACCENT_PUR, steps);
+
+        set_color(&layer, ACCENT_BLU);
+        layer.use_text(
+            count.to_string(), 7.5,
+            Mm(track_x + track_w + 2.5), Mm(row_y + 1.2), &font_b,
+        );
+    }
+
     // ── "What's in this report" section ───────────────────────────────────────
-    let section_y = card_y - 13.0;
+    let section_y = row_y - 9.0;
     draw_hline(&layer, MARGIN, PAGE_W - MARGIN, section_y, PANEL_BORDER);
     set_color(&layer, TEXT_MUT);
     layer.use_text("WHAT'S IN THIS REPORT", 6.5, Mm(MARGIN), Mm(section_y - 7.5), &font_b);
@@ -379,11 +449,69 @@ fn add_cover_page(
         format!("Generated by license-checkr v{}", env!("CARGO_PKG_VERSION")),
         7.5, Mm(MARGIN), Mm(15.0), &font_r,
     );
-    layer.use_text(chrono_now(), 7.5, Mm(PAGE_W - MARGIN - 22.0), Mm(15.0), &font_r);
+    layer.use_text(scanned_at, 7.5, Mm(PAGE_W - MARGIN - 34.0), Mm(15.0), &font_r);
 
     Ok(())
 }
 
+/// Cover heading over two lines: the first in white, the rest dimmed.
+fn draw_cover_title(layer: &PdfLayerReference, title: &str, font_b: &IndirectFontRef) {
+    for (i, line) in title_lines(title).iter().enumerate() {
+        set_color(layer, if i == 0 { WHITE } else { WHITE_DIM });
+        let y = PAGE_H - 26.0 - i as f32 * 15.0;
+        layer.use_text(line.as_str(), 28.0, Mm(MARGIN), Mm(y), font_b);
+    }
+}
+
+/// `title` wrapped to [`TITLE_WRAP`] characters, at most two lines; whatever
+/// does not fit is truncated on the second line.
+fn title_lines(title: &str) -> Vec<String> {
+    let mut lines = wrap_text(title, TITLE_WRAP);
+    if lines.len() > 2 {
+        let rest = lines.split_off(1).join(" ");
+        lines.push(truncate(&rest, TITLE_WRAP));
+    }
+    lines
+}
+
+/// Draw the `--pdf-logo` PNG right-aligned in the cover header, [`LOGO_H`]
+/// tall (narrower for very wide logos). A logo that cannot be read or decoded
+/// is reported and left out, keeping the standard cover.
+fn draw_logo(layer: &PdfLayerReference, path: Option<&Path>) {
+    let Some(path) = path else { return };
+    let image = match load_logo(path) {
+        Ok(image) => image,
+        Err(e) => {
+            Diagnostic::warning(format!("PDF logo skipped: {:#}", e)).file(path).emit();
+            return;
+        }
+    };
+    let (px_w, px_h) = (image.image.width.0 as f32, image.image.height.0 as f32);
+    let h = LOGO_H.min(LOGO_MAX_W * px_h / px_w);
+    let w = h * px_w / px_h;
+    image.add_to_layer(layer.clone(), ImageTransform {
+        translate_x: Some(Mm(T_END - w)),
+        translate_y: Some(Mm(PAGE_H - 15.0 - h)),
+        // dots per inch such that the image is exactly `h` mm tall
+        dpi: Some(px_h * 25.4 / h),
+        ..Default::default()
+    });
+}
+
+fn load_logo(path: &Path) -> Result<Image> {
+    let bytes = std::fs::read(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let decoder = PngDecoder::new(std::io::Cursor::new(bytes))
+        .with_context(|| format!("{} is not a valid PNG", path.display()))?;
+    let image = Image::try_from(decoder)
+        .with_context(|| format!("Failed to decode {}", path.display()))?;
+    anyhow::ensure!(
+        image.image.width.0 > 0 && image.image.height.0 > 0,
+        "{} has no pixels", path.display()
+    );
+    Ok(image)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn draw_stat_card(
     layer: &PdfLayerReference,
@@ -423,6 +551,7 @@ struct RenderedRow {
     fg: (f32, f32, f32),
     desc_lines: Vec<String>,
     dep_lines: Vec<String>,
+    count: usize,
     height: f32,
 }
 
@@ -478,7 +607,14 @@ fn add_risk_summary_page(
             .filter(|dep| dep.risk == d.risk)
             .map(|dep| dep.name.clone())
             .collect();
-        let desc_lines = wrap_text(d.description, DESC_WRAP);
+        let obligations = obligations::for_risk(&d.risk);
+        let description = if obligations.is_empty() {
+            d.description.to_string()
+        } else {
+            let labels: Vec<&str> = obligations.iter().map(|o| o.label()).collect();
+            format!("{} Obligations: {}.", d.description, labels.join(", "))
+        };
+        let desc_lines = wrap_text(&description, DESC_WRAP);
         // All names listed first (capped to DEPS_MAX_LINES), count line at the bottom
         let dep_lines = {
             let mut lines = format_dep_count_list(&names, DEPS_WRAP);
@@ -492,6 +628,7 @@ fn add_risk_summary_page(
         let n = desc_lines.len().max(dep_lines.len()).max(2) as f32;
         RenderedRow {
             name: d.name, bg: d.bg, fg: d.fg, desc_lines, dep_lines,
+            count: names.len(),
             height: n * LINE_H + ROW_PAD * 2.0,
         }
     }).collect();
@@ -575,6 +712,8 @@ fn add_risk_summary_page(
         y_top = y_bot;
     }
 
+    draw_risk_bars(&layer, &rows, table_bot - 10.0, &font_b);
+
     // Footer
     draw_hline(&layer, MARGIN, PAGE_W - MARGIN, 22.0, PANEL_BORDER);
     set_color(&layer, TEXT_MUT);
@@ -586,6 +725,79 @@ fn add_risk_summary_page(
     Ok(())
 }
 
+/// Horizontal bar chart of dependency counts per risk level, drawn below the
+/// risk table. Row pitch shrinks to fit above the footer; the chart is skipped
+/// when the table leaves too little room for it.
+fn draw_risk_bars(
+    layer: &PdfLayerReference,
+    rows: &[RenderedRow],
+    top: f32,
+    font_b: &IndirectFontRef,
+) {
+    const BOTTOM:    f32 = 28.0;  // keep clear of the footer rule at y = 22
+    const TITLE_H:   f32 = 7.0;
+    const MAX_PITCH: f32 = 9.0;
+    const MIN_PITCH: f32 = 5.0;
+    const LABEL_W:   f32 = 32.0;
+    const COUNT_W:   f32 = 12.0;
+
+    if rows.is_empty() {
+        return;
+    }
+    let pitch = ((top - BOTTOM - TITLE_H) / rows.len() as f32).min(MAX_PITCH);
+    if pitch < MIN_PITCH {
+        return;
+    }
+    let bar_h = pitch * 0.6;
+    let track_x = C1_X + LABEL_W;
+    let track_w = T_END - COUNT_W - track_x;
+    let max = rows.iter().map(|r| r.count).max().unwrap_or(0);
+
+    set_color(layer, TEXT_SEC);
+    layer.use_text("DEPENDENCIES BY RISK", 7.0, Mm(C1_X), Mm(top - 3.0), font_b);
+
+    for (i, row) in rows.iter().enumerate() {
+        let y = top - TITLE_H - (i as f32 + 1.0) * pitch + (pitch - bar_h) / 2.0;
+
+        set_color(layer, row.fg);
+        layer.use_text(row.name, 7.5, Mm(C1_X), Mm(y + bar_h * 0.25), font_b);
+
+        fill_rounded_rect(layer, track_x, y, track_w, bar_h, R_BADGE, row.bg);
+        let w = bar_width(row.count, max, track_w);
+        if w > 0.0 {
+            fill_rounded_rect(layer, track_x, y, w, bar_h, R_BADGE, row.fg);
+        }
+
+        set_color(layer, TEXT_PRI);
+        layer.use_text(
+            row.count.to_string(), 8.0,
+            Mm(track_x + track_w + 2.5), Mm(y + bar_h * 0.25), font_b,
+        );
+    }
+}
+
+/// Bars for the cover's ecosystem chart, largest first. Beyond [`ECO_MAX_BARS`]
+/// the smallest ecosystems share a single "Other" bar so the chart never grows
+/// past the space reserved for it.
+fn ecosystem_bars(counts: &std::collections::BTreeMap<String, usize>) -> Vec<(String, usize)> {
+    let mut bars: Vec<(String, usize)> =
+        counts.iter().map(|(eco, n)| (eco.clone(), *n)).collect();
+    bars.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    if bars.len() > ECO_MAX_BARS {
+        let other = bars.split_off(ECO_MAX_BARS - 1).iter().map(|(_, n)| n).sum();
+        bars.push(("Other".to_string(), other));
+    }
+    bars
+}
+
+/// Width of a bar for `count`, scaled so the largest bucket fills `track_w`.
+fn bar_width(count: usize, max: usize, track_w: f32) -> f32 {
+    if max == 0 {
+        return 0.0;
+    }
+    track_w * count as f32 / max as f32
+}
+
 // ── Full dependency table pages ───────────────────────────────────────────────
 
 fn add_table_pages(
@@ -676,6 +888,7 @@ fn add_table_pages(
         let (verdict_str, verdict_fg, verdict_bg) = match dep.verdict {
             PolicyVerdict::Pass  => ("PASS",  PASS_FG, PASS_BG),
             PolicyVerdict::Warn  => ("WARN",  WARN_FG, WARN_BG),
+            PolicyVerdict::Review => ("REVIEW", PROP_FG, PROP_BG),
             PolicyVerdict::Error => ("ERROR", ERR_FG,  ERR_BG),
         };
 
@@ -828,31 +1041,51 @@ fn fill_gradient_h(
 
 // ── Text helpers ──────────────────────────────────────────────────────────────
 
+/// `s` cut to at most `max` characters, the last of them `…` when shortened.
 fn truncate(s: &str, max: usize) -> String {
-    let chars: Vec<char> = s.chars().collect();
-    if chars.len() > max {
-        format!("{}…", chars[..max - 1].iter().collect::<String>())
-    } else {
-        s.to_string()
+    if s.chars().count() <= max {
+        return s.to_string();
+    }
+    if max == 0 {
+        return String::new();
     }
+    let mut out: String = s.chars().take(max - 1).collect();
+    out.push('…');
+    out
 }
 
+/// Word-wrap `text` into lines of at most `max_chars` characters (not bytes, so
+/// multibyte names wrap like ASCII ones). Words longer than a line are split.
 fn wrap_text(text: &str, max_chars: usize) -> Vec<String> {
-    if text.len() <= max_chars {
+    let max_chars = max_chars.max(1);
+    if text.chars().count() <= max_chars {
         return vec![text.to_string()];
     }
     let mut lines = Vec::new();
     let mut current = String::new();
+    let mut current_len = 0;
     for word in text.split_whitespace() {
-        if current.is_empty() {
-            current.push_str(word);
-        } else if current.len() + 1 + word.len() > max_chars {
-            lines.push(current.clone());
-            current = word.to_string();
-        } else {
+        let mut word: Vec<char> = word.chars().collect();
+        while word.len() > max_chars {
+            if current_len > 0 {
+                lines.push(std::mem::take(&mut current));
+                current_len = 0;
+            }
+            lines.push(word.drain(..max_chars).collect());
+        }
+        if word.is_empty() {
+            continue;
+        }
+        if current_len > 0 && current_len + 1 + word.len() > max_chars {
+            lines.push(std::mem::take(&mut current));
+            current_len = 0;
+        }
+        if current_len > 0 {
             current.push(' ');
-            current.push_str(word);
+            current_len += 1;
         }
+        current.extend(&word);
+        current_len += word.len();
     }
     if !current.is_empty() {
         lines.push(current);
@@ -871,7 +1104,7 @@ fn format_dep_count_list(names: &[String], max_chars: usize) -> Vec<String> {
     for name in names {
         let sep = if current.is_empty() { "" } else { ", " };
         let candidate = format!("{}{}", sep, name);
-        if !current.is_empty() && current.len() + candidate.len() > max_chars {
+        if !current.is_empty() && current.chars().count() + candidate.chars().count() > max_chars {
             lines.push(current.clone());
             current = name.clone();
         } else {
@@ -886,18 +1119,104 @@ fn format_dep_count_list(names: &[String], max_chars: usize) -> Vec<String> {
     lines
 }
 
-// ── Date helper ───────────────────────────────────────────────────────────────
-
-fn chrono_now() -> String {
-    use std::time::{SystemTime, UNIX_EPOCH};
-    let secs = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_secs())
-        .unwrap_or(0);
-    let days  = secs / 86400;
-    let year  = 1970 + days / 365;
-    let doy   = days % 365;
-    let month = (doy / 30) + 1;
-    let day   = (doy % 30) + 1;
-    format!("{:04}-{:02}-{:02}", year, month.min(12), day.min(31))
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_counts_chars() {
+        assert_eq!(truncate("日本語のパッケージ", 5), "日本語の…");
+        assert_eq!(truncate("café-crème", 10), "café-crème");
+        assert_eq!(truncate("café-crème", 6), "café-…");
+        assert_eq!(truncate("anything", 1), "…");
+        assert_eq!(truncate("anything", 0), "");
+        assert_eq!(truncate("", 0), "");
+    }
+
+    #[test]
+    fn test_wrap_text_counts_chars() {
+        // 11 chars but 14 bytes: fits without wrapping
+        assert_eq!(wrap_text("déjà vu été", 11), ["déjà vu été"]);
+        assert_eq!(
+            wrap_text("Licence publique générale", 10),
+            ["Licence", "publique", "générale"]
+        );
+        // Words longer than a line are split instead of overflowing
+        let lines = wrap_text("許可 日本語のパッケージ名", 4);
+        assert_eq!(lines, ["許可", "日本語の", "パッケー", "ジ名"]);
+        assert!(lines.iter().all(|l| l.chars().count() <= 4));
+        assert_eq!(wrap_text("a b", 0), ["a", "b"]);
+    }
+
+    #[test]
+    fn test_bar_width_scales_to_max_bucket() {
+        assert_eq!(bar_width(10, 10, 120.0), 120.0);
+        assert_eq!(bar_width(5, 10, 120.0), 60.0);
+        assert_eq!(bar_width(0, 10, 120.0), 0.0);
+        // No dependencies at all: nothing to draw, no division by zero
+        assert_eq!(bar_width(0, 0, 120.0), 0.0);
+    }
+
+    #[test]
+    fn test_title_lines_wrap_to_two_lines() {
+        assert_eq!(title_lines("License Compliance Report"), ["License Compliance", "Report"]);
+        assert_eq!(
+            title_lines("License Compliance Workspace Report"),
+            ["License Compliance", "Workspace Report"]
+        );
+        assert_eq!(title_lines("Acme"), ["Acme"]);
+        assert_eq!(
+            title_lines("Acme Corporation Third-Party Software Audit"),
+            ["Acme Corporation", "Third-Party Softw…"]
+        );
+    }
+
+    #[test]
+    fn test_render_with_logo_and_title() {
+        // 1×1 opaque PNG
+        const PNG: &[u8] = &[
+            0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D,
+            0x49, 0x48, 0x44, 0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01,
+            0x08, 0x02, 0x00, 0x00, 0x00, 0x90, 0x77, 0x53, 0xDE, 0x00, 0x00, 0x00,
+            0x0C, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9C, 0x63, 0xF8, 0xCF, 0xC0, 0x00,
+            0x00, 0x03, 0x01, 0x01, 0x00, 0xC9, 0xFE, 0x92, 0xEF, 0x00, 0x00, 0x00,
+            0x00, 0x49, 0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82,
+        ];
+        let dir = tempfile::tempdir().unwrap();
+        let logo = dir.path().join("logo.png");
+        std::fs::write(&logo, PNG).unwrap();
+        assert!(load_logo(&logo).is_ok());
+
+        let out = dir.path().join("report.pdf");
+        let branding = Branding { title: Some("Acme Audit"), logo: Some(&logo) };
+        render(&[], dir.path(), &out, "2024-03-14T09:21:00Z", &branding).unwrap();
+        assert!(std::fs::read(&out).unwrap().starts_with(b"%PDF"));
+
+        // An undecodable logo falls back to the standard cover
+        let bogus = dir.path().join("bogus.png");
+        std::fs::write(&bogus, b"not a png").unwrap();
+        assert!(load_logo(&bogus).is_err());
+        let branding = Branding { title: None, logo: Some(&bogus) };
+        render(&[], dir.path(), &out, "2024-03-14T09:21:00Z", &branding).unwrap();
+    }
+
+    #[test]
+    fn test_ecosystem_bars_sorted_and_capped() {
+        let counts = |pairs: &[(&str, usize)]| {
+            pairs.iter().map(|(e, n)| (e.to_string(), *n)).collect()
+        };
+
+        let single = ecosystem_bars(&counts(&[("Rust", 12)]));
+        assert_eq!(single, [("Rust".to_string(), 12)]);
+
+        let bars = ecosystem_bars(&counts(&[
+            ("Go", 1), ("Java", 9), ("Node", 40), ("PHP", 2),
+            ("Python", 9), ("Ruby", 3), ("Rust", 12), ("Deno", 1),
+        ]));
+        let names: Vec<&str> = bars.iter().map(|(e, _)| e.as_str()).collect();
+        assert_eq!(names, ["Node", "Rust", "Java", "Python", "Ruby", "Other"]);
+        // PHP + Deno + Go
+        assert_eq!(bars[5].1, 4);
+        assert_eq!(bars.len(), ECO_MAX_BARS);
+    }
 }
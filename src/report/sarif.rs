@@ -0,0 +1,330 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::models::{Dependency, Ecosystem, PolicyVerdict, ProjectScan};
+
+/// A SARIF 2.1.0 log with a single `license-checkr` run.
+#[derive(Serialize)]
+struct Log {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: [Run; 1],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run {
+    tool: Tool,
+    /// Where [`SRCROOT`] points: the scan root, as an absolute `file://` URI.
+    original_uri_base_ids: BTreeMap<&'static str, ArtifactLocation>,
+    results: Vec<SarifResult>,
+}
+
+/// Base id of the scan root, which result paths are relative to.
+const SRCROOT: &str = "SRCROOT";
+
+#[derive(Serialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<Rule>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    id: String,
+    short_description: Message,
+}
+
+#[derive(Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    level: &'static str,
+    message: Message,
+    locations: [Location; 1],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactLocation {
+    uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uri_base_id: Option<&'static str>,
+}
+
+/// Serialize the policy findings of each `(project directory, dependencies)`
+/// pair as a pretty-printed SARIF 2.1.0 log.
+///
+/// `error` verdicts become `error` results, `warn` verdicts `warning` and
+/// `review` verdicts `note`; passing dependencies are left out. Every result
+/// points at the project's manifest for the dependency's ecosystem, since code
+/// scanning needs a file to attach it to, as a path relative to `root` (the
+/// [`SRCROOT`] base id). Rules are named `license-policy/<license>`.
+pub fn to_sarif<'a>(
+    root: &Path,
+    projects: impl IntoIterator<Item = (&'a Path, &'a [Dependency])>,
+) -> Result<String> {
+    let root = std::path::absolute(root)
+        .with_context(|| format!("Failed to resolve {}", root.display()))?;
+    let mut rules: BTreeMap<String, Rule> = BTreeMap::new();
+    let mut results = Vec::new();
+    for (dir, deps) in projects {
+        for dep in deps {
+            let level = match dep.verdict {
+                PolicyVerdict::Pass => continue,
+                PolicyVerdict::Warn => "warning",
+                PolicyVerdict::Review => "note",
+                PolicyVerdict::Error => "error",
+            };
+            let license = dep
+                .license_spdx
+                .as_deref()
+                .or(dep.license_raw.as_deref())
+                .unwrap_or("unknown");
+            let rule_id = format!("license-policy/{}", license);
+            rules.entry(rule_id.clone()).or_insert_with(|| Rule {
+                id: rule_id.clone(),
+                short_description: Message {
+                    text: format!("Dependency licensed under {}", license),
+                },
+            });
+            results.push(SarifResult {
+                rule_id,
+                level,
+                message: Message {
+                    text: format!(
+                        "{} {} ({}) is licensed under {}: {} under the license policy",
+                        dep.name, dep.version, dep.ecosystem, license, dep.verdict
+                    ),
+                },
+                locations: [Location {
+                    physical_location: PhysicalLocation {
+                        artifact_location: manifest_location(&root, dir, &dep.ecosystem),
+                    },
+                }],
+            });
+        }
+    }
+
+    let log = Log {
+        schema: "https://json.schemastore.org/sarif-2.1.0.json",
+        version: "2.1.0",
+        runs: [Run {
+            tool: Tool {
+                driver: Driver {
+                    name: "license-checkr",
+                    version: env!("CARGO_PKG_VERSION"),
+                    information_uri: "https://github.com/QuentinRob/license-checkr",
+                    rules: rules.into_values().collect(),
+                },
+            },
+            original_uri_base_ids: BTreeMap::from([(
+                SRCROOT,
+                ArtifactLocation {
+                    uri: format!("{}/", file_uri(&root).trim_end_matches('/')),
+                    uri_base_id: None,
+                },
+            )]),
+            results,
+        }],
+    };
+    Ok(serde_json::to_string_pretty(&log)?)
+}
+
+/// Files declaring an ecosystem's dependencies, lockfiles first.
+fn manifest_candidates(ecosystem: &Ecosystem) -> &'static [&'static str] {
+    match ecosystem {
+        Ecosystem::Rust => &["Cargo.lock", "Cargo.toml"],
+        Ecosystem::Python => &[
+            "poetry.lock",
+            "Pipfile.lock",
+            "requirements.txt",
+            "pyproject.toml",
+        ],
+        Ecosystem::Conda => &["conda-lock.yml"],
+        Ecosystem::Java => &["pom.xml", "build.gradle.kts", "build.gradle"],
+        Ecosystem::Clojure => &["deps.edn", "project.clj"],
+        Ecosystem::Node => &[
+            "package-lock.json",
+            "yarn.lock",
+            "pnpm-lock.yaml",
+            "package.json",
+        ],
+        Ecosystem::Deno => &["deno.lock", "deno.json"],
+        Ecosystem::DotNet => &["packages.config", "paket.dependencies"],
+        Ecosystem::OCaml => &["dune-project"],
+        Ecosystem::Go => &["go.mod"],
+        Ecosystem::Ruby => &["Gemfile.lock", "Gemfile"],
+        Ecosystem::Php => &["composer.lock", "composer.json"],
+    }
+}
+
+/// Location of the first manifest of `ecosystem` found in `dir`, or of `dir`
+/// itself (e.g. .NET project files, whose names vary): relative to the absolute
+/// `root` when it lies inside it, else an absolute `file://` URI.
+fn manifest_location(root: &Path, dir: &Path, ecosystem: &Ecosystem) -> ArtifactLocation {
+    let path = manifest_candidates(ecosystem)
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| dir.to_path_buf());
+    let path = std::path::absolute(&path).unwrap_or(path);
+    match path.strip_prefix(root) {
+        Ok(relative) => ArtifactLocation {
+            uri: uri_path(relative),
+            uri_base_id: Some(SRCROOT),
+        },
+        Err(_) => ArtifactLocation {
+            uri: file_uri(&path),
+            uri_base_id: None,
+        },
+    }
+}
+
+/// `/`-separated `path`, percent-encoding bytes that may not appear in a URI path.
+fn uri_path(path: &Path) -> String {
+    let mut uri = String::new();
+    for byte in path.to_string_lossy().replace('\\', "/").bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/:@+".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    uri
+}
+
+/// `file://` URI of the absolute `path` (`file:///C:/…` on Windows).
+fn file_uri(path: &Path) -> String {
+    format!("file:///{}", uri_path(path).trim_start_matches('/'))
+}
+
+/// Print the SARIF log to stdout, with paths relative to `scan_path`.
+pub fn render(deps: &[Dependency], scan_path: &Path) -> Result<()> {
+    println!("{}", to_sarif(scan_path, [(scan_path, deps)])?);
+    Ok(())
+}
+
+/// Print one SARIF log covering every project, with paths relative to `root`.
+pub fn render_workspace(projects: &[ProjectScan], root: &Path) -> Result<()> {
+    println!("{}", to_sarif(root, workspace(projects))?);
+    Ok(())
+}
+
+/// Write the [`render`] output to `path` instead of stdout.
+pub fn write(deps: &[Dependency], scan_path: &Path, path: &Path) -> Result<()> {
+    std::fs::write(path, to_sarif(scan_path, [(scan_path, deps)])?)
+        .with_context(|| format!("Failed to write SARIF report to {}", path.display()))
+}
+
+/// Write the [`render_workspace`] output to `path` instead of stdout.
+pub fn write_workspace(projects: &[ProjectScan], root: &Path, path: &Path) -> Result<()> {
+    std::fs::write(path, to_sarif(root, workspace(projects))?)
+        .with_context(|| format!("Failed to write SARIF report to {}", path.display()))
+}
+
+fn workspace(projects: &[ProjectScan]) -> impl Iterator<Item = (&Path, &[Dependency])> {
+    projects
+        .iter()
+        .map(|p| (p.path.as_path(), p.deps.as_slice()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::dep;
+
+    #[test]
+    fn test_sarif_results_skip_passing_deps() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("package-lock.json"), "{}").unwrap();
+        let deps = vec![
+            dep("express", Some("MIT"), PolicyVerdict::Pass),
+            dep("gpl-lib", Some("GPL-3.0"), PolicyVerdict::Error),
+            dep("other-gpl", Some("GPL-3.0"), PolicyVerdict::Error),
+            dep("mystery", None, PolicyVerdict::Warn),
+        ];
+
+        let json: serde_json::Value =
+            serde_json::from_str(&to_sarif(tmp.path(), [(tmp.path(), deps.as_slice())]).unwrap())
+                .unwrap();
+        assert_eq!(json["version"], "2.1.0");
+        let results = json["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["ruleId"], "license-policy/GPL-3.0");
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(
+            results[0]["message"]["text"],
+            "gpl-lib 1.0.0 (Node) is licensed under GPL-3.0: error under the license policy"
+        );
+        let location = &results[0]["locations"][0]["physicalLocation"]["artifactLocation"];
+        assert_eq!(location["uri"], "package-lock.json");
+        assert_eq!(location["uriBaseId"], "SRCROOT");
+        let root = json["runs"][0]["originalUriBaseIds"]["SRCROOT"]["uri"]
+            .as_str()
+            .unwrap();
+        assert!(
+            root.starts_with("file:///") && root.ends_with('/'),
+            "{}",
+            root
+        );
+        assert_eq!(results[2]["ruleId"], "license-policy/unknown");
+        assert_eq!(results[2]["level"], "warning");
+        // One rule per license
+        assert_eq!(
+            json["runs"][0]["tool"]["driver"]["rules"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_sarif_workspace_paths_are_relative_to_the_root() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let web = tmp.path().join("apps").join("my web");
+        std::fs::create_dir_all(&web).unwrap();
+        std::fs::write(web.join("package.json"), "{}").unwrap();
+        let deps = vec![dep("gpl-lib", Some("GPL-3.0"), PolicyVerdict::Error)];
+
+        let json: serde_json::Value = serde_json::from_str(
+            &to_sarif(tmp.path(), [(web.as_path(), deps.as_slice())]).unwrap(),
+        )
+        .unwrap();
+        let location =
+            &json["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"];
+        assert_eq!(location["uri"], "apps/my%20web/package.json");
+        assert_eq!(location["uriBaseId"], "SRCROOT");
+    }
+}
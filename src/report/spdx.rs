@@ -0,0 +1,263 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+
+use super::cyclonedx::purl;
+use crate::license::expression::{tokenize_spdx, Token};
+use crate::license::spdx::classify_spdx_id;
+use crate::models::{Dependency, LicenseRisk, ProjectScan};
+
+const NOASSERTION: &str = "NOASSERTION";
+
+/// An SPDX 2.3 JSON document with one package per dependency.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Document {
+    spdx_version: &'static str,
+    data_license: &'static str,
+    #[serde(rename = "SPDXID")]
+    spdx_id: &'static str,
+    name: String,
+    document_namespace: String,
+    creation_info: CreationInfo,
+    packages: Vec<Package>,
+    relationships: Vec<Relationship>,
+}
+
+#[derive(Serialize)]
+struct CreationInfo {
+    created: String,
+    creators: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Package {
+    #[serde(rename = "SPDXID")]
+    spdx_id: String,
+    name: String,
+    version_info: String,
+    download_location: &'static str,
+    files_analyzed: bool,
+    license_concluded: String,
+    license_declared: &'static str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    external_refs: Vec<ExternalRef>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExternalRef {
+    reference_category: &'static str,
+    reference_type: &'static str,
+    reference_locator: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Relationship {
+    spdx_element_id: &'static str,
+    relationship_type: &'static str,
+    related_spdx_element: String,
+}
+
+/// Serialize `deps` as a pretty-printed SPDX 2.3 JSON document named `name`.
+///
+/// `licenseConcluded` is the dependency's SPDX expression when every id in it is
+/// on the SPDX license list, else `NOASSERTION`; the download location is never
+/// asserted. Packages carry their package URL as an external reference.
+pub fn to_spdx<'a>(
+    deps: impl IntoIterator<Item = &'a Dependency>,
+    name: &str,
+    scanned_at: &str,
+) -> Result<String> {
+    // SPDX requires UTC, even when the reports show local time (`--local-time`)
+    let created = DateTime::parse_from_rfc3339(scanned_at)
+        .map(|t| {
+            t.with_timezone(&Utc)
+                .to_rfc3339_opts(SecondsFormat::Secs, true)
+        })
+        .unwrap_or_else(|_| scanned_at.to_string());
+
+    let packages: Vec<Package> = deps
+        .into_iter()
+        .enumerate()
+        .map(|(i, dep)| package(i + 1, dep))
+        .collect();
+    let relationships = packages
+        .iter()
+        .map(|p| Relationship {
+            spdx_element_id: "SPDXRef-DOCUMENT",
+            relationship_type: "DESCRIBES",
+            related_spdx_element: p.spdx_id.clone(),
+        })
+        .collect();
+
+    let document = Document {
+        spdx_version: "SPDX-2.3",
+        data_license: "CC0-1.0",
+        spdx_id: "SPDXRef-DOCUMENT",
+        name: name.to_string(),
+        document_namespace: format!(
+            "https://spdx.org/spdxdocs/{}-{}",
+            id_safe(name),
+            id_safe(&created)
+        ),
+        creation_info: CreationInfo {
+            created,
+            creators: vec![format!(
+                "Tool: license-checkr-{}",
+                env!("CARGO_PKG_VERSION")
+            )],
+        },
+        packages,
+        relationships,
+    };
+    Ok(serde_json::to_string_pretty(&document)?)
+}
+
+fn package(index: usize, dep: &Dependency) -> Package {
+    Package {
+        spdx_id: format!("SPDXRef-Package-{}-{}", index, id_safe(&dep.name)),
+        name: dep.name.clone(),
+        version_info: dep.version.clone(),
+        download_location: NOASSERTION,
+        files_analyzed: false,
+        license_concluded: dep
+            .license_spdx
+            .as_deref()
+            .filter(|spdx| is_listed_expression(spdx))
+            .unwrap_or(NOASSERTION)
+            .to_string(),
+        license_declared: NOASSERTION,
+        external_refs: purl(dep)
+            .map(|locator| ExternalRef {
+                reference_category: "PACKAGE-MANAGER",
+                reference_type: "purl",
+                reference_locator: locator,
+            })
+            .into_iter()
+            .collect(),
+    }
+}
+
+/// Whether every license id of `expr` is one we know from the SPDX list
+/// (exceptions after `WITH` are not checked).
+fn is_listed_expression(expr: &str) -> bool {
+    let tokens = tokenize_spdx(expr);
+    let mut after_with = false;
+    let mut any_id = false;
+    for token in &tokens {
+        match token {
+            Token::Id(id) if !after_with => {
+                if classify_spdx_id(id) == LicenseRisk::Unknown {
+                    return false;
+                }
+                any_id = true;
+            }
+            _ => {}
+        }
+        after_with = *token == Token::With;
+    }
+    any_id
+}
+
+/// `text` with every character not allowed in an SPDX id (`[A-Za-z0-9.-]`)
+/// replaced by `-`.
+fn id_safe(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Print the SPDX document to stdout.
+pub fn render(deps: &[Dependency], scan_path: &Path, scanned_at: &str) -> Result<()> {
+    println!(
+        "{}",
+        to_spdx(deps, &super::project_name(scan_path), scanned_at)?
+    );
+    Ok(())
+}
+
+/// Print one SPDX document covering every project.
+pub fn render_workspace(projects: &[ProjectScan], scanned_at: &str) -> Result<()> {
+    println!(
+        "{}",
+        to_spdx(
+            projects.iter().flat_map(|p| &p.deps),
+            "workspace",
+            scanned_at
+        )?
+    );
+    Ok(())
+}
+
+/// Write the [`render`] output to `path` instead of stdout.
+pub fn write(deps: &[Dependency], scan_path: &Path, scanned_at: &str, path: &Path) -> Result<()> {
+    std::fs::write(
+        path,
+        to_spdx(deps, &super::project_name(scan_path), scanned_at)?,
+    )
+    .with_context(|| format!("Failed to write SPDX report to {}", path.display()))
+}
+
+/// Write the [`render_workspace`] output to `path` instead of stdout.
+pub fn write_workspace(projects: &[ProjectScan], scanned_at: &str, path: &Path) -> Result<()> {
+    let document = to_spdx(
+        projects.iter().flat_map(|p| &p.deps),
+        "workspace",
+        scanned_at,
+    )?;
+    std::fs::write(path, document)
+        .with_context(|| format!("Failed to write SPDX report to {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{dep, Ecosystem, PolicyVerdict};
+
+    #[test]
+    fn test_spdx_packages() {
+        let deps = vec![
+            dep("serde", Some("MIT OR Apache-2.0"), PolicyVerdict::Pass)
+                .with_ecosystem(Ecosystem::Rust),
+            dep("@babel/core", Some("Custom license"), PolicyVerdict::Pass)
+                .with_ecosystem(Ecosystem::Node),
+            dep("requests", None, PolicyVerdict::Pass).with_ecosystem(Ecosystem::Python),
+        ];
+        let json: serde_json::Value =
+            serde_json::from_str(&to_spdx(&deps, "my app", "2024-03-14T10:21:00+01:00").unwrap())
+                .unwrap();
+
+        assert_eq!(json["spdxVersion"], "SPDX-2.3");
+        assert_eq!(json["SPDXID"], "SPDXRef-DOCUMENT");
+        assert_eq!(json["creationInfo"]["created"], "2024-03-14T09:21:00Z");
+        assert!(json["creationInfo"]["creators"][0]
+            .as_str()
+            .unwrap()
+            .starts_with("Tool: license-checkr-"));
+        let packages = json["packages"].as_array().unwrap();
+        assert_eq!(packages.len(), 3);
+        assert_eq!(json["relationships"].as_array().unwrap().len(), 3);
+
+        assert_eq!(packages[0]["SPDXID"], "SPDXRef-Package-1-serde");
+        assert_eq!(packages[0]["licenseConcluded"], "MIT OR Apache-2.0");
+        assert_eq!(packages[0]["downloadLocation"], "NOASSERTION");
+        assert_eq!(
+            packages[0]["externalRefs"][0]["referenceLocator"],
+            "pkg:cargo/serde@1.0.0"
+        );
+        assert_eq!(packages[1]["SPDXID"], "SPDXRef-Package-2--babel-core");
+        assert_eq!(packages[1]["licenseConcluded"], "NOASSERTION");
+        assert_eq!(packages[2]["licenseConcluded"], "NOASSERTION");
+    }
+}
@@ -0,0 +1,132 @@
+//! `--summary-format`: a user-defined template for the one-line `--quiet`
+//! terminal summary, e.g. `total={total} pass={pass} warn={warn} error={error}`.
+
+use std::str::FromStr;
+
+use super::json::Counts;
+
+/// Placeholders accepted in a summary template.
+const PLACEHOLDERS: &[&str] = &["total", "pass", "warn", "review", "error", "projects"];
+
+/// A parsed `--summary-format` template. Unknown placeholders are rejected when
+/// parsing, so a typo fails at argument parsing rather than in the output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SummaryFormat {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    Placeholder(&'static str),
+}
+
+impl FromStr for SummaryFormat {
+    type Err = String;
+
+    /// Parse `{name}` placeholders; `{{` and `}}` are literal braces.
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(format!("unclosed placeholder {{{}", name)),
+                        }
+                    }
+                    let placeholder =
+                        PLACEHOLDERS.iter().find(|&&p| p == name).ok_or_else(|| {
+                            format!(
+                                "unknown placeholder {{{}}} (expected one of: {})",
+                                name,
+                                PLACEHOLDERS
+                                    .iter()
+                                    .map(|p| format!("{{{}}}", p))
+                                    .collect::<Vec<_>>()
+                                    .join(" ")
+                            )
+                        })?;
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Placeholder(placeholder));
+                }
+                '}' => return Err("unmatched '}' (use '}}' for a literal brace)".to_string()),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+        Ok(SummaryFormat { segments })
+    }
+}
+
+impl SummaryFormat {
+    /// Fill the template from verdict `counts` over `projects` scanned projects.
+    pub fn render(&self, counts: &Counts, projects: usize) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(text) => text.clone(),
+                Segment::Placeholder(name) => match *name {
+                    "total" => counts.total,
+                    "pass" => counts.pass,
+                    "warn" => counts.warn,
+                    "review" => counts.review,
+                    "error" => counts.error,
+                    _ => projects,
+                }
+                .to_string(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_custom_template() {
+        let format: SummaryFormat =
+            "total={total} pass={pass} warn={warn} error={error} projects={projects} {{ok}}"
+                .parse()
+                .unwrap();
+        let counts = Counts {
+            total: 120,
+            pass: 100,
+            warn: 18,
+            error: 2,
+            ..Counts::default()
+        };
+        assert_eq!(
+            format.render(&counts, 3),
+            "total=120 pass=100 warn=18 error=2 projects=3 {ok}"
+        );
+    }
+
+    #[test]
+    fn test_unknown_placeholder_is_rejected() {
+        let err = "total={total} fail={failed}"
+            .parse::<SummaryFormat>()
+            .unwrap_err();
+        assert!(err.starts_with("unknown placeholder {failed}"));
+        assert!("{total".parse::<SummaryFormat>().is_err());
+        assert!("total}".parse::<SummaryFormat>().is_err());
+    }
+}
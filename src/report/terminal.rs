@@ -5,13 +5,32 @@ use colored::*;
 use comfy_table::presets::UTF8_FULL;
 use comfy_table::{Attribute, Cell, CellAlignment, Color, ContentArrangement, Table};
 
+use super::json::Counts;
+use super::RenderOptions;
+use crate::cli::{Column, GroupBy, SortKey};
 use crate::models::{Dependency, LicenseRisk, PolicyVerdict, ProjectScan};
+use crate::version::compare_versions;
+
+/// Table columns without `--columns`.
+const ALL_COLUMNS: [Column; 6] = [
+    Column::Name,
+    Column::Version,
+    Column::Ecosystem,
+    Column::License,
+    Column::Risk,
+    Column::Verdict,
+];
 
 /// Render a colored terminal report.
-pub fn render(deps: &[Dependency], path: &Path, verbose: bool, quiet: bool) -> Result<()> {
+///
+/// `opts.min_risk` hides table rows below the given risk level; summary counts
+/// always cover every dependency.
+pub fn render(deps: &[Dependency], path: &Path, opts: &RenderOptions<'_>) -> Result<()> {
+    let quiet = opts.quiet;
     let total = deps.len();
     let pass_count = deps.iter().filter(|d| d.verdict == PolicyVerdict::Pass).count();
     let warn_count = deps.iter().filter(|d| d.verdict == PolicyVerdict::Warn).count();
+    let review_count = deps.iter().filter(|d| d.verdict == PolicyVerdict::Review).count();
     let error_count = deps.iter().filter(|d| d.verdict == PolicyVerdict::Error).count();
 
     if !quiet {
@@ -20,20 +39,27 @@ pub fn render(deps: &[Dependency], path: &Path, verbose: bool, quiet: bool) -> R
             "license-checkr".bold(),
             env!("CARGO_PKG_VERSION")
         );
-        println!(" Scanning: {}\n", path.display());
+        println!(" Scanning: {}", path.display());
+        println!(" Scanned:  {}\n", opts.scanned_at);
     }
 
     // Summary box
     let pass_licenses = summarize_licenses(deps, &PolicyVerdict::Pass);
     let warn_licenses = summarize_licenses(deps, &PolicyVerdict::Warn);
+    let review_licenses = summarize_licenses(deps, &PolicyVerdict::Review);
     let error_licenses = summarize_licenses(deps, &PolicyVerdict::Error);
 
     if quiet {
+        if let Some(format) = opts.summary_format {
+            println!("{}", format.render(&Counts::from_deps(deps, opts.scanned_at), 1));
+            return Ok(());
+        }
         println!(
-            "Total: {}  Pass: {}  Warn: {}  Error: {}",
+            "Total: {}  Pass: {}  Warn: {}{}  Error: {}",
             total,
             pass_count.to_string().green(),
             warn_count.to_string().yellow(),
+            review_suffix(review_count),
             error_count.to_string().red(),
         );
         return Ok(());
@@ -63,6 +89,17 @@ pub fn render(deps: &[Dependency], path: &Path, verbose: bool, quiet: bool) -> R
             warn_licenses
         )
     );
+    if review_count > 0 {
+        println!(
+            " │  {:<48} │",
+            format!(
+                "{}  Review          : {:>4}  {}",
+                "◆".blue(),
+                review_count,
+                review_licenses
+            )
+        );
+    }
     println!(
         " │  {:<48} │",
         format!(
@@ -74,46 +111,36 @@ pub fn render(deps: &[Dependency], path: &Path, verbose: bool, quiet: bool) -> R
     );
     println!(" └────────────────────────────────────────────────────┘\n");
 
-    // Error table
-    if error_count > 0 {
-        println!(" {} Dependencies requiring attention:\n", "[ERROR]".red().bold());
-        render_table(deps, &PolicyVerdict::Error);
-        println!();
-    }
-
-    // Warn table
-    if warn_count > 0 {
-        println!(" {} Dependencies with warnings:\n", "[WARN]".yellow().bold());
-        render_table(deps, &PolicyVerdict::Warn);
-        println!();
-    }
-
-    // Verbose: show all passing
-    if verbose && pass_count > 0 {
-        println!(" {} All passing dependencies:\n", "[PASS]".green().bold());
-        render_table(deps, &PolicyVerdict::Pass);
-        println!();
-    }
+    render_dep_tables(deps, opts);
+    render_risk_legend();
 
     Ok(())
 }
 
 /// Render a workspace report: aggregated summary + per-project sections.
-pub fn render_workspace(projects: &[ProjectScan], verbose: bool, quiet: bool) -> Result<()> {
+pub fn render_workspace(projects: &[ProjectScan], opts: &RenderOptions<'_>) -> Result<()> {
+    let quiet = opts.quiet;
     let all_deps: Vec<&Dependency> = projects.iter().flat_map(|p| &p.deps).collect();
     let total = all_deps.len();
     let pass_count = all_deps.iter().filter(|d| d.verdict == PolicyVerdict::Pass).count();
     let warn_count = all_deps.iter().filter(|d| d.verdict == PolicyVerdict::Warn).count();
+    let review_count = all_deps.iter().filter(|d| d.verdict == PolicyVerdict::Review).count();
     let error_count = all_deps.iter().filter(|d| d.verdict == PolicyVerdict::Error).count();
 
     if quiet {
+        if let Some(format) = opts.summary_format {
+            let counts = Counts::from_deps(all_deps.iter().copied(), opts.scanned_at);
+            println!("{}", format.render(&counts, projects.len()));
+            return Ok(());
+        }
         println!(
-            "Workspace — {} project{}  Total: {}  Pass: {}  Warn: {}  Error: {}",
+            "Workspace — {} project{}  Total: {}  Pass: {}  Warn: {}{}  Error: {}",
             projects.len(),
             if projects.len() == 1 { "" } else { "s" },
             total,
             pass_count.to_string().green(),
             warn_count.to_string().yellow(),
+            review_suffix(review_count),
             error_count.to_string().red(),
         );
         return Ok(());
@@ -122,6 +149,7 @@ pub fn render_workspace(projects: &[ProjectScan], verbose: bool, quiet: bool) ->
     // Aggregated workspace summary box
     let pass_licenses = summarize_licenses_refs(&all_deps, &PolicyVerdict::Pass);
     let warn_licenses = summarize_licenses_refs(&all_deps, &PolicyVerdict::Warn);
+    let review_licenses = summarize_licenses_refs(&all_deps, &PolicyVerdict::Review);
     let error_licenses = summarize_licenses_refs(&all_deps, &PolicyVerdict::Error);
 
     println!(" ┌────────────────────────────────────────────────────┐");
@@ -130,6 +158,10 @@ pub fn render_workspace(projects: &[ProjectScan], verbose: bool, quiet: bool) ->
         " │  {:<48} │",
         format!("Projects           : {}", projects.len())
     );
+    println!(
+        " │  {:<48} │",
+        format!("Scanned at         : {}", opts.scanned_at)
+    );
     println!(
         " │  {:<48} │",
         format!("Total dependencies : {}", total)
@@ -152,6 +184,17 @@ pub fn render_workspace(projects: &[ProjectScan], verbose: bool, quiet: bool) ->
             warn_licenses
         )
     );
+    if review_count > 0 {
+        println!(
+            " │  {:<48} │",
+            format!(
+                "{}  Review          : {:>4}  {}",
+                "◆".blue(),
+                review_count,
+                review_licenses
+            )
+        );
+    }
     println!(
         " │  {:<48} │",
         format!(
@@ -168,6 +211,7 @@ pub fn render_workspace(projects: &[ProjectScan], verbose: bool, quiet: bool) ->
         let p_total = proj.deps.len();
         let p_pass = proj.deps.iter().filter(|d| d.verdict == PolicyVerdict::Pass).count();
         let p_warn = proj.deps.iter().filter(|d| d.verdict == PolicyVerdict::Warn).count();
+        let p_review = proj.deps.iter().filter(|d| d.verdict == PolicyVerdict::Review).count();
         let p_err = proj.deps.iter().filter(|d| d.verdict == PolicyVerdict::Error).count();
 
         println!(
@@ -177,83 +221,241 @@ pub fn render_workspace(projects: &[ProjectScan], verbose: bool, quiet: bool) ->
             proj.path.display()
         );
         println!(
-            "     Total: {}  Pass: {}  Warn: {}  Error: {}\n",
+            "     Total: {}  Pass: {}  Warn: {}{}  Error: {}\n",
             p_total,
             p_pass.to_string().green(),
             p_warn.to_string().yellow(),
+            review_suffix(p_review),
             p_err.to_string().red(),
         );
 
-        if p_err > 0 {
-            println!(" {} Dependencies requiring attention:\n", "[ERROR]".red().bold());
-            render_table(&proj.deps, &PolicyVerdict::Error);
-            println!();
-        }
+        render_dep_tables(&proj.deps, opts);
+    }
 
-        if p_warn > 0 {
-            println!(" {} Dependencies with warnings:\n", "[WARN]".yellow().bold());
-            render_table(&proj.deps, &PolicyVerdict::Warn);
-            println!();
+    render_risk_legend();
+
+    Ok(())
+}
+
+/// Print the verdict tables of `deps`, or with `--group-by ecosystem` one
+/// section per ecosystem holding that ecosystem's tables and counts.
+fn render_dep_tables(deps: &[Dependency], opts: &RenderOptions<'_>) {
+    let deps: Vec<&Dependency> = deps.iter().collect();
+    match opts.group_by {
+        None => render_verdict_tables(&deps, opts),
+        Some(GroupBy::Ecosystem) => {
+            for (ecosystem, group) in group_by_ecosystem(&deps) {
+                println!(" {} {}", "▸".dimmed(), ecosystem.bold());
+                println!("   {}\n", count_line(&group));
+                render_verdict_tables(&group, opts);
+            }
         }
+    }
+}
+
+/// `deps` split by ecosystem, ordered by the ecosystem's display name.
+fn group_by_ecosystem<'a>(deps: &[&'a Dependency]) -> Vec<(String, Vec<&'a Dependency>)> {
+    let mut groups: std::collections::BTreeMap<String, Vec<&Dependency>> =
+        std::collections::BTreeMap::new();
+    for dep in deps {
+        groups
+            .entry(dep.ecosystem.to_string())
+            .or_default()
+            .push(dep);
+    }
+    groups.into_iter().collect()
+}
+
+/// `Total: N  Pass: N  Warn: N  Error: N` for a section of the report.
+fn count_line(deps: &[&Dependency]) -> String {
+    let count = |verdict: PolicyVerdict| deps.iter().filter(|d| d.verdict == verdict).count();
+    format!(
+        "Total: {}  Pass: {}  Warn: {}{}  Error: {}",
+        deps.len(),
+        count(PolicyVerdict::Pass).to_string().green(),
+        count(PolicyVerdict::Warn).to_string().yellow(),
+        review_suffix(count(PolicyVerdict::Review)),
+        count(PolicyVerdict::Error).to_string().red(),
+    )
+}
+
+/// Print the error, review and warn tables (plus pass when `verbose`), skipping
+/// empty sections. Rows follow `--sort` and show the `--columns`.
+fn render_verdict_tables(deps: &[&Dependency], opts: &RenderOptions<'_>) {
+    let mut sections = vec![
+        (
+            PolicyVerdict::Error,
+            "[ERROR]".red().bold(),
+            "Dependencies requiring attention:",
+        ),
+        (
+            PolicyVerdict::Review,
+            "[REVIEW]".blue().bold(),
+            "Dependencies requiring review:",
+        ),
+        (
+            PolicyVerdict::Warn,
+            "[WARN]".yellow().bold(),
+            "Dependencies with warnings:",
+        ),
+    ];
+    if opts.verbose {
+        sections.push((
+            PolicyVerdict::Pass,
+            "[PASS]".green().bold(),
+            "All passing dependencies:",
+        ));
+    }
 
-        if verbose && p_pass > 0 {
-            println!(" {} All passing dependencies:\n", "[PASS]".green().bold());
-            render_table(&proj.deps, &PolicyVerdict::Pass);
-            println!();
+    for (verdict, tag, title) in sections {
+        let mut rows = visible_rows(deps.iter().copied(), &verdict, opts.min_risk);
+        if rows.is_empty() {
+            continue;
         }
+        if let Some(key) = opts.sort {
+            sort_rows(&mut rows, key);
+        }
+        println!(" {} {}\n", tag, title);
+        println!("{}", build_table(&rows, opts.columns));
+        println!();
     }
+}
 
-    Ok(())
+/// Stable sort of table rows by `key`: names, versions and licenses ascending,
+/// risks and verdicts most severe first.
+fn sort_rows(rows: &mut [&Dependency], key: &SortKey) {
+    match key {
+        SortKey::Name => rows.sort_by_key(|d| d.name.to_lowercase()),
+        SortKey::Version => rows.sort_by(|a, b| compare_versions(&a.version, &b.version)),
+        SortKey::License => rows.sort_by_key(|d| license_of(d).to_lowercase()),
+        // Unknown risk has no severity and sorts after every ranked risk
+        SortKey::Risk => rows.sort_by_key(|d| std::cmp::Reverse(d.risk.severity().map(|s| s + 1))),
+        SortKey::Verdict => rows.sort_by_key(|d| std::cmp::Reverse(d.verdict.severity())),
+    }
+}
+
+/// `"  Review: N"` for the one-line summaries, or nothing when no dependency
+/// needs review — keeps the common three-verdict output unchanged.
+fn review_suffix(count: usize) -> String {
+    if count == 0 {
+        return String::new();
+    }
+    format!("  Review: {}", count.to_string().blue())
+}
+
+/// Dependencies with the given verdict whose risk is at or above `min_risk`.
+fn visible_rows<'a>(
+    deps: impl IntoIterator<Item = &'a Dependency>,
+    verdict: &PolicyVerdict,
+    min_risk: Option<&LicenseRisk>,
+) -> Vec<&'a Dependency> {
+    deps.into_iter()
+        .filter(|d| &d.verdict == verdict)
+        .filter(|d| match min_risk {
+            Some(min) => d.risk.at_least(min),
+            None => true,
+        })
+        .collect()
+}
+
+/// Explain the risk column colors.
+fn render_risk_legend() {
+    println!(
+        " Risk: {}  {}  {}  {}  {}\n",
+        "● Permissive".green(),
+        "● Weak Copyleft".yellow(),
+        "● Strong Copyleft".red(),
+        "● Proprietary".magenta(),
+        "● Unknown".dimmed(),
+    );
 }
 
-fn render_table(deps: &[Dependency], verdict_filter: &PolicyVerdict) {
+/// Table of `deps` with the given columns (every column when empty).
+fn build_table(deps: &[&Dependency], columns: &[Column]) -> Table {
+    let columns = if columns.is_empty() {
+        &ALL_COLUMNS[..]
+    } else {
+        columns
+    };
     let mut table = Table::new();
     table
         .load_preset(UTF8_FULL)
         .set_content_arrangement(ContentArrangement::Dynamic)
-        .set_header(vec![
-            Cell::new("Name").add_attribute(Attribute::Bold),
-            Cell::new("Version").add_attribute(Attribute::Bold),
-            Cell::new("Ecosystem").add_attribute(Attribute::Bold),
-            Cell::new("License").add_attribute(Attribute::Bold),
-            Cell::new("Risk").add_attribute(Attribute::Bold),
-            Cell::new("Verdict").add_attribute(Attribute::Bold),
-        ]);
-
-    for dep in deps.iter().filter(|d| &d.verdict == verdict_filter) {
-        let license = dep
-            .license_spdx
-            .as_deref()
-            .or(dep.license_raw.as_deref())
-            .unwrap_or("unknown");
-
-        let (verdict_str, verdict_color) = match dep.verdict {
-            PolicyVerdict::Pass => ("✓ pass", Color::Green),
-            PolicyVerdict::Warn => ("⚠ warn", Color::Yellow),
-            PolicyVerdict::Error => ("✗ error", Color::Red),
-        };
-
-        let risk_color = match dep.risk {
-            LicenseRisk::Permissive => Color::Green,
-            LicenseRisk::WeakCopyleft => Color::Yellow,
-            LicenseRisk::StrongCopyleft => Color::Red,
-            LicenseRisk::Proprietary => Color::Magenta,
-            LicenseRisk::Unknown => Color::DarkGrey,
-        };
-
-        table.add_row(vec![
-            Cell::new(&dep.name),
-            Cell::new(&dep.version),
-            Cell::new(dep.ecosystem.to_string()),
-            Cell::new(license),
-            Cell::new(dep.risk.to_string()).fg(risk_color),
+        .set_header(
+            columns
+                .iter()
+                .map(|c| Cell::new(column_title(c)).add_attribute(Attribute::Bold)),
+        );
+    // `--no-color` / `NO_COLOR` also apply to the table cells
+    if !colored::control::SHOULD_COLORIZE.should_colorize() {
+        table.force_no_tty();
+    }
+
+    for dep in deps {
+        table.add_row(columns.iter().map(|c| cell(dep, c)));
+    }
+    table
+}
+
+fn column_title(column: &Column) -> &'static str {
+    match column {
+        Column::Name => "Name",
+        Column::Version => "Version",
+        Column::Ecosystem => "Ecosystem",
+        Column::License => "License",
+        Column::Risk => "Risk",
+        Column::Verdict => "Verdict",
+    }
+}
+
+/// The `column` cell of the row for `dep`.
+fn cell(dep: &Dependency, column: &Column) -> Cell {
+    match column {
+        Column::Name => {
+            // `--track-history` tags changes since the last run; annotations
+            // (e.g. patched crates) are shown beneath the name
+            let mut name = dep.name.clone();
+            if dep.is_new {
+                name.push_str("  NEW");
+            }
+            if !dep.notes.is_empty() {
+                name = format!("{}\n↳ {}", name, dep.notes.join("; "));
+            }
+            Cell::new(name)
+        }
+        Column::Version => Cell::new(&dep.version),
+        Column::Ecosystem => Cell::new(dep.ecosystem.to_string()),
+        Column::License => Cell::new(license_of(dep)),
+        Column::Risk => {
+            let risk_color = match dep.risk {
+                LicenseRisk::Permissive => Color::Green,
+                LicenseRisk::WeakCopyleft => Color::Yellow,
+                LicenseRisk::StrongCopyleft => Color::Red,
+                LicenseRisk::Proprietary => Color::Magenta,
+                LicenseRisk::Unknown => Color::DarkGrey,
+            };
+            Cell::new(dep.risk.to_string()).fg(risk_color)
+        }
+        Column::Verdict => {
+            let (verdict_str, verdict_color) = match dep.verdict {
+                PolicyVerdict::Pass => ("✓ pass", Color::Green),
+                PolicyVerdict::Warn => ("⚠ warn", Color::Yellow),
+                PolicyVerdict::Review => ("◆ review", Color::Blue),
+                PolicyVerdict::Error => ("✗ error", Color::Red),
+            };
             Cell::new(verdict_str)
                 .fg(verdict_color)
-                .set_alignment(CellAlignment::Center),
-        ]);
+                .set_alignment(CellAlignment::Center)
+        }
     }
+}
 
-    println!("{}", table);
+/// License shown for `dep`: the SPDX id, else the raw string, else `unknown`.
+fn license_of(dep: &Dependency) -> &str {
+    dep.license_spdx
+        .as_deref()
+        .or(dep.license_raw.as_deref())
+        .unwrap_or("unknown")
 }
 
 fn summarize_licenses_refs(deps: &[&Dependency], verdict: &PolicyVerdict) -> String {
@@ -268,7 +470,7 @@ fn summarize_licenses_refs(deps: &[&Dependency], verdict: &PolicyVerdict) -> Str
         *counts.entry(lic).or_insert(0) += 1;
     }
     let mut pairs: Vec<(String, usize)> = counts.into_iter().collect();
-    pairs.sort_by(|a, b| b.1.cmp(&a.1));
+    pairs.sort_by_key(|p| std::cmp::Reverse(p.1));
     let summary: Vec<String> = pairs
         .iter()
         .take(3)
@@ -294,7 +496,7 @@ fn summarize_licenses(deps: &[Dependency], verdict: &PolicyVerdict) -> String {
     }
 
     let mut pairs: Vec<(String, usize)> = counts.into_iter().collect();
-    pairs.sort_by(|a, b| b.1.cmp(&a.1));
+    pairs.sort_by_key(|p| std::cmp::Reverse(p.1));
 
     let summary: Vec<String> = pairs
         .iter()
@@ -308,3 +510,149 @@ fn summarize_licenses(deps: &[Dependency], verdict: &PolicyVerdict) -> String {
         format!("[{}]", summary.join(", "))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{dep, Ecosystem};
+
+    #[test]
+    fn test_min_risk_strong_copyleft_hides_permissive_and_weak() {
+        let deps = vec![
+            dep("mit", None, PolicyVerdict::Warn).with_risk(LicenseRisk::Permissive),
+            dep("lgpl", None, PolicyVerdict::Warn).with_risk(LicenseRisk::WeakCopyleft),
+            dep("gpl", None, PolicyVerdict::Warn).with_risk(LicenseRisk::StrongCopyleft),
+            dep("closed", None, PolicyVerdict::Warn).with_risk(LicenseRisk::Proprietary),
+            dep("mystery", None, PolicyVerdict::Warn).with_risk(LicenseRisk::Unknown),
+        ];
+        let names: Vec<&str> = visible_rows(
+            &deps,
+            &PolicyVerdict::Warn,
+            Some(&LicenseRisk::StrongCopyleft),
+        )
+        .iter()
+        .map(|d| d.name.as_str())
+        .collect();
+        assert_eq!(names, vec!["gpl", "closed", "mystery"]);
+    }
+
+    #[test]
+    fn test_no_min_risk_shows_all_rows_for_verdict() {
+        let deps = vec![
+            dep("mit", None, PolicyVerdict::Pass).with_risk(LicenseRisk::Permissive),
+            dep("gpl", None, PolicyVerdict::Error).with_risk(LicenseRisk::StrongCopyleft),
+        ];
+        assert_eq!(visible_rows(&deps, &PolicyVerdict::Pass, None).len(), 1);
+        assert_eq!(visible_rows(&deps, &PolicyVerdict::Error, None).len(), 1);
+    }
+
+    #[test]
+    fn test_group_by_ecosystem_splits_rows_and_counts() {
+        let deps = [
+            dep("serde", None, PolicyVerdict::Pass).with_ecosystem(Ecosystem::Rust),
+            dep("lodash", None, PolicyVerdict::Pass),
+            dep("lgpl-sys", None, PolicyVerdict::Warn).with_ecosystem(Ecosystem::Rust),
+            dep("gpl-js", None, PolicyVerdict::Error),
+        ];
+        let refs: Vec<&Dependency> = deps.iter().collect();
+
+        let groups = group_by_ecosystem(&refs);
+        let layout: Vec<(&str, Vec<&str>)> = groups
+            .iter()
+            .map(|(eco, group)| {
+                (
+                    eco.as_str(),
+                    group.iter().map(|d| d.name.as_str()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            layout,
+            [
+                ("Node", vec!["lodash", "gpl-js"]),
+                ("Rust", vec!["serde", "lgpl-sys"]),
+            ]
+        );
+
+        // Each section counts only its own rows
+        let node = &groups[0].1;
+        assert!(count_line(node).starts_with("Total: 2  Pass: "));
+        assert_eq!(
+            visible_rows(node.iter().copied(), &PolicyVerdict::Error, None).len(),
+            1
+        );
+        assert!(visible_rows(node.iter().copied(), &PolicyVerdict::Warn, None).is_empty());
+    }
+
+    #[test]
+    fn test_sort_by_verdict_most_severe_first_and_stable() {
+        let deps = [
+            dep("a-pass", None, PolicyVerdict::Pass).with_risk(LicenseRisk::Permissive),
+            dep("b-error", None, PolicyVerdict::Error).with_risk(LicenseRisk::StrongCopyleft),
+            dep("c-warn", None, PolicyVerdict::Warn).with_risk(LicenseRisk::WeakCopyleft),
+            dep("d-error", None, PolicyVerdict::Error).with_risk(LicenseRisk::Proprietary),
+            dep("e-review", None, PolicyVerdict::Review).with_risk(LicenseRisk::Unknown),
+        ];
+        let mut rows: Vec<&Dependency> = deps.iter().collect();
+        sort_rows(&mut rows, &SortKey::Verdict);
+        let names: Vec<&str> = rows.iter().map(|d| d.name.as_str()).collect();
+        // Equal verdicts keep their original order
+        assert_eq!(
+            names,
+            ["b-error", "d-error", "e-review", "c-warn", "a-pass"]
+        );
+
+        sort_rows(&mut rows, &SortKey::Risk);
+        let names: Vec<&str> = rows.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["d-error", "b-error", "c-warn", "a-pass", "e-review"]
+        );
+
+        sort_rows(&mut rows, &SortKey::Name);
+        let names: Vec<&str> = rows.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["a-pass", "b-error", "c-warn", "d-error", "e-review"]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_version_compares_numerically() {
+        let mut deps = [
+            dep("x", None, PolicyVerdict::Pass).with_risk(LicenseRisk::Permissive),
+            dep("y", None, PolicyVerdict::Pass).with_risk(LicenseRisk::Permissive),
+            dep("z", None, PolicyVerdict::Pass).with_risk(LicenseRisk::Permissive),
+        ];
+        deps[0].version = "1.10.0".to_string();
+        deps[1].version = "1.9.2".to_string();
+        deps[2].version = "0.3.0".to_string();
+        let mut rows: Vec<&Dependency> = deps.iter().collect();
+        sort_rows(&mut rows, &SortKey::Version);
+        let names: Vec<&str> = rows.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["z", "y", "x"]);
+    }
+
+    #[test]
+    fn test_table_with_reduced_columns() {
+        let mut gpl =
+            dep("gpl-crate", None, PolicyVerdict::Error).with_risk(LicenseRisk::StrongCopyleft);
+        gpl.license_spdx = Some("GPL-3.0".to_string());
+        let rows = [&gpl];
+
+        let table = build_table(&rows, &[Column::License, Column::Name]);
+        let header: Vec<String> = table
+            .header()
+            .unwrap()
+            .cell_iter()
+            .map(|c| c.content())
+            .collect();
+        assert_eq!(header, ["License", "Name"]);
+        let text = table.to_string();
+        assert!(text.contains("GPL-3.0") && text.contains("gpl-crate"));
+        assert!(!text.contains("1.0.0") && !text.contains("Rust"));
+
+        // No selection: every column
+        assert_eq!(build_table(&rows, &[]).header().unwrap().cell_count(), 6);
+    }
+}
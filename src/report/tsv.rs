@@ -0,0 +1,83 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::models::{Dependency, ProjectScan};
+
+/// One header-less line per dependency:
+/// `name\tversion\tecosystem\tlicense\tverdict`.
+///
+/// The license is the SPDX expression, else the raw license string, else
+/// `unknown`. Tabs and line breaks inside a field are replaced by spaces so
+/// every line has exactly five columns.
+pub fn to_tsv<'a>(deps: impl IntoIterator<Item = &'a Dependency>) -> String {
+    let mut out = String::new();
+    for dep in deps {
+        let license = dep
+            .license_spdx
+            .as_deref()
+            .or(dep.license_raw.as_deref())
+            .unwrap_or("unknown");
+        let fields = [
+            field(&dep.name),
+            field(&dep.version),
+            field(&dep.ecosystem.to_string()),
+            field(license),
+            dep.verdict.to_string(),
+        ];
+        out.push_str(&fields.join("\t"));
+        out.push('\n');
+    }
+    out
+}
+
+fn field(value: &str) -> String {
+    value.replace(['\t', '\r', '\n'], " ")
+}
+
+/// Print the TSV report to stdout.
+pub fn render(deps: &[Dependency]) {
+    print!("{}", to_tsv(deps));
+}
+
+/// Print the dependencies of every project as one TSV stream.
+pub fn render_workspace(projects: &[ProjectScan]) {
+    print!("{}", to_tsv(projects.iter().flat_map(|p| &p.deps)));
+}
+
+/// Write the [`render`] output to `path` instead of stdout.
+pub fn write(deps: &[Dependency], path: &Path) -> Result<()> {
+    std::fs::write(path, to_tsv(deps))
+        .with_context(|| format!("Failed to write TSV report to {}", path.display()))
+}
+
+/// Write the [`render_workspace`] output to `path` instead of stdout.
+pub fn write_workspace(projects: &[ProjectScan], path: &Path) -> Result<()> {
+    std::fs::write(path, to_tsv(projects.iter().flat_map(|p| &p.deps)))
+        .with_context(|| format!("Failed to write TSV report to {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{dep, PolicyVerdict};
+
+    #[test]
+    fn test_tsv_columns() {
+        let deps = vec![
+            dep("express", Some("MIT"), PolicyVerdict::Pass),
+            dep("dual", Some("MIT OR GPL-3.0"), PolicyVerdict::Warn),
+            dep("mystery", None, PolicyVerdict::Review),
+        ];
+        assert_eq!(
+            to_tsv(&deps),
+            "express\t1.0.0\tNode\tMIT\tpass\n\
+             dual\t1.0.0\tNode\tMIT OR GPL-3.0\twarn\n\
+             mystery\t1.0.0\tNode\tunknown\treview\n"
+        );
+
+        // Embedded tabs never add a column
+        let odd = dep("odd", Some("MIT\tsee file"), PolicyVerdict::Pass);
+        assert_eq!(to_tsv([&odd]).trim_end().split('\t').count(), 5);
+    }
+}
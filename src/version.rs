@@ -0,0 +1,38 @@
+//! Version ordering shared by registry lookups and reports.
+
+/// Compare dotted versions numerically where possible (`1.10.0` > `1.9.2`);
+/// `~` marks a pre-release that sorts before its release, as in opam.
+pub fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let key = |v: &str| {
+        let (release, pre) = match v.split_once('~') {
+            Some((release, pre)) => (release, Some(pre.to_string())),
+            None => (v, None),
+        };
+        let parts: Vec<(u64, String)> = release
+            .split(['.', '-', '+'])
+            .map(|part| {
+                let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+                (
+                    digits.parse().unwrap_or(0),
+                    part[digits.len()..].to_string(),
+                )
+            })
+            .collect();
+        (parts, pre.is_none(), pre)
+    };
+    key(a).cmp(&key(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn test_numeric_parts_and_prereleases() {
+        assert_eq!(compare_versions("1.10.0", "1.9.2"), Ordering::Greater);
+        assert_eq!(compare_versions("1.10.0~beta1", "1.10.0"), Ordering::Less);
+        assert_eq!(compare_versions("2.0.0-rc1", "2.0.0-rc2"), Ordering::Less);
+        assert_eq!(compare_versions("0.4.3", "0.4.3"), Ordering::Equal);
+    }
+}
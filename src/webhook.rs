@@ -0,0 +1,234 @@
+//! `--webhook`: POST a short JSON summary of the scan (e.g. to a Slack or
+//! ChatOps endpoint) once reports are rendered.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::models::{Dependency, PolicyVerdict};
+use crate::report::json::Counts;
+
+/// Body sent to the webhook.
+#[derive(Debug, Serialize)]
+pub struct Payload {
+    /// Scanned project or workspace root; `workspace` for several explicit paths.
+    pub project: String,
+    #[serde(flatten)]
+    pub counts: Counts,
+    /// Dependencies whose verdict fails the run (see `--fail-on`).
+    pub failures: Vec<Failure>,
+}
+
+/// One failing dependency in a [`Payload`].
+#[derive(Debug, Serialize)]
+pub struct Failure {
+    /// Sub-project the dependency belongs to (workspace mode only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    pub name: String,
+    pub version: String,
+    pub ecosystem: String,
+    pub license: String,
+    pub verdict: String,
+}
+
+impl Payload {
+    /// Build the payload from `(sub-project, dependency)` pairs; dependencies at or
+    /// above `fail_on` are listed as failures (none without a threshold, i.e. with
+    /// `--fail-on never`).
+    pub fn new<'a>(
+        project: &str,
+        deps: impl IntoIterator<Item = (Option<&'a str>, &'a Dependency)> + Clone,
+        scanned_at: &str,
+        fail_on: Option<&PolicyVerdict>,
+    ) -> Self {
+        let counts = Counts::from_deps(deps.clone().into_iter().map(|(_, d)| d), scanned_at);
+        let failures = deps
+            .into_iter()
+            .filter(|(_, d)| fail_on.is_some_and(|f| d.verdict.severity() >= f.severity()))
+            .map(|(project, d)| Failure {
+                project: project.map(str::to_string),
+                name: d.name.clone(),
+                version: d.version.clone(),
+                ecosystem: d.ecosystem.to_string(),
+                license: d
+                    .license_spdx
+                    .as_deref()
+                    .or(d.license_raw.as_deref())
+                    .unwrap_or("unknown")
+                    .to_string(),
+                verdict: d.verdict.to_string(),
+            })
+            .collect();
+        Payload {
+            project: project.to_string(),
+            counts,
+            failures,
+        }
+    }
+
+    /// Whether the webhook fires: only when something fails, unless `always`
+    /// (`--webhook-always`).
+    pub fn should_send(&self, always: bool) -> bool {
+        always || !self.failures.is_empty()
+    }
+}
+
+/// POST `payload` as JSON to `url`, failing on transport errors and non-2xx responses.
+///
+/// Incoming-webhook URLs carry their secret in the path, so errors name only the
+/// scheme and host (see [`redacted`]).
+pub async fn send(url: &str, payload: &Payload) -> Result<()> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()?;
+    client
+        .post(url)
+        .header(
+            "User-Agent",
+            concat!("license-checkr/", env!("CARGO_PKG_VERSION")),
+        )
+        .json(payload)
+        .send()
+        .await
+        .map_err(reqwest::Error::without_url)
+        .with_context(|| format!("Failed to reach webhook {}", redacted(url)))?
+        .error_for_status()
+        .map_err(reqwest::Error::without_url)
+        .with_context(|| format!("Webhook {} rejected the notification", redacted(url)))?;
+    Ok(())
+}
+
+/// `url` reduced to its scheme, host and port, e.g. `https://hooks.slack.com`.
+fn redacted(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(url) => match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}://{}:{}", url.scheme(), host, port),
+            (Some(host), None) => format!("{}://{}", url.scheme(), host),
+            (None, _) => format!("{}:", url.scheme()),
+        },
+        Err(_) => "(invalid URL)".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::dep;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Accept one HTTP request on a local port, answer with `status` (e.g.
+    /// `"204 No Content"`), and return its body.
+    async fn mock_server(status: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request).to_string();
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let length = head
+                        .lines()
+                        .find_map(|l| {
+                            l.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if body.len() >= length {
+                        let response = format!("HTTP/1.1 {}\r\nContent-Length: 0\r\n\r\n", status);
+                        socket.write_all(response.as_bytes()).await.unwrap();
+                        return body.to_string();
+                    }
+                }
+            }
+        });
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn test_payload_shape_posted_to_webhook() {
+        let deps = [
+            dep("express", Some("MIT"), PolicyVerdict::Pass),
+            dep("gpl-thing", Some("GPL-3.0"), PolicyVerdict::Error),
+        ];
+        let payload = Payload::new(
+            "web",
+            deps.iter().map(|d| (None, d)),
+            "2024-03-14T09:21:00Z",
+            Some(&PolicyVerdict::Error),
+        );
+        assert!(payload.should_send(false));
+
+        let (url, server) = mock_server("204 No Content").await;
+        send(&url, &payload).await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(&server.await.unwrap()).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "project": "web",
+                "scanned_at": "2024-03-14T09:21:00Z",
+                "total": 2, "pass": 1, "warn": 0, "review": 0, "error": 1,
+                "failures": [{
+                    "name": "gpl-thing",
+                    "version": "1.0.0",
+                    "ecosystem": "Node",
+                    "license": "GPL-3.0",
+                    "verdict": "error"
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_only_sent_on_failures_by_default() {
+        let deps = [
+            dep("express", Some("MIT"), PolicyVerdict::Pass),
+            dep("lgpl-thing", Some("LGPL-2.1"), PolicyVerdict::Warn),
+        ];
+        let payload = Payload::new(
+            "web",
+            deps.iter().map(|d| (Some("api"), d)),
+            "2024-03-14T09:21:00Z",
+            Some(&PolicyVerdict::Error),
+        );
+        assert!(payload.failures.is_empty());
+        assert!(!payload.should_send(false));
+        assert!(payload.should_send(true));
+    }
+
+    #[tokio::test]
+    async fn test_errors_do_not_leak_the_webhook_secret() {
+        let deps = [dep("gpl-thing", Some("GPL-3.0"), PolicyVerdict::Error)];
+        let payload = Payload::new(
+            "web",
+            deps.iter().map(|d| (None, d)),
+            "2024-03-14T09:21:00Z",
+            Some(&PolicyVerdict::Error),
+        );
+
+        let (url, server) = mock_server("403 Forbidden").await;
+        let secret_url = format!("{}/T000/B000/s3cr3t", url.trim_end_matches("/hook"));
+        let err = format!("{:#}", send(&secret_url, &payload).await.unwrap_err());
+        server.await.unwrap();
+        assert!(err.contains("403"), "{}", err);
+        assert!(!err.contains("s3cr3t"), "{}", err);
+
+        // Nothing listening: the transport error is redacted too
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        let err = format!(
+            "{:#}",
+            send(&format!("http://{}/T000/B000/s3cr3t", addr), &payload)
+                .await
+                .unwrap_err()
+        );
+        assert!(err.starts_with(&format!("Failed to reach webhook http://{}", addr)));
+        assert!(!err.contains("s3cr3t"), "{}", err);
+    }
+}